@@ -29,6 +29,20 @@ pub enum ReadError {
         location: &'static Location<'static>,
         remainder: Bytes,
     },
+    #[error = "expected the magic bytes {expected:?} but found {found:?}"]
+    BadMagic {
+        #[location]
+        location: &'static Location<'static>,
+        expected: &'static [u8],
+        found: Vec<u8>,
+    },
+    #[error = "the {what} is not supported: {reason}"]
+    Unsupported {
+        #[location]
+        location: &'static Location<'static>,
+        what: &'static str,
+        reason: String,
+    },
     #[error = "opcode {opcode} is not implemented"]
     OpcodeNotImplemented {
         opcode: u8,
@@ -171,6 +185,22 @@ pub trait BufExtra: Buf + Sized + Clone {
         Ok(ret)
     }
 
+    /// Reads one or two bytes as an 16-bit signed integer.
+    #[track_caller]
+    #[inline]
+    fn try_get_signed_smart(&mut self) -> Result<i16, ReadError> {
+        let first = *self.chunk().first().context(Eof {
+            expected: 1,
+            found: self.remaining(),
+        })?;
+        let ret = if first < 0x80 {
+            self.try_get_u8()? as i16 - 0x40
+        } else {
+            (self.try_get_u16()? as i32 - 0xC000) as i16
+        };
+        Ok(ret)
+    }
+
     /// Reads one or two unsigned bytes as an 16-bit unsigned integer.
     #[inline]
     fn get_unsigned_smart(&mut self) -> u16 {
@@ -192,6 +222,32 @@ pub trait BufExtra: Buf + Sized + Clone {
         }
     }
 
+    /// Reads Kind one or two bytes.
+    #[inline]
+    fn try_get_decr_smart(&mut self) -> Result<Option<u16>, ReadError> {
+        let ret = match self.try_get_u8()? as u16 {
+            first if first < 128 => first.checked_sub(1),
+            first => (first << 8 | self.try_get_u8()? as u16).checked_sub(0x8000).and_then(|value| value.checked_sub(1)),
+        };
+        Ok(ret)
+    }
+
+    /// Reads masked data.
+    #[inline]
+    fn try_get_masked_data(&mut self) -> Result<Vec<(Option<u32>, Option<u32>)>, ReadError> {
+        let mut result = Vec::new();
+        let mut mask = self.try_get_u8()?;
+        while mask > 0 {
+            if mask & 0x1 == 1 {
+                result.push((self.try_get_smart32()?, self.try_get_decr_smart()?.map(|c| c as u32)));
+            } else {
+                result.push((None, None));
+            }
+            mask /= 2;
+        }
+        Ok(result)
+    }
+
     /// Reads masked data.
     #[inline]
     fn get_masked_data(&mut self) -> Vec<(Option<u32>, Option<u32>)> {
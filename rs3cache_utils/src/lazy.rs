@@ -20,6 +20,14 @@ impl<A: Clone, T, E> Lazy<A, T, E> {
     pub fn get(&self) -> &Result<T, E> {
         self.ready.get_or_init(|| (self.f)(self.args.clone()))
     }
+
+    /// Takes the value, computing it if that has not happened yet.
+    pub fn into_inner(self) -> Result<T, E> {
+        match self.ready.into_inner() {
+            Some(ready) => ready,
+            None => (self.f)(self.args),
+        }
+    }
 }
 
 impl<A: Clone, T, E> Deref for Lazy<A, T, E> {
@@ -28,3 +36,10 @@ impl<A: Clone, T, E> Deref for Lazy<A, T, E> {
         self.get()
     }
 }
+
+/// The clone starts out unevaluated, and computes its value again when it is first used.
+impl<A: Clone, T, E> Clone for Lazy<A, T, E> {
+    fn clone(&self) -> Self {
+        Self::new(self.args.clone(), self.f)
+    }
+}
@@ -16,14 +16,17 @@ use crate::renderers::map;
 pub enum Render {
     All,
     Map,
+    #[cfg(feature = "rs3")]
+    Zones,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl Render {
     pub fn call(&self, config: &Config) -> CacheResult<()> {
         match self {
-            Render::All => map::render(config)?,
-            Render::Map => map::render(config)?,
+            Render::All | Render::Map => map::render(config)?,
+            #[cfg(feature = "rs3")]
+            Render::Zones => map::render_zones(config)?,
         };
 
         Ok(())
@@ -39,17 +42,29 @@ pub enum Dump {
     Music,
     #[cfg(feature = "rs3")]
     Achievements,
+    #[cfg(feature = "rs3")]
+    AchievementGraph,
+    #[cfg(feature = "rs3")]
+    Sounds,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    SynthSounds,
     Sprites,
     Locations,
     LocationsEach,
     TilesEach,
+    #[cfg(feature = "rs3")]
+    NpcsEach,
     LocationConfigs,
     LocationConfigsEach,
     NpcConfig,
     ItemConfigs,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    SpotAnimConfigs,
     Maplabels,
-    #[cfg(feature = "rs3")]
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
     Worldmaps,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    Geojson,
     VarbitConfigs,
     Structs,
     Enums,
@@ -57,8 +72,36 @@ pub enum Dump {
     Underlays,
     #[cfg(any(feature = "rs3", feature = "osrs"))]
     Overlays,
-    #[cfg(feature = "osrs")]
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
     Textures,
+    #[cfg(feature = "rs3")]
+    Quickchat,
+    #[cfg(feature = "rs3")]
+    Materials,
+    #[cfg(feature = "rs3")]
+    Defaults,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    IdentityKits,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    Inventories,
+    #[cfg(feature = "rs3")]
+    Cursors,
+    #[cfg(feature = "rs3")]
+    VarConfigs,
+    #[cfg(feature = "rs3")]
+    AreaConfigs,
+    #[cfg(feature = "rs3")]
+    Skyboxes,
+    #[cfg(feature = "rs3")]
+    LightConfigs,
+    #[cfg(feature = "rs3")]
+    Hitsplats,
+    #[cfg(feature = "rs3")]
+    Headbars,
+    #[cfg(feature = "rs3")]
+    Quests,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    Params,
 }
 
 impl Dump {
@@ -68,15 +111,30 @@ impl Dump {
             Dump::Music => definitions::music::export_each,
             #[cfg(feature = "rs3")]
             Dump::Achievements => definitions::achievements::export,
+            #[cfg(feature = "rs3")]
+            Dump::AchievementGraph => definitions::achievements::export_graph,
+            #[cfg(feature = "rs3")]
+            Dump::Sounds => definitions::sounds::export_vorbis,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::SynthSounds => definitions::sounds::export_synth,
             Dump::Sprites => definitions::sprites::save_all,
             Dump::TilesEach => definitions::mapsquares::export_tiles_by_square,
             Dump::Locations => definitions::mapsquares::export_locations_by_id,
             Dump::LocationsEach => definitions::mapsquares::export_locations_by_square,
+            #[cfg(feature = "rs3")]
+            Dump::NpcsEach => definitions::mapsquares::export_npcs_by_square,
             Dump::LocationConfigs => definitions::location_configs::export,
             Dump::LocationConfigsEach => definitions::location_configs::export_each,
             Dump::NpcConfig => definitions::npc_configs::export,
             Dump::ItemConfigs => definitions::item_configs::export,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::SpotAnimConfigs => definitions::spotanim_configs::export,
             Dump::Maplabels => definitions::maplabel_configs::export,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Geojson => |config| try {
+                definitions::maplabel_configs::export_geojson(config)?;
+                definitions::worldmaps::export_zones_geojson(config)?;
+            },
             #[cfg(feature = "rs3")]
             Dump::Worldmaps => |config| try {
                 definitions::worldmaps::dump_big(config)?;
@@ -84,6 +142,12 @@ impl Dump {
                 definitions::worldmaps::export_pastes(config)?;
                 definitions::worldmaps::export_zones(config)?;
             },
+            #[cfg(feature = "osrs")]
+            Dump::Worldmaps => |config| try {
+                definitions::worldmaps::dump_raw(config)?;
+                definitions::worldmaps::export_pastes(config)?;
+                definitions::worldmaps::export_zones(config)?;
+            },
             Dump::VarbitConfigs => definitions::varbit_configs::export,
             Dump::Structs => definitions::structs::export,
             Dump::Enums => definitions::enums::export,
@@ -91,8 +155,36 @@ impl Dump {
             Dump::Underlays => definitions::underlays::export,
             #[cfg(any(feature = "rs3", feature = "osrs"))]
             Dump::Overlays => definitions::overlays::export,
-            #[cfg(feature = "osrs")]
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
             Dump::Textures => definitions::textures::export,
+            #[cfg(feature = "rs3")]
+            Dump::Quickchat => definitions::quickchat::export,
+            #[cfg(feature = "rs3")]
+            Dump::Materials => definitions::materials::export,
+            #[cfg(feature = "rs3")]
+            Dump::Defaults => definitions::defaults::export,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::IdentityKits => definitions::identity_kits::export,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Inventories => definitions::inventories::export,
+            #[cfg(feature = "rs3")]
+            Dump::Cursors => definitions::cursors::export,
+            #[cfg(feature = "rs3")]
+            Dump::VarConfigs => definitions::var_configs::export,
+            #[cfg(feature = "rs3")]
+            Dump::AreaConfigs => definitions::area_configs::export,
+            #[cfg(feature = "rs3")]
+            Dump::Skyboxes => definitions::skyboxes::export,
+            #[cfg(feature = "rs3")]
+            Dump::LightConfigs => definitions::light_configs::export,
+            #[cfg(feature = "rs3")]
+            Dump::Hitsplats => definitions::hitsplats::export,
+            #[cfg(feature = "rs3")]
+            Dump::Headbars => definitions::headbars::export,
+            #[cfg(feature = "rs3")]
+            Dump::Quests => definitions::quests::export,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Params => definitions::params::export,
             Dump::All | Dump::Configs => |_| Ok(()),
         }
     }
@@ -103,17 +195,29 @@ impl Dump {
             Dump::Music => "music",
             #[cfg(feature = "rs3")]
             Dump::Achievements => "achievements",
+            #[cfg(feature = "rs3")]
+            Dump::AchievementGraph => "achievement_graph",
+            #[cfg(feature = "rs3")]
+            Dump::Sounds => "sounds",
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::SynthSounds => "synth_sounds",
             Dump::Sprites => "sprites",
             Dump::TilesEach => "tiles_by_square",
             Dump::Locations => "locations_by_id",
             Dump::LocationsEach => "locations_by_square",
+            #[cfg(feature = "rs3")]
+            Dump::NpcsEach => "npcs_each",
             Dump::LocationConfigs => "location_configs",
             Dump::LocationConfigsEach => "location_configs_each",
             Dump::NpcConfig => "npc_configs",
             Dump::ItemConfigs => "item_configs",
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::SpotAnimConfigs => "spotanim_configs",
             Dump::Maplabels => "maplabel_configs",
-            #[cfg(feature = "rs3")]
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
             Dump::Worldmaps => "world_maps",
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Geojson => "geojson",
             Dump::VarbitConfigs => "varbit_configs",
             Dump::Structs => "structs",
             Dump::Enums => "enums",
@@ -121,8 +225,36 @@ impl Dump {
             Dump::Underlays => "underlays",
             #[cfg(any(feature = "rs3", feature = "osrs"))]
             Dump::Overlays => "overlays",
-            #[cfg(feature = "osrs")]
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
             Dump::Textures => "textures",
+            #[cfg(feature = "rs3")]
+            Dump::Quickchat => "quickchat",
+            #[cfg(feature = "rs3")]
+            Dump::Materials => "materials",
+            #[cfg(feature = "rs3")]
+            Dump::Defaults => "defaults",
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::IdentityKits => "identity_kits",
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Inventories => "inventories",
+            #[cfg(feature = "rs3")]
+            Dump::Cursors => "cursors",
+            #[cfg(feature = "rs3")]
+            Dump::VarConfigs => "var_configs",
+            #[cfg(feature = "rs3")]
+            Dump::AreaConfigs => "area_configs",
+            #[cfg(feature = "rs3")]
+            Dump::Skyboxes => "skyboxes",
+            #[cfg(feature = "rs3")]
+            Dump::LightConfigs => "light_configs",
+            #[cfg(feature = "rs3")]
+            Dump::Hitsplats => "hitsplats",
+            #[cfg(feature = "rs3")]
+            Dump::Headbars => "headbars",
+            #[cfg(feature = "rs3")]
+            Dump::Quests => "quests",
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Params => "params",
             Dump::All => "all",
             Dump::Configs => "configs",
         }
@@ -135,6 +267,8 @@ impl Dump {
             Dump::LocationConfigs,
             Dump::NpcConfig,
             Dump::ItemConfigs,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::SpotAnimConfigs,
             Dump::Maplabels,
             Dump::VarbitConfigs,
             Dump::Structs,
@@ -145,6 +279,34 @@ impl Dump {
             Dump::Overlays,
             #[cfg(feature = "osrs")]
             Dump::Textures,
+            #[cfg(feature = "rs3")]
+            Dump::Quickchat,
+            #[cfg(feature = "rs3")]
+            Dump::Materials,
+            #[cfg(feature = "rs3")]
+            Dump::Defaults,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::IdentityKits,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Inventories,
+            #[cfg(feature = "rs3")]
+            Dump::Cursors,
+            #[cfg(feature = "rs3")]
+            Dump::VarConfigs,
+            #[cfg(feature = "rs3")]
+            Dump::AreaConfigs,
+            #[cfg(feature = "rs3")]
+            Dump::Skyboxes,
+            #[cfg(feature = "rs3")]
+            Dump::LightConfigs,
+            #[cfg(feature = "rs3")]
+            Dump::Hitsplats,
+            #[cfg(feature = "rs3")]
+            Dump::Headbars,
+            #[cfg(feature = "rs3")]
+            Dump::Quests,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            Dump::Params,
         ]
     }
 }
@@ -183,10 +345,32 @@ pub struct Config {
     #[clap(value_enum, long, num_args(..))]
     pub render: Vec<Render>,
 
+    /// Draws the text of map labels onto the rendered map.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[clap(long)]
+    pub render_labels: bool,
+
+    /// Also renders the underwater tiles, as a separate `mapsquares_water` map (rs3 only).
+    #[cfg(not(target_arch = "wasm32"))]
+    #[clap(long)]
+    pub render_water: bool,
+
     /// Dumps the given archives.
     #[clap(value_enum, long, num_args(..))]
     pub dump: Vec<Dump>,
 
+    /// Interprets the values of param tables according to their type when dumping configs.
+    #[clap(long)]
+    pub resolve_params: bool,
+
+    /// Copies the properties of linked items into noted, lent, bound and placeholder items when dumping item configs.
+    #[clap(long)]
+    pub resolve_items: bool,
+
+    /// Includes the names of the items, npcs and locations that enum values refer to when dumping enums.
+    #[clap(long)]
+    pub resolve_enums: bool,
+
     /// Checks whether the cache is in a consistent state.
     /// Indices 14, 40, 54, 55 are not necessarily complete.
     #[clap(long)]
@@ -1,6 +1,6 @@
 //! Describes the properties of Achievements.
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fs::{self, File},
     io::Write,
     iter,
@@ -18,7 +18,7 @@ use rs3cache_backend::{
 };
 use serde::Serialize;
 
-use crate::definitions::indextype::IndexType;
+use crate::definitions::{indextype::IndexType, quests::QuestConfig};
 /// Describes the properties of a given Achievement.
 
 #[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
@@ -251,6 +251,294 @@ impl Achievement {
             }
         }
     }
+
+    /// Collects the requirements spread over this achievement's fields into one [`Requirement`].
+    pub fn requirements(&self) -> Requirement {
+        let mut requirements = Vec::new();
+
+        let achievements = self.previous_achievements.iter().chain(&self.sub_achievements).flatten();
+        requirements.extend(achievements.map(|&id| Requirement::Achievement { id }));
+
+        let quests = self.required_quest_ids.iter().chain(&self.quest_req_for_miniquests).flatten();
+        requirements.extend(quests.map(|&id| Requirement::Quest { id }));
+
+        requirements.extend(self.skill_requirements_1.iter().flatten().map(|req| Requirement::Skill {
+            skill: req.skill,
+            level: req.level,
+            ironman: req.is_ironman,
+        }));
+        requirements.extend(self.skill_requirements_2.iter().flatten().map(|req| Requirement::Skill {
+            skill: req.skill,
+            level: req.level,
+            ironman: false,
+        }));
+
+        let varbits = self.unknown_9.iter().chain(&self.unknown_10).flatten();
+        requirements.extend(varbits.map(|req| Requirement::Varbits {
+            varbits: req.varbit.val.into_iter().collect(),
+            value: req.value,
+            description: req.description.clone(),
+        }));
+        let varbits = self.unknown_13.iter().chain(&self.subreqs_14).flatten();
+        requirements.extend(varbits.map(|req| Requirement::Varbits {
+            varbits: req.varbits.iter().filter_map(|varbit| varbit.val).collect(),
+            value: req.value,
+            description: req.description.clone(),
+        }));
+        let varbits = self.reqs_23.iter().chain(&self.reqs_25).flatten();
+        requirements.extend(varbits.map(|req| Requirement::Varbits {
+            varbits: req.varbit.val.into_iter().collect(),
+            value: req.value as u32,
+            description: req.description.clone(),
+        }));
+
+        Requirement::All { requirements }
+    }
+}
+
+/// A requirement of an [`Achievement`], see [`Achievement::requirements`].
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Requirement {
+    /// All of these must be met.
+    All { requirements: Vec<Requirement> },
+    /// Another achievement must be completed.
+    Achievement { id: u32 },
+    /// A quest must be completed.
+    Quest { id: u32 },
+    /// A skill must be at least `level`.
+    Skill { skill: u16, level: u8, ironman: bool },
+    /// The varbits must reach `value`.
+    Varbits {
+        varbits: Vec<u16>,
+        value: u32,
+        description: JString<Bytes>,
+    },
+}
+
+impl Requirement {
+    /// Iterates over the requirements that are not [`Requirement::All`].
+    pub fn leaves(&self) -> Box<dyn Iterator<Item = &Requirement> + '_> {
+        match self {
+            Self::All { requirements } => Box::new(requirements.iter().flat_map(Self::leaves)),
+            other => Box::new(iter::once(other)),
+        }
+    }
+}
+
+/// Something an [`Achievement`] can depend on.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum RequirementNode {
+    Achievement(u32),
+    Quest(u32),
+    Skill(u16),
+    Varbit(u16),
+}
+
+impl Display for RequirementNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Achievement(id) => write!(f, "achievement_{id}"),
+            Self::Quest(id) => write!(f, "quest_{id}"),
+            Self::Skill(id) => write!(f, "skill_{id}"),
+            Self::Varbit(id) => write!(f, "varbit_{id}"),
+        }
+    }
+}
+
+/// A node of a [`RequirementGraph`], with its name if it has one.
+#[derive(Serialize, Debug, Clone)]
+pub struct GraphNode {
+    #[serde(flatten)]
+    pub node: RequirementNode,
+    pub name: Option<JString<Bytes>>,
+}
+
+/// An edge of a [`RequirementGraph`]: achievement `to` requires `from`.
+#[derive(Serialize, Debug, Clone)]
+pub struct GraphEdge {
+    pub from: RequirementNode,
+    pub to: u32,
+    /// Describes the requirement, like the level of a skill.
+    pub label: Option<String>,
+}
+
+/// The requirements of all achievements, as a dependency graph.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct RequirementGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Groups of achievements that require each other.
+    pub cycles: Vec<Vec<u32>>,
+    /// Achievements that can never be completed,
+    /// because they require an achievement that does not exist or that is part of a cycle.
+    pub unreachable: Vec<u32>,
+}
+
+impl RequirementGraph {
+    /// Links `achievements` to the achievements, quests, skills and varbits they require.
+    pub fn new(achievements: &BTreeMap<u32, Achievement>, quests: &BTreeMap<u32, QuestConfig>) -> Self {
+        let mut nodes = BTreeMap::new();
+        let mut edges = Vec::new();
+        let mut dependencies = BTreeMap::<u32, Vec<u32>>::new();
+
+        for achievement in achievements.values() {
+            nodes.insert(RequirementNode::Achievement(achievement.id), achievement.name.clone());
+            let deps = dependencies.entry(achievement.id).or_default();
+
+            let requirements = achievement.requirements();
+            for requirement in requirements.leaves() {
+                let mut add = |from: RequirementNode, name: Option<JString<Bytes>>, label: Option<String>| {
+                    nodes.entry(from).or_insert(name);
+                    edges.push(GraphEdge {
+                        from,
+                        to: achievement.id,
+                        label,
+                    });
+                };
+                match requirement {
+                    Requirement::All { .. } => unreachable!("leaves does not yield Requirement::All"),
+                    &Requirement::Achievement { id } => {
+                        deps.push(id);
+                        add(
+                            RequirementNode::Achievement(id),
+                            achievements.get(&id).and_then(|ach| ach.name.clone()),
+                            None,
+                        );
+                    }
+                    &Requirement::Quest { id } => add(RequirementNode::Quest(id), quests.get(&id).and_then(|quest| quest.name.clone()), None),
+                    &Requirement::Skill { skill, level, ironman } => {
+                        let label = if ironman { format!("level {level} (ironman)") } else { format!("level {level}") };
+                        add(RequirementNode::Skill(skill), None, Some(label));
+                    }
+                    Requirement::Varbits { varbits, value, .. } => {
+                        for &varbit in varbits {
+                            add(RequirementNode::Varbit(varbit), None, Some(value.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+
+        let cycles = find_cycles(&dependencies);
+
+        let mut completable = BTreeSet::new();
+        loop {
+            let count = completable.len();
+            for (&id, deps) in &dependencies {
+                if !completable.contains(&id) && deps.iter().all(|dep| completable.contains(dep)) {
+                    completable.insert(id);
+                }
+            }
+            if completable.len() == count {
+                break;
+            }
+        }
+        let unreachable = dependencies.keys().copied().filter(|id| !completable.contains(id)).collect();
+
+        Self {
+            nodes: nodes.into_iter().map(|(node, name)| GraphNode { node, name }).collect(),
+            edges,
+            cycles,
+            unreachable,
+        }
+    }
+
+    /// Renders the graph in the Graphviz dot format.
+    ///
+    /// Unreachable achievements are coloured red.
+    pub fn to_dot(&self) -> String {
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+
+        let mut dot = String::from("digraph achievements {\n");
+        for GraphNode { node, name } in &self.nodes {
+            let label = match name {
+                Some(name) => escape(name),
+                None => node.to_string().replace('_', " "),
+            };
+            let shape = match node {
+                RequirementNode::Achievement(_) => "box",
+                RequirementNode::Quest(_) => "ellipse",
+                RequirementNode::Skill(_) => "diamond",
+                RequirementNode::Varbit(_) => "note",
+            };
+            let colour = match node {
+                RequirementNode::Achievement(id) if self.unreachable.binary_search(id).is_ok() => ", color=red",
+                _ => "",
+            };
+            dot.push_str(&format!("    {node} [label=\"{label}\", shape={shape}{colour}];\n"));
+        }
+        for GraphEdge { from, to, label } in &self.edges {
+            let to = RequirementNode::Achievement(*to);
+            match label {
+                Some(label) => dot.push_str(&format!("    {from} -> {to} [label=\"{}\"];\n", escape(label))),
+                None => dot.push_str(&format!("    {from} -> {to};\n")),
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Finds the strongly connected components of `graph` that contain a cycle, using Tarjan's algorithm.
+fn find_cycles(graph: &BTreeMap<u32, Vec<u32>>) -> Vec<Vec<u32>> {
+    #[derive(Default)]
+    struct State {
+        next_index: u32,
+        indices: BTreeMap<u32, u32>,
+        low_links: BTreeMap<u32, u32>,
+        stack: Vec<u32>,
+        on_stack: BTreeSet<u32>,
+        cycles: Vec<Vec<u32>>,
+    }
+
+    fn visit(node: u32, graph: &BTreeMap<u32, Vec<u32>>, state: &mut State) {
+        state.indices.insert(node, state.next_index);
+        state.low_links.insert(node, state.next_index);
+        state.next_index += 1;
+        state.stack.push(node);
+        state.on_stack.insert(node);
+
+        for &next in graph.get(&node).into_iter().flatten() {
+            if !graph.contains_key(&next) {
+                continue;
+            }
+            if !state.indices.contains_key(&next) {
+                visit(next, graph, state);
+                let low_link = state.low_links[&node].min(state.low_links[&next]);
+                state.low_links.insert(node, low_link);
+            } else if state.on_stack.contains(&next) {
+                let low_link = state.low_links[&node].min(state.indices[&next]);
+                state.low_links.insert(node, low_link);
+            }
+        }
+
+        if state.low_links[&node] == state.indices[&node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack.remove(&member);
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            let is_cycle = component.len() > 1 || graph[&node].contains(&node);
+            if is_cycle {
+                component.sort_unstable();
+                state.cycles.push(component);
+            }
+        }
+    }
+
+    let mut state = State::default();
+    for &node in graph.keys() {
+        if !state.indices.contains_key(&node) {
+            visit(node, graph, &mut state);
+        }
+    }
+    state.cycles.sort_unstable();
+    state.cycles
 }
 
 pub mod achievement_fields_impl {
@@ -369,9 +657,9 @@ pub mod achievement_fields_impl {
     #[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
     #[derive(Serialize, Debug, Clone)]
     pub struct MultipleVarbitsRequirement {
-        value: u32,
-        description: JString<Bytes>,
-        varbits: Vec<Varbit>,
+        pub value: u32,
+        pub description: JString<Bytes>,
+        pub varbits: Vec<Varbit>,
     }
 
     impl MultipleVarbitsRequirement {
@@ -414,10 +702,10 @@ pub mod achievement_fields_impl {
     #[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
     #[derive(Serialize, Debug, Clone)]
     pub struct PackedVarbitRequirement {
-        value: u8,
-        value2: u8,
-        description: JString<Bytes>,
-        varbit: Varbit,
+        pub value: u8,
+        pub value2: u8,
+        pub description: JString<Bytes>,
+        pub varbit: Varbit,
     }
 
     impl PackedVarbitRequirement {
@@ -466,6 +754,16 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     let mut achievement_configs = Achievement::dump_all(config)?.into_values().collect::<Vec<_>>();
     achievement_configs.sort_unstable_by_key(|loc| loc.id);
 
+    let quests = QuestConfig::dump_all(config)?;
+    let quest_fields = [
+        ("/quest_req_for_miniquests", "quest_req_for_miniquests_names"),
+        ("/required_quest_ids", "required_quest_names"),
+    ];
+    let achievement_configs = achievement_configs
+        .iter()
+        .map(|achievement| QuestConfig::annotate(achievement, &quest_fields, &quests))
+        .collect::<Vec<_>>();
+
     let path = path!(config.output / "achievements.json");
     let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
     let data = serde_json::to_string_pretty(&achievement_configs).unwrap();
@@ -473,6 +771,25 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
 
     Ok(())
 }
+
+/// Save the requirements of all achievements as `achievement_graph.json` and `achievement_graph.dot`. Exposed as `--dump achievement_graph`.
+pub fn export_graph(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let achievements = Achievement::dump_all(config)?;
+    let quests = QuestConfig::dump_all(config)?;
+    let graph = RequirementGraph::new(&achievements, &quests);
+
+    let path = path!(config.output / "achievement_graph.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&graph).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    let path = path!(config.output / "achievement_graph.dot");
+    fs::write(&path, graph.to_dot()).context(error::Io { path })?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,4 +803,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn cycles_are_unreachable() {
+        let achievement = |id, previous: Vec<u32>| {
+            (
+                id,
+                Achievement {
+                    id,
+                    previous_achievements: Some(previous),
+                    ..Default::default()
+                },
+            )
+        };
+        let achievements = BTreeMap::from([
+            achievement(1, vec![2]),
+            achievement(2, vec![1]),
+            achievement(3, vec![1]),
+            achievement(4, vec![]),
+            achievement(5, vec![4]),
+            achievement(6, vec![404]),
+        ]);
+        let graph = RequirementGraph::new(&achievements, &BTreeMap::new());
+
+        assert_eq!(graph.cycles, vec![vec![1, 2]]);
+        assert_eq!(graph.unreachable, vec![1, 2, 3, 6]);
+    }
 }
@@ -0,0 +1,195 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::{
+    definitions::{
+        indextype::{ConfigType, IndexType},
+        maplabel_configs::maplabel_config_fields::{Polygon, Toggle},
+    },
+    structures::paramtable::ParamTable,
+};
+
+/// Describes an area of the world map, such as a town, or an icon on it.
+///
+/// Newer world maps use [`MapLabelConfig`](crate::definitions::maplabel_configs::MapLabelConfig)s instead.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct AreaConfig {
+    /// Its id.
+    pub id: u32,
+    /// The sprite shown on the map.
+    pub sprite: Option<u32>,
+    /// The sprite shown on the map on mouseover.
+    pub hover_sprite: Option<u32>,
+    /// The name of the area.
+    pub name: Option<JString<Bytes>>,
+    /// Text colour.
+    pub text_colour: Option<[u8; 3]>,
+    /// Text colour on mouseover.
+    pub hover_text_colour: Option<[u8; 3]>,
+    /// Font size ( any of 0, 1, 2, 3), if the area has a name.
+    pub font_size: Option<u8>,
+    pub unknown_7: Option<u8>,
+    pub unknown_8: Option<u8>,
+    /// Controls whether the area is shown.
+    pub toggle_1: Option<Toggle>,
+    /// Text shown when the area is rightclicked.
+    pub options: Option<[Option<JString<Bytes>>; 5]>,
+    /// Describes the polygon drawn on the map, if present.
+    pub polygon: Option<Polygon>,
+    pub option_base: Option<JString<Bytes>>,
+    pub category: Option<u16>,
+    /// Also controls whether the area is shown.
+    pub toggle_2: Option<Toggle>,
+    pub unknown_21: Option<[u8; 4]>,
+    pub unknown_22: Option<[u8; 4]>,
+    pub unknown_23: Option<[u8; 3]>,
+    pub unknown_24: Option<[i16; 2]>,
+    /// The sprite shown on the map behind the main sprite.
+    pub background_sprite: Option<u32>,
+    pub unknown_28: Option<u8>,
+    /// Horizontal alignment of the text.
+    pub horizontal_alignment: Option<u8>,
+    /// Vertical alignment of the text.
+    pub vertical_alignment: Option<u8>,
+    #[serde(flatten)]
+    pub params: Option<ParamTable>,
+}
+
+impl AreaConfig {
+    /// Returns a mapping of all [`AreaConfig`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let areas = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::AREA)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "area configs" })?;
+        Ok(areas)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut area = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(area);
+                        }
+                    }
+                    1 => area.sprite = buffer.try_get_smart32()?,
+                    2 => area.hover_sprite = buffer.try_get_smart32()?,
+                    3 => area.name = Some(buffer.try_get_string()?),
+                    4 => area.text_colour = Some(buffer.try_get_array()?),
+                    5 => area.hover_text_colour = Some(buffer.try_get_array()?),
+                    6 => area.font_size = Some(buffer.try_get_u8()?),
+                    7 => area.unknown_7 = Some(buffer.try_get_u8()?),
+                    8 => area.unknown_8 = Some(buffer.try_get_u8()?),
+                    9 => area.toggle_1 = Some(Toggle::deserialize(&mut buffer)?),
+                    opcode @ 10..=14 => area.options.get_or_insert_default()[opcode as usize - 10] = Some(buffer.try_get_string()?),
+                    15 => area.polygon = Some(Polygon::deserialize(&mut buffer)?),
+                    17 => area.option_base = Some(buffer.try_get_string()?),
+                    19 => area.category = Some(buffer.try_get_u16()?),
+                    20 => area.toggle_2 = Some(Toggle::deserialize(&mut buffer)?),
+                    21 => area.unknown_21 = Some(buffer.try_get_array()?),
+                    22 => area.unknown_22 = Some(buffer.try_get_array()?),
+                    23 => area.unknown_23 = Some(buffer.try_get_array()?),
+                    24 => area.unknown_24 = Some([buffer.try_get_u16()? as i16, buffer.try_get_u16()? as i16]),
+                    25 => area.background_sprite = buffer.try_get_smart32()?,
+                    28 => area.unknown_28 = Some(buffer.try_get_u8()?),
+                    29 => area.horizontal_alignment = Some(buffer.try_get_u8()?),
+                    30 => area.vertical_alignment = Some(buffer.try_get_u8()?),
+                    249 => area.params = Some(ParamTable::deserialize(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: area.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for AreaConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl AreaConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("AreaConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("AreaConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the area configs as `area_configs.json`. Exposed as `--dump area_configs`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let areas = AreaConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "area_configs.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&areas).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_area_configs() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let areas = AreaConfig::dump_all(&config)?;
+        assert!(areas.values().any(|area| area.name.is_some()));
+        Ok(())
+    }
+}
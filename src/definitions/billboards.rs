@@ -0,0 +1,127 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::IndexType;
+
+/// A textured quad that always faces the camera, which can be attached to models.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct BillboardConfig {
+    /// Its id.
+    pub id: u32,
+    /// The texture drawn on the billboard.
+    pub texture: Option<u16>,
+    /// How the billboard is blended with what is behind it.
+    pub blend_mode: Option<u8>,
+    /// The width and height of the billboard.
+    pub size: Option<[u16; 2]>,
+    pub unknown_4: Option<i8>,
+    pub unknown_5: Option<u8>,
+    pub unknown_6: Option<bool>,
+}
+
+impl BillboardConfig {
+    /// Returns a mapping of all [`BillboardConfig`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let billboards = CacheIndex::new(IndexType::BILLBOARDS, config.input.clone())?
+            .archive(0)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "billboards" })?;
+        Ok(billboards)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut billboard = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(billboard);
+                        }
+                    }
+                    1 => billboard.texture = Some(buffer.try_get_u16()?),
+                    2 => billboard.blend_mode = Some(buffer.try_get_u8()?),
+                    3 => billboard.size = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]),
+                    4 => billboard.unknown_4 = Some(buffer.try_get_i8()?),
+                    5 => billboard.unknown_5 = Some(buffer.try_get_u8()?),
+                    6 => billboard.unknown_6 = Some(true),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: billboard.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for BillboardConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl BillboardConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("BillboardConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("BillboardConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_billboards() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let billboards = BillboardConfig::dump_all(&config)?;
+        assert!(billboards.values().any(|billboard| billboard.texture.is_some()));
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::{ConfigType, IndexType};
+
+/// Describes a mouse cursor.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Cursor {
+    /// Its id.
+    pub id: u32,
+    /// The sprite drawn as the cursor.
+    pub sprite: Option<u32>,
+    /// The point of the sprite that is considered to be the cursor's position.
+    pub hotspot: Option<[u8; 2]>,
+}
+
+impl Cursor {
+    /// Returns a mapping of all [`Cursor`] configurations.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let cursors = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::CURSORS)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "cursors" })?;
+        Ok(cursors)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut cursor = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(cursor);
+                        }
+                    }
+                    1 => cursor.sprite = buffer.try_get_smart32()?,
+                    2 => cursor.hotspot = Some([buffer.try_get_u8()?, buffer.try_get_u8()?]),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: cursor.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl Cursor {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Cursor({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("Cursor({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the cursors as `cursors.json`. Exposed as `--dump cursors`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let cursors = Cursor::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "cursors.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&cursors).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_cursors() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let cursors = Cursor::dump_all(&config)?;
+        assert!(cursors.values().any(|cursor| cursor.sprite.is_some()));
+        Ok(())
+    }
+}
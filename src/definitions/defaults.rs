@@ -0,0 +1,490 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::IndexType;
+
+/// The archives of the [`DEFAULTS`](IndexType::DEFAULTS) index.
+pub struct DefaultsGroup;
+
+impl DefaultsGroup {
+    /// Contains the [`GraphicsDefaults`].
+    pub const GRAPHICS: u32 = 3;
+    /// Contains the [`AudioDefaults`].
+    pub const AUDIO: u32 = 4;
+    /// Contains the [`CameraDefaults`].
+    pub const CAMERA: u32 = 5;
+    /// Contains the [`WearposDefaults`].
+    pub const WEARPOS: u32 = 6;
+}
+
+/// Reads the file of a defaults group, if the cache has it.
+fn group_file(group: u32, config: &crate::cli::Config) -> CacheResult<Option<Bytes>> {
+    let index = CacheIndex::new(IndexType::DEFAULTS, config.input.clone())?;
+    if !index.metadatas().keys().any(|&archive_id| archive_id == group) {
+        return Ok(None);
+    }
+    Ok(index.archive(group)?.file(&0))
+}
+
+/// Reads a list of slots, prefixed by its length.
+fn read_slots(buffer: &mut Bytes) -> Result<Vec<u8>, ReadError> {
+    let count = buffer.try_get_u8()?;
+    (0..count).map(|_| buffer.try_get_u8()).collect()
+}
+
+/// Client-wide graphics settings.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct GraphicsDefaults {
+    /// The sprites used for hitsplats.
+    pub hitsplat_sprites: Option<Vec<u32>>,
+    /// The default brightness, from 1 to 4.
+    pub brightness: Option<u8>,
+    /// The textures the client preloads.
+    pub preloaded_textures: Option<Vec<u16>>,
+    pub unknown_4: Option<u16>,
+    pub unknown_5: Option<bool>,
+}
+
+impl GraphicsDefaults {
+    /// Loads the [`GraphicsDefaults`], or their defaults if the cache does not have them.
+    pub fn load(config: &crate::cli::Config) -> CacheResult<Self> {
+        let Some(file) = group_file(DefaultsGroup::GRAPHICS, config)? else { return Ok(Self::default()) };
+        Self::deserialize(file).context(error::Read { what: "graphics defaults" })
+    }
+
+    fn deserialize(mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut graphics = Self::default();
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(graphics);
+                        }
+                    }
+                    1 => {
+                        let count = buffer.try_get_u8()?;
+                        let sprites = (0..count).map(|_| buffer.try_get_smart32().map(Option::unwrap_or_default));
+                        graphics.hitsplat_sprites = Some(sprites.collect::<Result<_, _>>()?);
+                    }
+                    2 => graphics.brightness = Some(buffer.try_get_u8()?),
+                    3 => {
+                        let count = buffer.try_get_u16()?;
+                        graphics.preloaded_textures = Some((0..count).map(|_| buffer.try_get_u16()).collect::<Result<_, _>>()?);
+                    }
+                    4 => graphics.unknown_4 = Some(buffer.try_get_u16()?),
+                    5 => graphics.unknown_5 = Some(true),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: graphics.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Client-wide audio settings.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct AudioDefaults {
+    /// The music track played on the login screen.
+    pub theme_music: Option<u32>,
+    /// The default music, sound effect and ambient sound volumes, from 0 to 255.
+    pub volumes: Option<[u8; 3]>,
+    pub unknown_3: Option<u16>,
+}
+
+impl AudioDefaults {
+    /// Loads the [`AudioDefaults`], or their defaults if the cache does not have them.
+    pub fn load(config: &crate::cli::Config) -> CacheResult<Self> {
+        let Some(file) = group_file(DefaultsGroup::AUDIO, config)? else { return Ok(Self::default()) };
+        Self::deserialize(file).context(error::Read { what: "audio defaults" })
+    }
+
+    fn deserialize(mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut audio = Self::default();
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(audio);
+                        }
+                    }
+                    1 => audio.theme_music = buffer.try_get_smart32()?,
+                    2 => audio.volumes = Some([buffer.try_get_u8()?, buffer.try_get_u8()?, buffer.try_get_u8()?]),
+                    3 => audio.unknown_3 = Some(buffer.try_get_u16()?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: audio.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Client-wide camera settings.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct CameraDefaults {
+    /// The minimum and maximum pitch of the camera.
+    pub pitch: Option<[u16; 2]>,
+    /// The minimum and maximum zoom of the camera.
+    pub zoom: Option<[u16; 2]>,
+    /// The default field of view.
+    pub fov: Option<u16>,
+    pub unknown_4: Option<u16>,
+}
+
+impl CameraDefaults {
+    /// Loads the [`CameraDefaults`], or their defaults if the cache does not have them.
+    pub fn load(config: &crate::cli::Config) -> CacheResult<Self> {
+        let Some(file) = group_file(DefaultsGroup::CAMERA, config)? else { return Ok(Self::default()) };
+        Self::deserialize(file).context(error::Read { what: "camera defaults" })
+    }
+
+    fn deserialize(mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut camera = Self::default();
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(camera);
+                        }
+                    }
+                    1 => camera.pitch = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]),
+                    2 => camera.zoom = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]),
+                    3 => camera.fov = Some(buffer.try_get_u16()?),
+                    4 => camera.unknown_4 = Some(buffer.try_get_u16()?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: camera.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Describes the equipment slots, as used by [`ItemConfig`](crate::definitions::item_configs::ItemConfig)s.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct WearposDefaults {
+    /// How each slot is hidden when rendering the player; the length is the number of slots.
+    pub slots: Vec<u8>,
+    /// The slot holding the weapon.
+    pub weapon_slot: Option<u8>,
+    /// The slot holding the shield or offhand weapon.
+    pub offhand_slot: Option<u8>,
+    /// Slots that are hidden when a weapon is wielded in both hands.
+    pub two_handed_hidden: Option<Vec<u8>>,
+    /// Slots that are hidden when the player is transformed.
+    pub transform_hidden: Option<Vec<u8>>,
+}
+
+impl WearposDefaults {
+    /// Loads the [`WearposDefaults`], or their defaults if the cache does not have them.
+    pub fn load(config: &crate::cli::Config) -> CacheResult<Self> {
+        let Some(file) = group_file(DefaultsGroup::WEARPOS, config)? else { return Ok(Self::default()) };
+        Self::deserialize(file).context(error::Read { what: "wearpos defaults" })
+    }
+
+    fn deserialize(mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut wearpos = Self::default();
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(wearpos);
+                        }
+                    }
+                    1 => wearpos.slots = read_slots(&mut buffer)?,
+                    3 => wearpos.weapon_slot = Some(buffer.try_get_u8()?),
+                    4 => wearpos.offhand_slot = Some(buffer.try_get_u8()?),
+                    5 => wearpos.two_handed_hidden = Some(read_slots(&mut buffer)?),
+                    6 => wearpos.transform_hidden = Some(read_slots(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: wearpos.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Returns the name of a slot, or `None` if the cache does not say what the slot is for.
+    ///
+    /// The cache only numbers the slots, and only marks which of them hold the weapon and offhand.
+    pub fn slot_name(&self, slot: u8) -> Option<&'static str> {
+        if self.weapon_slot == Some(slot) {
+            Some("weapon")
+        } else if self.offhand_slot == Some(slot) {
+            Some("offhand")
+        } else {
+            None
+        }
+    }
+}
+
+/// All defaults groups.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Defaults {
+    pub graphics: GraphicsDefaults,
+    pub audio: AudioDefaults,
+    pub camera: CameraDefaults,
+    pub wearpos: WearposDefaults,
+}
+
+impl Defaults {
+    /// Loads all defaults groups.
+    pub fn load(config: &crate::cli::Config) -> CacheResult<Self> {
+        Ok(Self {
+            graphics: GraphicsDefaults::load(config)?,
+            audio: AudioDefaults::load(config)?,
+            camera: CameraDefaults::load(config)?,
+            wearpos: WearposDefaults::load(config)?,
+        })
+    }
+}
+
+impl Display for GraphicsDefaults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl GraphicsDefaults {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("GraphicsDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("GraphicsDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+impl Display for AudioDefaults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl AudioDefaults {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("AudioDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("AudioDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+impl Display for CameraDefaults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl CameraDefaults {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("CameraDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("CameraDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+impl Display for WearposDefaults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl WearposDefaults {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("WearposDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("WearposDefaults({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+impl Display for Defaults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl Defaults {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Defaults({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("Defaults({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Saves all defaults groups as `defaults.json`. Exposed as `--dump defaults`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let defaults = Defaults::load(config)?;
+
+    let path = path!(config.output / "defaults.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&defaults).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_defaults() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let defaults = Defaults::load(&config)?;
+        assert!(!defaults.wearpos.slots.is_empty());
+        assert_eq!(defaults.wearpos.weapon_slot, Some(3));
+        assert_eq!(defaults.wearpos.slot_name(3), Some("weapon"));
+        Ok(())
+    }
+
+    #[test]
+    fn slot_names_come_from_the_cache() {
+        let wearpos = WearposDefaults {
+            slots: vec![0; 8],
+            weapon_slot: Some(3),
+            offhand_slot: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(wearpos.slot_name(3), Some("weapon"));
+        assert_eq!(wearpos.slot_name(5), Some("offhand"));
+        assert_eq!(wearpos.slot_name(0), None);
+        assert_eq!(wearpos.slot_name(20), None);
+    }
+}
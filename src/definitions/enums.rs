@@ -19,7 +19,12 @@ use rs3cache_backend::{
 };
 use serde::Serialize;
 
-use crate::definitions::indextype::IndexType;
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::definitions::{item_configs::ItemConfig, location_configs::LocationConfig, npc_configs::NpcConfig};
+use crate::{
+    definitions::indextype::IndexType,
+    types::typed_value::{ScriptType, TypedValue},
+};
 
 #[derive(Debug, PartialEq, Eq, Hash, Serialize, Clone, Copy, Default)]
 pub enum KeyType {
@@ -203,6 +208,16 @@ pub enum Value {
     String(JString<Bytes>),
 }
 
+impl Value {
+    /// Interprets this value as a `ty`.
+    pub fn typed(&self, ty: ScriptType) -> TypedValue {
+        match self {
+            Self::Integer(value) => TypedValue::new(*value, ty),
+            Self::String(value) => TypedValue::String(value.clone()),
+        }
+    }
+}
+
 #[cfg(feature = "pyo3")]
 impl<'py> IntoPyObject<'py> for Value {
     type Target = PyAny;
@@ -229,9 +244,19 @@ pub struct Enum {
     key_type: KeyType,
     #[serde(skip_serializing_if = "ValueType::is_init")]
     value_type: ValueType,
+    #[serde(skip)]
+    key_script_type: ScriptType,
+    #[serde(skip)]
+    value_script_type: ScriptType,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub variants: BTreeMap<i32, Value>,
     pub default: Option<Value>,
+    /// The names of the items, npcs or locations the keys refer to, by key.
+    /// Only present when dumped with `--resolve-enums`.
+    pub key_names: Option<BTreeMap<i32, JString<Bytes>>>,
+    /// The names of the items, npcs or locations the values refer to, by key.
+    /// Only present when dumped with `--resolve-enums`.
+    pub value_names: Option<BTreeMap<i32, JString<Bytes>>>,
 }
 
 impl Enum {
@@ -263,10 +288,26 @@ impl Enum {
 
                     break r#enum;
                 }
-                1 => r#enum.key_type = buffer.get_u8().try_into().unwrap(),
-                2 => r#enum.value_type = buffer.get_u8().try_into().unwrap(),
-                101 => r#enum.key_type = buffer.get_u8().try_into().unwrap(),
-                102 => r#enum.value_type = buffer.get_u8().try_into().unwrap(),
+                1 => {
+                    let discriminant = buffer.get_u8();
+                    r#enum.key_type = discriminant.try_into().unwrap();
+                    r#enum.key_script_type = ScriptType::from_char(discriminant);
+                }
+                2 => {
+                    let discriminant = buffer.get_u8();
+                    r#enum.value_type = discriminant.try_into().unwrap();
+                    r#enum.value_script_type = ScriptType::from_char(discriminant);
+                }
+                101 => {
+                    let discriminant = buffer.get_u8();
+                    r#enum.key_type = discriminant.try_into().unwrap();
+                    r#enum.key_script_type = ScriptType::from_id(discriminant as u16);
+                }
+                102 => {
+                    let discriminant = buffer.get_u8();
+                    r#enum.value_type = discriminant.try_into().unwrap();
+                    r#enum.value_script_type = ScriptType::from_id(discriminant as u16);
+                }
                 3 => r#enum.default = Some(Value::String(buffer.get_string())),
                 4 => r#enum.default = Some(Value::Integer(buffer.get_i32())),
                 5 => {
@@ -301,6 +342,65 @@ impl Enum {
             }
         }
     }
+
+    /// The type of the keys, as it is in the cache.
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    /// The type of the values, as it is in the cache.
+    pub fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+
+    /// What the keys refer to.
+    pub fn key_script_type(&self) -> ScriptType {
+        self.key_script_type
+    }
+
+    /// What the values refer to.
+    pub fn value_script_type(&self) -> ScriptType {
+        self.value_script_type
+    }
+
+    /// Iterates over the variants, with keys and values interpreted according to their types.
+    pub fn typed_variants(&self) -> impl Iterator<Item = (TypedValue, TypedValue)> + '_ {
+        self.variants.iter().map(|(&key, value)| {
+            (
+                TypedValue::new(key, self.key_script_type),
+                value.typed(self.value_script_type),
+            )
+        })
+    }
+
+    /// The default value, interpreted according to its type.
+    pub fn typed_default(&self) -> Option<TypedValue> {
+        self.default.as_ref().map(|value| value.typed(self.value_script_type))
+    }
+
+    /// Fills in the names of the items, npcs or locations the keys and values refer to.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub fn resolve_names(&mut self, items: &BTreeMap<u32, ItemConfig>, npcs: &BTreeMap<u32, NpcConfig>, locations: &BTreeMap<u32, LocationConfig>) {
+        let name = |value: TypedValue| match value {
+            TypedValue::Item(id) => items.get(&id)?.name.clone(),
+            TypedValue::Npc(id) => npcs.get(&id)?.name.clone(),
+            TypedValue::Location(id) => locations.get(&id)?.name.clone(),
+            _ => None,
+        };
+
+        let mut key_names = BTreeMap::new();
+        let mut value_names = BTreeMap::new();
+        for (&key, value) in &self.variants {
+            if let Some(name) = name(TypedValue::new(key, self.key_script_type)) {
+                key_names.insert(key, name);
+            }
+            if let Some(name) = name(value.typed(self.value_script_type)) {
+                value_names.insert(key, name);
+            }
+        }
+        self.key_names = (!key_names.is_empty()).then_some(key_names);
+        self.value_names = (!value_names.is_empty()).then_some(value_names);
+    }
 }
 
 use std::fmt::{self, Display, Formatter};
@@ -328,6 +428,17 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
     let mut enums = Enum::dump_all(config)?.into_values().collect::<Vec<_>>();
     enums.sort_unstable_by_key(|loc| loc.id);
+
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    if config.resolve_enums {
+        let items = ItemConfig::dump_all(config)?;
+        let npcs = NpcConfig::dump_all(config)?;
+        let locations = LocationConfig::dump_all(config)?;
+        for r#enum in &mut enums {
+            r#enum.resolve_names(&items, &npcs, &locations);
+        }
+    }
+
     let path = path!(config.output / "enums.json");
     let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
 
@@ -336,3 +447,59 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
 
     Ok(())
 }
+
+#[cfg(all(test, any(feature = "rs3", feature = "osrs")))]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> Option<JString<Bytes>> {
+        Some(JString::from(name.to_owned()))
+    }
+
+    #[test]
+    fn resolve_names_of_keys_and_values() {
+        let items = BTreeMap::from([(
+            10,
+            ItemConfig {
+                id: 10,
+                name: name("Bronze sword"),
+                ..Default::default()
+            },
+        )]);
+        let npcs = BTreeMap::from([(
+            20,
+            NpcConfig {
+                id: 20,
+                name: name("Hans"),
+                ..Default::default()
+            },
+        )]);
+        let locations = BTreeMap::new();
+
+        let mut r#enum = Enum {
+            key_script_type: ScriptType::Item,
+            value_script_type: ScriptType::Npc,
+            variants: BTreeMap::from([(10, Value::Integer(20)), (11, Value::Integer(-1))]),
+            ..Default::default()
+        };
+        r#enum.resolve_names(&items, &npcs, &locations);
+
+        assert_eq!(
+            r#enum.key_names,
+            Some(BTreeMap::from([(10, JString::from(String::from("Bronze sword")))]))
+        );
+        assert_eq!(r#enum.value_names, Some(BTreeMap::from([(10, JString::from(String::from("Hans")))])));
+    }
+
+    #[test]
+    fn resolve_names_of_integers() {
+        let mut r#enum = Enum {
+            variants: BTreeMap::from([(10, Value::Integer(20))]),
+            ..Default::default()
+        };
+        r#enum.resolve_names(&BTreeMap::new(), &BTreeMap::new(), &BTreeMap::new());
+
+        assert_eq!(r#enum.key_names, None);
+        assert_eq!(r#enum.value_names, None);
+    }
+}
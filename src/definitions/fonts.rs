@@ -0,0 +1,290 @@
+use std::collections::BTreeMap;
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use image::Rgba;
+use rs3cache_backend::{
+    buf::{BufExtra, ReadError},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::{
+    indextype::IndexType,
+    sprites::{self, Sprite},
+};
+
+/// Describes the spacing of the glyphs of a [`Font`].
+///
+/// Fonts have the same id as the sprite holding their glyphs.
+#[derive(Serialize, Clone, Debug)]
+pub struct FontMetrics {
+    /// Its id.
+    pub id: u32,
+    /// How far to advance after drawing each character.
+    pub advances: Vec<u8>,
+    /// Adjustments to the advance between pairs of characters, indexed by `first << 8 | second`.
+    #[serde(skip)]
+    pub kerning: Option<Vec<i8>>,
+    /// The distance between the top of a line and its baseline.
+    pub ascent: u8,
+}
+
+impl FontMetrics {
+    /// Returns a mapping of all [`FontMetrics`].
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let archives = CacheIndex::new(IndexType::FONTMETRICS, config.input.clone())?.into_iter();
+        let metrics = archives
+            .map(Result::unwrap)
+            .filter_map(|archive| {
+                let id = archive.archive_id();
+                archive.file(&0).map(|file| Self::deserialize(id, file).map(|metrics| (id, metrics)))
+            })
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "font metrics" })?;
+        Ok(metrics)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        // Fonts without kerning information.
+        #[cfg(feature = "osrs")]
+        if buffer.len() == 257 {
+            let advances = buffer.try_get_array::<256>()?.to_vec();
+            let ascent = buffer.try_get_u8()?;
+            return Ok(Self {
+                id,
+                advances,
+                kerning: None,
+                ascent,
+            });
+        }
+
+        #[cfg(feature = "osrs")]
+        let has_kerning = {
+            let _unknown = buffer.try_get_u8()?;
+            true
+        };
+        #[cfg(feature = "rs3")]
+        let has_kerning = {
+            let _version = buffer.try_get_u8()?;
+            buffer.try_get_u8()? == 1
+        };
+
+        let advances = buffer.try_get_array::<256>()?.to_vec();
+
+        if !has_kerning {
+            let ascent = buffer.try_get_u8()?;
+            return Ok(Self {
+                id,
+                advances,
+                kerning: None,
+                ascent,
+            });
+        }
+
+        let heights = buffer.try_get_array::<256>()?;
+        let tops = buffer.try_get_array::<256>()?;
+
+        // The horizontal extent of each row of each glyph, delta encoded.
+        let mut read_edges = || {
+            heights
+                .iter()
+                .map(|&height| {
+                    let mut edge = 0i8;
+                    (0..height)
+                        .map(|_| {
+                            edge = edge.wrapping_add(buffer.try_get_i8()?);
+                            Ok(edge)
+                        })
+                        .collect::<Result<Vec<_>, ReadError>>()
+                })
+                .collect::<Result<Vec<_>, ReadError>>()
+        };
+        let left_edges = read_edges()?;
+        let right_edges = read_edges()?;
+
+        let kern = |first: usize, second: usize| {
+            let start = tops[first].max(tops[second]) as usize;
+            let end = (tops[first] as usize + heights[first] as usize).min(tops[second] as usize + heights[second] as usize);
+
+            let mut gap = advances[first].min(advances[second]) as i32;
+            for row in start..end {
+                let right = right_edges[first][row - tops[first] as usize] as i32;
+                let left = left_edges[second][row - tops[second] as usize] as i32;
+                gap = gap.min(right + left);
+            }
+            -gap as i8
+        };
+
+        let mut kerning = vec![0i8; 256 * 256];
+        // Spaces are never kerned.
+        let glyphs = || (0..256).filter(|&c| c != 32 && c != 160);
+        for first in glyphs() {
+            for second in glyphs() {
+                kerning[first << 8 | second] = kern(first, second);
+            }
+        }
+
+        Ok(Self {
+            id,
+            ascent: tops[32].saturating_add(heights[32]),
+            advances,
+            kerning: Some(kerning),
+        })
+    }
+}
+
+/// A single character of a [`Font`].
+#[derive(Clone, Debug)]
+pub struct Glyph {
+    pub sprite: Sprite,
+    pub offset_x: u16,
+    pub offset_y: u16,
+}
+
+/// A font, made up of [`FontMetrics`] and a sprite for every glyph.
+#[derive(Clone, Debug)]
+pub struct Font {
+    pub metrics: FontMetrics,
+    pub glyphs: BTreeMap<u8, Glyph>,
+}
+
+impl Font {
+    /// Loads the font with the given id.
+    pub fn load(font_id: u32, config: &crate::cli::Config) -> CacheResult<Self> {
+        let file = CacheIndex::new(IndexType::FONTMETRICS, config.input.clone())?
+            .archive(font_id)?
+            .file(&0)
+            .unwrap_or_default();
+        let metrics = FontMetrics::deserialize(font_id, file).context(error::Read { what: "font metrics" })?;
+
+        let file = CacheIndex::new(IndexType::SPRITES, config.input.clone())?
+            .archive(font_id)?
+            .file(&0)
+            .unwrap_or_default();
+        let layout = sprites::deserialize_layout(file.clone()).context(error::Read { what: "font glyph layout" })?;
+        let sprites = sprites::deserialize(file).context(error::Read { what: "font glyphs" })?;
+
+        let glyphs = sprites
+            .into_iter()
+            .filter_map(|(frame, sprite)| {
+                let (offset_x, offset_y) = layout.offsets.get(frame).copied().unwrap_or_default();
+                let glyph = Glyph { sprite, offset_x, offset_y };
+                Some((u8::try_from(frame).ok()?, glyph))
+            })
+            .collect();
+
+        Ok(Self { metrics, glyphs })
+    }
+
+    /// The distance between the top of a line and the lowest point of any glyph.
+    pub fn line_height(&self) -> u32 {
+        let ascent = self.metrics.ascent as i32;
+        let (max_ascent, max_descent) = self.glyphs.values().fold((ascent, 0), |(up, down), glyph| {
+            let top = glyph.offset_y as i32;
+            let bottom = top + glyph.sprite.height() as i32;
+            (up.max(ascent - top), down.max(bottom - ascent))
+        });
+        (max_ascent + max_descent) as u32
+    }
+
+    /// The width of a single line of text.
+    pub fn text_width(&self, text: &str) -> u32 {
+        let mut width = 0i32;
+        let mut previous = None;
+        for c in characters(text) {
+            width += self.kerning(previous, c) + self.metrics.advances[c as usize] as i32;
+            previous = Some(c);
+        }
+        width.max(0) as u32
+    }
+
+    fn kerning(&self, previous: Option<u8>, c: u8) -> i32 {
+        match (&self.metrics.kerning, previous) {
+            (Some(kerning), Some(previous)) => kerning[(previous as usize) << 8 | c as usize] as i32,
+            _ => 0,
+        }
+    }
+
+    /// Draws `text` in the given colour.
+    ///
+    /// Lines can be separated with `<br>`. Other tags are not drawn.
+    pub fn render(&self, text: &str, colour: Rgba<u8>) -> Sprite {
+        let lines = text.split("<br>").map(strip_tags).collect::<Vec<_>>();
+        let line_height = self.line_height();
+        let ascent = self.metrics.ascent as i32;
+        let max_ascent = self
+            .glyphs
+            .values()
+            .map(|glyph| ascent - glyph.offset_y as i32)
+            .fold(ascent, i32::max);
+
+        let width = lines.iter().map(|line| self.text_width(line)).max().unwrap_or(0).max(1);
+        let height = (line_height * lines.len() as u32).max(1);
+        let mut img = Sprite::new(width, height);
+
+        for (line_number, line) in lines.iter().enumerate() {
+            let baseline = line_number as i32 * line_height as i32 + max_ascent;
+            let mut x = 0i32;
+            let mut previous = None;
+            for c in characters(line) {
+                x += self.kerning(previous, c);
+                if let Some(glyph) = self.glyphs.get(&c) {
+                    let left = x + glyph.offset_x as i32;
+                    let top = baseline - ascent + glyph.offset_y as i32;
+                    for (gx, gy, pixel) in glyph.sprite.enumerate_pixels() {
+                        let (px, py) = (left + gx as i32, top + gy as i32);
+                        if pixel[3] != 0 && (0..width as i32).contains(&px) && (0..height as i32).contains(&py) {
+                            img.put_pixel(px as u32, py as u32, colour);
+                        }
+                    }
+                }
+                x += self.metrics.advances[c as usize] as i32;
+                previous = Some(c);
+            }
+        }
+        img
+    }
+}
+
+/// Renders `text` in white, with the font `font_id`.
+pub fn render_text(font_id: u32, text: &str, config: &crate::cli::Config) -> CacheResult<Sprite> {
+    let font = Font::load(font_id, config)?;
+    Ok(font.render(text, Rgba([255, 255, 255, 255])))
+}
+
+/// The characters of `text`, in the game's encoding.
+fn characters(text: &str) -> impl Iterator<Item = u8> + '_ {
+    text.chars().map(|c| u8::try_from(c as u32).unwrap_or(b'?'))
+}
+
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_hello_world() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let font = Font::load(495, &config)?;
+        let img = font.render("Hello world", Rgba([255, 255, 0, 255]));
+        assert_eq!(img.width(), font.text_width("Hello world"));
+        assert!(img.pixels().any(|pixel| pixel[3] != 0));
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use image::imageops;
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::{
+    indextype::{ConfigType, IndexType},
+    sprites::{self, Sprite},
+};
+
+/// Describes a headbar, such as the health bar shown above entities in combat.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Headbar {
+    /// Its id.
+    pub id: u32,
+    pub unknown_1: Option<u16>,
+    pub unknown_2: Option<u8>,
+    pub unknown_3: Option<u8>,
+    pub unknown_4: Option<bool>,
+    /// How long the headbar is shown, in client ticks.
+    pub duration: Option<u16>,
+    pub unknown_6: Option<u8>,
+    /// The sprite showing the filled part of the bar.
+    pub front_sprite: Option<u32>,
+    /// The sprite showing the empty part of the bar.
+    pub back_sprite: Option<u32>,
+    /// How long the bar takes to fade out, in client ticks.
+    pub fade_duration: Option<u16>,
+    /// The width of the bar, if it has no sprites.
+    pub width: Option<u8>,
+    /// Padding on either side of the bar, within the sprites.
+    pub width_padding: Option<u8>,
+}
+
+impl Headbar {
+    /// Returns a mapping of all [`Headbar`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let headbars = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::HEADBAR)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "headbars" })?;
+        Ok(headbars)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut headbar = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(headbar);
+                        }
+                    }
+                    1 => headbar.unknown_1 = Some(buffer.try_get_u16()?),
+                    2 => headbar.unknown_2 = Some(buffer.try_get_u8()?),
+                    3 => headbar.unknown_3 = Some(buffer.try_get_u8()?),
+                    4 => headbar.unknown_4 = Some(true),
+                    5 => headbar.duration = Some(buffer.try_get_u16()?),
+                    6 => headbar.unknown_6 = Some(buffer.try_get_u8()?),
+                    7 => headbar.front_sprite = buffer.try_get_smart32()?,
+                    8 => headbar.back_sprite = buffer.try_get_smart32()?,
+                    11 => headbar.fade_duration = Some(buffer.try_get_u16()?),
+                    14 => headbar.width = Some(buffer.try_get_u8()?),
+                    15 => headbar.width_padding = Some(buffer.try_get_u8()?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: headbar.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Headbar {
+    /// The ids of the sprites this headbar is drawn with.
+    pub fn sprite_ids(&self) -> impl Iterator<Item = u32> {
+        [self.back_sprite, self.front_sprite].into_iter().flatten()
+    }
+
+    /// Draws this headbar filled to `fraction`, or `None` if none of its sprites are in `sprites`.
+    pub fn preview(&self, fraction: f32, sprites: &BTreeMap<u32, Sprite>) -> Option<Sprite> {
+        let back = self.back_sprite.and_then(|id| sprites.get(&id));
+        let front = self.front_sprite.and_then(|id| sprites.get(&id));
+        let width = back.into_iter().chain(front).map(Sprite::width).max()?;
+        let height = back.into_iter().chain(front).map(Sprite::height).max()?;
+        let mut img = Sprite::new(width, height);
+
+        if let Some(back) = back {
+            imageops::overlay(&mut img, back, 0, 0);
+        }
+        if let Some(front) = front {
+            let padding = self.width_padding.unwrap_or(0) as u32;
+            let inner = front.width().saturating_sub(2 * padding);
+            let filled = padding + (inner as f32 * fraction.clamp(0.0, 1.0)).round() as u32;
+            let filled = imageops::crop_imm(front, 0, 0, filled.min(front.width()), front.height()).to_image();
+            imageops::overlay(&mut img, &filled, 0, 0);
+        }
+        Some(img)
+    }
+}
+
+impl Display for Headbar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl Headbar {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Headbar({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("Headbar({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the headbars as `headbars.json`, and a preview of each as `headbars/{id}.png`. Exposed as `--dump headbars`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    let folder = path!(config.output / "headbars");
+    fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
+    let headbars = Headbar::dump_all(config)?;
+
+    let path = path!(config.output / "headbars.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&headbars.values().collect::<Vec<_>>()).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    let sprites = headbars
+        .values()
+        .flat_map(Headbar::sprite_ids)
+        .collect::<BTreeSet<u32>>()
+        .into_iter()
+        .filter_map(|id| sprites::load(id, config).transpose().map(|sprite| sprite.map(|sprite| (id, sprite))))
+        .collect::<CacheResult<BTreeMap<u32, Sprite>>>()?;
+
+    for headbar in headbars.values() {
+        if let Some(img) = headbar.preview(0.5, &sprites) {
+            let filename = path!(&folder / format!("{}.png", headbar.id));
+            img.save(&filename)
+                .unwrap_or_else(|_| panic!("Unable to save headbar {} to {}", headbar.id, filename.to_string_lossy()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_headbars() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let headbars = Headbar::dump_all(&config)?;
+        assert!(headbars.values().any(|headbar| headbar.front_sprite.is_some()));
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use image::{imageops, Rgba};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::{
+    fonts::Font,
+    indextype::{ConfigType, IndexType},
+    location_configs::location_config_fields::{ExtendedLocationMorphTable, LocationMorphTable},
+    sprites::{self, Sprite},
+};
+
+/// Describes a hitsplat, the marker showing the damage or healing an entity receives.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Hitsplat {
+    /// Its id.
+    pub id: u32,
+    /// The font of the text.
+    pub font: Option<u32>,
+    /// The colour of the text.
+    pub text_colour: Option<[u8; 3]>,
+    /// The sprite drawn to the left of the text.
+    pub icon: Option<u32>,
+    /// The left edge of the background.
+    pub left_sprite: Option<u32>,
+    /// The background, which is repeated to fit the text.
+    pub middle_sprite: Option<u32>,
+    /// The right edge of the background.
+    pub right_sprite: Option<u32>,
+    /// How far the hitsplat moves horizontally while it is shown.
+    pub offset_x: Option<i16>,
+    /// The text shown, where `%1` is replaced by the amount.
+    pub text: Option<JString<Bytes>>,
+    /// How long the hitsplat is shown, in client ticks.
+    pub duration: Option<u16>,
+    /// How far the hitsplat moves vertically while it is shown.
+    pub offset_y: Option<i16>,
+    /// Whether the hitsplat starts to fade as soon as it is shown, so it fades for its whole duration.
+    pub fades_from_start: Option<bool>,
+    pub comparison_type: Option<u8>,
+    /// Vertical offset of the text.
+    pub text_offset_y: Option<i16>,
+    /// When the hitsplat starts to fade, in client ticks.
+    pub fade_at: Option<u16>,
+    /// The hitsplats this can change into.
+    pub morphs_1: Option<LocationMorphTable>,
+    /// The hitsplats this can change into.
+    pub morphs_2: Option<ExtendedLocationMorphTable>,
+}
+
+impl Hitsplat {
+    /// Returns a mapping of all [`Hitsplat`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let hitsplats = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::HITSPLATS)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "hitsplats" })?;
+        Ok(hitsplats)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut hitsplat = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(hitsplat);
+                        }
+                    }
+                    1 => hitsplat.font = buffer.try_get_smart32()?,
+                    2 => hitsplat.text_colour = Some(buffer.try_get_array()?),
+                    3 => hitsplat.icon = buffer.try_get_smart32()?,
+                    4 => hitsplat.left_sprite = buffer.try_get_smart32()?,
+                    5 => hitsplat.middle_sprite = buffer.try_get_smart32()?,
+                    6 => hitsplat.right_sprite = buffer.try_get_smart32()?,
+                    7 => hitsplat.offset_x = Some(buffer.try_get_u16()? as i16),
+                    8 => {
+                        // This string is prefixed by a zero byte.
+                        buffer.try_get_u8()?;
+                        hitsplat.text = Some(buffer.try_get_string()?);
+                    }
+                    9 => hitsplat.duration = Some(buffer.try_get_u16()?),
+                    10 => hitsplat.offset_y = Some(buffer.try_get_u16()? as i16),
+                    11 => hitsplat.fades_from_start = Some(true),
+                    12 => hitsplat.comparison_type = Some(buffer.try_get_u8()?),
+                    13 => hitsplat.text_offset_y = Some(buffer.try_get_u16()? as i16),
+                    14 => hitsplat.fade_at = Some(buffer.try_get_u16()?),
+                    17 => hitsplat.morphs_1 = Some(LocationMorphTable::deserialize(&mut buffer)?),
+                    18 => hitsplat.morphs_2 = Some(ExtendedLocationMorphTable::deserialize(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: hitsplat.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Hitsplat {
+    /// The ids of the sprites this hitsplat is drawn with.
+    pub fn sprite_ids(&self) -> impl Iterator<Item = u32> {
+        [self.icon, self.left_sprite, self.middle_sprite, self.right_sprite].into_iter().flatten()
+    }
+
+    /// The text shown for the given amount.
+    pub fn format(&self, amount: i32) -> String {
+        match &self.text {
+            Some(text) => text.replace("%1", &amount.to_string()),
+            None => amount.to_string(),
+        }
+    }
+
+    /// Draws this hitsplat showing `amount`, or `None` if none of its sprites are in `sprites`.
+    pub fn preview(&self, amount: i32, sprites: &BTreeMap<u32, Sprite>, font: Option<&Font>) -> Option<Sprite> {
+        let get = |id: Option<u32>| id.and_then(|id| sprites.get(&id));
+        let [icon, left, middle, right] = [self.icon, self.left_sprite, self.middle_sprite, self.right_sprite].map(get);
+        if [icon, left, middle, right].iter().all(Option::is_none) {
+            return None;
+        }
+
+        let [r, g, b] = self.text_colour.unwrap_or([255, 255, 255]);
+        let text = font.map(|font| font.render(&self.format(amount), Rgba([r, g, b, 255])));
+        let text_width = text.as_ref().map_or(0, Sprite::width);
+
+        let repeats = match middle {
+            Some(middle) if middle.width() > 0 => text_width.div_ceil(middle.width()).max(1),
+            _ => 0,
+        };
+        let middle_width = (repeats * middle.map_or(0, Sprite::width)).max(text_width);
+        let width = [icon, left, right].into_iter().flatten().map(Sprite::width).sum::<u32>() + middle_width;
+        let height = [icon, left, middle, right]
+            .into_iter()
+            .flatten()
+            .chain(text.as_ref())
+            .map(Sprite::height)
+            .max()
+            .unwrap_or(0);
+        let mut img = Sprite::new(width.max(1), height.max(1));
+
+        let mut x = 0;
+        for sprite in [icon, left].into_iter().flatten() {
+            place(&mut img, sprite, x, 0);
+            x += sprite.width();
+        }
+        let middle_x = x;
+        if let Some(middle) = middle {
+            for i in 0..repeats {
+                place(&mut img, middle, x + i * middle.width(), 0);
+            }
+        }
+        x += middle_width;
+        if let Some(right) = right {
+            place(&mut img, right, x, 0);
+        }
+        if let Some(text) = &text {
+            let text_x = middle_x + (middle_width - text.width()) / 2;
+            place(&mut img, text, text_x, self.text_offset_y.unwrap_or(0) as i64);
+        }
+        Some(img)
+    }
+}
+
+/// Draws `sprite` onto `img`, centered vertically.
+fn place(img: &mut Sprite, sprite: &Sprite, x: u32, offset_y: i64) {
+    let y = (img.height() as i64 - sprite.height() as i64) / 2 + offset_y;
+    imageops::overlay(img, sprite, x as i64, y);
+}
+
+impl Display for Hitsplat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl Hitsplat {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Hitsplat({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("Hitsplat({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the hitsplats as `hitsplats.json`, and a preview of each as `hitsplats/{id}.png`. Exposed as `--dump hitsplats`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    let folder = path!(config.output / "hitsplats");
+    fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
+    let hitsplats = Hitsplat::dump_all(config)?;
+
+    let path = path!(config.output / "hitsplats.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&hitsplats.values().collect::<Vec<_>>()).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    let sprites = hitsplats
+        .values()
+        .flat_map(Hitsplat::sprite_ids)
+        .collect::<BTreeSet<u32>>()
+        .into_iter()
+        .filter_map(|id| sprites::load(id, config).transpose().map(|sprite| sprite.map(|sprite| (id, sprite))))
+        .collect::<CacheResult<BTreeMap<u32, Sprite>>>()?;
+    let fonts = hitsplats
+        .values()
+        .filter_map(|hitsplat| hitsplat.font)
+        .collect::<BTreeSet<u32>>()
+        .into_iter()
+        .map(|id| Ok((id, Font::load(id, config)?)))
+        .collect::<CacheResult<BTreeMap<u32, Font>>>()?;
+
+    for hitsplat in hitsplats.values() {
+        let font = hitsplat.font.and_then(|id| fonts.get(&id));
+        if let Some(img) = hitsplat.preview(123, &sprites, font) {
+            let filename = path!(&folder / format!("{}.png", hitsplat.id));
+            img.save(&filename)
+                .unwrap_or_else(|_| panic!("Unable to save hitsplat {} to {}", hitsplat.id, filename.to_string_lossy()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_hitsplats() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let hitsplats = Hitsplat::dump_all(&config)?;
+        assert!(hitsplats.values().any(|hitsplat| hitsplat.font.is_some()));
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::{
+    indextype::{ConfigType, IndexType},
+    location_configs::location_config_fields::{ColourReplacements, Textures},
+};
+
+/// Describes a part of a player's appearance, such as a hairstyle or a pair of legs.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct IdentityKit {
+    /// Its id.
+    pub id: u32,
+    /// The body part this kit is worn on.
+    pub body_part: Option<u8>,
+    /// The models drawn on the player.
+    pub models: Option<Vec<u32>>,
+    /// Whether this kit can not be chosen when designing a character.
+    pub non_selectable: Option<bool>,
+    #[serde(flatten)]
+    pub colour_replacements: Option<ColourReplacements>,
+    #[serde(flatten)]
+    pub textures: Option<Textures>,
+    /// The models drawn on the player's chathead.
+    pub head_models: Option<[Option<u32>; 10]>,
+}
+
+impl IdentityKit {
+    /// Returns a mapping of all [`IdentityKit`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let kits = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::IDENTITY_KIT)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "identity kits" })?;
+        Ok(kits)
+    }
+
+    #[cfg(feature = "rs3")]
+    fn read_model(buffer: &mut Bytes) -> Result<u32, ReadError> {
+        Ok(buffer.try_get_smart32()?.unwrap_or_default())
+    }
+
+    #[cfg(feature = "osrs")]
+    fn read_model(buffer: &mut Bytes) -> Result<u32, ReadError> {
+        Ok(buffer.try_get_u16()? as u32)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut kit = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(kit);
+                        }
+                    }
+                    1 => kit.body_part = Some(buffer.try_get_u8()?),
+                    2 => {
+                        let count = buffer.try_get_u8()?;
+                        kit.models = Some((0..count).map(|_| Self::read_model(&mut buffer)).collect::<Result<_, _>>()?);
+                    }
+                    3 => kit.non_selectable = Some(true),
+                    40 => kit.colour_replacements = Some(ColourReplacements::deserialize(&mut buffer)?),
+                    41 => kit.textures = Some(Textures::deserialize(&mut buffer)?),
+                    opcode @ 60..=69 => kit.head_models.get_or_insert_default()[opcode as usize - 60] = Some(Self::read_model(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: kit.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for IdentityKit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl IdentityKit {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("IdentityKit({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("IdentityKit({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the identity kits as `identity_kits.json`. Exposed as `--dump identity_kits`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let kits = IdentityKit::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "identity_kits.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&kits).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_identity_kits() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let kits = IdentityKit::dump_all(&config)?;
+        assert!(kits.values().any(|kit| kit.models.is_some()));
+        Ok(())
+    }
+}
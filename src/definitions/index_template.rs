@@ -5,17 +5,19 @@ use std::{
     fs::{self, File},
     io::Write,
 };
-use rs3cache_backend::error::{CacheError, Context};
+use ::error::Context;
 use path_macro::path;
 #[cfg(feature = "pyo3")]
 use pyo3::{prelude::*};
 use serde::Serialize;
 
 use bytes::{Buf, Bytes};
-use crate::{
-    cache::{buf::{BufExtra,Buffer}, error::{ self, CacheResult}, index::CacheIndex, indextype::IndexType},
-    structures::paramtable::ParamTable,
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
 };
+use crate::{definitions::indextype::IndexType, structures::paramtable::ParamTable};
 
 /// Describes the properties of a given <Name>.
 
@@ -45,24 +47,49 @@ impl <Name> {
                     .into_iter()
                     .map(move |(file_id, file)| (archive_id << 8 | file_id, file))
             })
-            .map(|(id, file)| (id, Self::deserialize(id, file)))
-            .collect::<BTreeMap<u32, Self>>();
+            .map(|(id, file)| Self::deserialize(id, file).map(|<Name>| (id, <Name>)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "<Name>s" })?;
         Ok(<Name>s)
     }
 
-    fn deserialize(id: u32, mut buffer: Bytes) -> Self {
-        
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
         let mut <Name> = Self { id, ..Default::default() };
 
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
         loop {
-            match buffer.get_u8() {
-                0 => {
-                    debug_assert!(!buffer.has_remaining());
-                    break <Name>;
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(<Name>);
+                        }
+                    }
+                    249 => <Name>.params = Some(ParamTable::deserialize(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: <Name>.to_string(),
+                    })
                 }
-                249 => <Name>.params = Some(ParamTable::deserialize(&mut buffer)),
-
-                missing => unimplemented!("<Name>::deserialize cannot deserialize opcode {} in id {}", missing, id),
             }
         }
     }
@@ -90,15 +117,15 @@ impl <Name> {
 
 /// Save the <Name> configs as `<Name>>.json`. Exposed as `--dump <Name>`.
 pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
-    fs::create_dir_all(&config.output).with_context(|| error::Io {file: config.output.clone()})?;
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
     let mut <Name>_configs = <Name>::dump_all(config)?.into_values().collect::<Vec<_>>();
     <Name>_configs.sort_unstable_by_key(|loc| loc.id);
 
     let path = path!(config.output / "<Name>_configs.json");
 
-    let mut file = File::create(&path).with_context(|| error::Io{ file: path.clone()})?;
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
     let data = serde_json::to_string_pretty(&<Name>_configs).unwrap();
-    file.write_all(data.as_bytes()).context(error::Io {file: path})?;
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
 
     Ok(())
 }
@@ -10,6 +10,8 @@ impl IndexType {
     pub const CONFIG: u32 = 2;
     /// Unimplemented.
     pub const INTERFACES: u32 = 3;
+    /// Contains [`SynthSound`](../../sqlitecache/definitions/sounds/struct.SynthSound.html) definitions.
+    pub const SYNTH_SOUNDS: u32 = 4;
     /// Contains [`MapSquare`](../../sqlitecache/definitions/mapsquares/struct.MapSquares.html) definitions.
     pub const MAPSV2: u32 = 5;
     /// Discontinued.
@@ -23,9 +25,9 @@ impl IndexType {
     pub const BINARY: u32 = 10;
     /// Contains client side scripts in a bytecode-like format (cs2). Unimplemented.
     pub const SCRIPTS: u32 = 12;
-    /// Unimplemented.
+    /// Contains the glyph spacing of fonts, see [`FontMetrics`](crate::definitions::fonts::FontMetrics).
     pub const FONTMETRICS: u32 = 13;
-    /// Unimplemented.
+    /// Contains sound effects, as Ogg Vorbis files.
     pub const VORBIS: u32 = 14;
     /// Contains the [`LocationConfig`](../../sqlitecache/definitions/location_configs/struct.LocationConfig.html) definitions.
     #[cfg(any(feature = "rs3", feature = "2008_3_shim"))]
@@ -39,25 +41,39 @@ impl IndexType {
     pub const OBJ_CONFIG: u32 = 19;
     /// Unimplemented.
     pub const SEQ_CONFIG: u32 = 20;
-    /// Unimplemented.
+    /// Contains the [`SpotAnimConfig`](../../sqlitecache/definitions/spotanim_configs/struct.SpotAnimConfig.html) definitions.
     pub const SPOT_CONFIG: u32 = 21;
     /// Unimplemented.
     pub const STRUCT_CONFIG: u32 = 22;
     /// Contains [`MapZone`](../../sqlitecache/definitions/worldmaps/struct.MapZone.html),
     /// [`MapPastes`](../../sqlitecache/definitions/worldmaps/struct.MapPastes.html) definitions,
     /// as well as PNG images of the world map.
+    #[cfg(not(feature = "osrs"))]
     pub const WORLDMAP: u32 = 23;
-    /// Unimplemented.
+    /// Contains the geography of the world map. Not decoded, see [`WorldMapType`](crate::definitions::worldmaps::WorldMapType).
+    #[cfg(feature = "osrs")]
+    pub const WORLDMAP_GEOGRAPHY: u32 = 18;
+    /// Contains [`MapZone`](crate::definitions::worldmaps::MapZone) and [`MapPastes`](crate::definitions::worldmaps::MapPastes) definitions,
+    /// in archives named after [`WorldMapType`](crate::definitions::worldmaps::WorldMapType).
+    #[cfg(feature = "osrs")]
+    pub const WORLDMAP: u32 = 19;
+    /// Contains the terrain drawn on the world map. Not decoded, see [`WorldMapType`](crate::definitions::worldmaps::WorldMapType).
+    #[cfg(feature = "osrs")]
+    pub const WORLDMAP_GROUND: u32 = 20;
+    /// Contains [`QuickChatCategory`](crate::definitions::quickchat::QuickChatCategory)
+    /// and [`QuickChatPhrase`](crate::definitions::quickchat::QuickChatPhrase) definitions.
     pub const QUICKCHAT: u32 = 24;
-    /// Unimplemented.
+    /// Contains [`QuickChatCategory`](crate::definitions::quickchat::QuickChatCategory)
+    /// and [`QuickChatPhrase`](crate::definitions::quickchat::QuickChatPhrase) definitions that are used everywhere.
     pub const GLOBAL_QUICKCHAT: u32 = 25;
-    /// Unimplemented.
+    /// Contains the [`MaterialConfig`](crate::definitions::materials::MaterialConfig) definitions.
     pub const MATERIALS: u32 = 26;
-    /// Unimplemented.
+    /// Contains the [`ParticleEmitterConfig`](crate::definitions::particles::ParticleEmitterConfig)
+    /// and [`ParticleEffectorConfig`](crate::definitions::particles::ParticleEffectorConfig) definitions.
     pub const PARTICLES: u32 = 27;
-    /// Unimplemented.
+    /// Contains the [`Defaults`](crate::definitions::defaults::Defaults).
     pub const DEFAULTS: u32 = 28;
-    /// Unimplemented.
+    /// Contains the [`BillboardConfig`](crate::definitions::billboards::BillboardConfig) definitions.
     pub const BILLBOARDS: u32 = 29;
     /// Discontinued.
     pub const DLLS: u32 = 30;
@@ -73,9 +89,9 @@ impl IndexType {
     pub const CUTSCENES: u32 = 35;
     /// Unimplemented.
     pub const AUDIOSTREAMS: u32 = 40;
-    /// Unimplemented.
+    /// Unimplemented. On osrs, world map areas are in the `area` archive of the [WORLDMAP](IndexType::WORLDMAP) index instead.
     pub const WORLDMAPAREAS: u32 = 41;
-    /// Unimplemented.
+    /// Unimplemented. On osrs, world map labels are in the `labels` archive of the [WORLDMAP](IndexType::WORLDMAP) index instead.
     pub const WORLDMAPLABELS: u32 = 42;
     /// Unimplemented.
     pub const MODELSRT7: u32 = 47;
@@ -83,14 +99,14 @@ impl IndexType {
     pub const ANIMSRT7: u32 = 48;
     /// Unimplemented.
     pub const DBTABLEINDEX: u32 = 49;
-    /// Unimplemented.
+    /// Contains DXT compressed [`Texture`](crate::definitions::textures::Texture)s.
     #[cfg(feature = "rs3")]
     pub const TEXTURES: u32 = 52;
-    /// Unimplemented.
+    /// Contains [`Texture`](crate::definitions::textures::Texture)s as PNG images.
     pub const TEXTURES_PNG: u32 = 53;
-    /// Unimplemented.
+    /// Contains [`Texture`](crate::definitions::textures::Texture)s as PNG images, with mipmaps.
     pub const TEXTURES_PNG_MIPPED: u32 = 54;
-    /// Unimplemented.
+    /// Contains ETC compressed [`Texture`](crate::definitions::textures::Texture)s.
     pub const TEXTURES_ETC: u32 = 55;
     /// Unimplemented.
     pub const ANIMS_KEYFRAMES: u32 = 56;
@@ -116,11 +132,11 @@ impl ConfigType {
     pub const UNDERLAYS: u32 = 1;
     /// Unimplemented.
     pub const HUNT: u32 = 2;
-    /// Unimplemented.
+    /// Contains [`IdentityKit`](crate::definitions::identity_kits::IdentityKit) definitions.
     pub const IDENTITY_KIT: u32 = 3;
     /// Contains [`Overlay`](../../sqlitecache/definitions/overlays/struct.Overlay.html) definitions.
     pub const OVERLAYS: u32 = 4;
-    /// Unimplemented.
+    /// Contains [`Inventory`](crate::definitions::inventories::Inventory) definitions.
     pub const INVENTORY: u32 = 5;
 
     #[cfg(feature = "osrs")]
@@ -130,17 +146,22 @@ impl ConfigType {
     /// Unimplemented.
     #[cfg(feature = "osrs")]
     pub const NPC_CONFIG: u32 = 9;
-
-    pub const TOOLTIPS: u32 = 11;
-    /// Unimplemented.
+    /// Contains [`ParamConfig`](crate::definitions::params::ParamConfig) definitions.
+    pub const PARAMS: u32 = 11;
+    /// Contains the [`SpotAnimConfig`](../../sqlitecache/definitions/spotanim_configs/struct.SpotAnimConfig.html) definitions.
+    #[cfg(feature = "osrs")]
+    pub const SPOTANIM: u32 = 13;
+    /// Contains [`AreaConfig`](crate::definitions::area_configs::AreaConfig) definitions.
+    ///
+    /// On osrs, these are in the [`MAPLABELS`](ConfigType::MAPLABELS) archive instead.
     pub const AREA: u32 = 18;
-    /// Unimplemented.
+    /// Contains [`Skybox`](crate::definitions::skyboxes::Skybox) definitions.
     pub const SKYBOX: u32 = 29;
-    /// Unimplemented.
+    /// Contains [`LightConfig`](crate::definitions::light_configs::LightConfig) definitions.
     pub const LIGHT: u32 = 31;
     /// Unimplemented.
     pub const BASE_ANIMATION_SET: u32 = 32;
-    /// Unimplemented.
+    /// Contains [`Cursor`](crate::definitions::cursors::Cursor) definitions.
     pub const CURSORS: u32 = 33;
     /// Contains [`MapScene`](../../sqlitecache/definitions/mapscenes/struct.MapScene.html).
     #[cfg(any(feature = "rs3", feature = "2008_3_shim"))]
@@ -148,7 +169,7 @@ impl ConfigType {
 
     #[cfg(all(any(feature = "osrs", feature = "legacy"), not(feature = "2008_3_shim")))]
     pub const MAPLABELS: u32 = 35;
-    /// Unimplemented.
+    /// Contains [`QuestConfig`](crate::definitions::quests::QuestConfig) definitions.
     #[cfg(feature = "rs3")]
     pub const QUESTS: u32 = 35;
     /// Contains [`MapLabelConfig`](../../sqlitecache/definitions/maplabel_configs/struct.MapLabelConfig.html).
@@ -160,35 +181,35 @@ impl ConfigType {
     pub const DBROWS: u32 = 41;
     /// Unimplemented.
     pub const UNKNOWN_42: u32 = 42;
-    /// Unimplemented.
+    /// Contains [`Hitsplat`](crate::definitions::hitsplats::Hitsplat) definitions.
     pub const HITSPLATS: u32 = 46;
     /// Unimplemented.
     pub const UNKNOWN_48: u32 = 48;
     /// Unimplemented.
     pub const UNKNOWN_49: u32 = 49;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the player domain.
     pub const PLAYER: u32 = 60;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the npc domain.
     pub const NPC: u32 = 61;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the client domain.
     pub const CLIENT: u32 = 62;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the world domain.
     pub const WORLD: u32 = 63;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the region domain.
     pub const REGION: u32 = 64;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the object domain.
     pub const OBJECT: u32 = 65;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the clan domain.
     pub const CLAN: u32 = 66;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the clan setting domain.
     pub const CLAN_SETTING: u32 = 67;
-    /// Unimplemented.
+    /// Contains the [`VarConfig`](crate::definitions::var_configs::VarConfig)s of the campaign domain.
     pub const CAMPAIGN: u32 = 68;
-    /// Unimplemented.
+    /// Contains [`VarbitConfig`](crate::definitions::varbit_configs::VarbitConfig) definitions.
     pub const VARBITS: u32 = 69;
     /// Unimplemented.
     pub const UNKNOWN_70: u32 = 70;
-    /// Unimplemented.
+    /// Contains [`Headbar`](crate::definitions::headbars::Headbar) definitions.
     pub const HEADBAR: u32 = 72;
     /// Unimplemented.
     pub const UNKNOWN_73: u32 = 73;
@@ -213,6 +234,7 @@ impl MapFileType {
     pub const LOCATIONS: &'static str = "l";
     /// Deserializes to the [`TileArray`] of `self`.
     pub const TILES: &'static str = "m";
+    /// The environment of `self`. Its layout is not known yet, so it is kept as raw bytes.
     pub const ENVIRONMENT: &'static str = "e";
 }
 
@@ -220,7 +242,7 @@ impl MapFileType {
 impl MapFileType {
     /// Deserializes to the sequence of [`Location`](../../rs3cache/definitions/locations/struct.Location.html)s in `self`.
     pub const LOCATIONS: u32 = 0;
-    /// Deserializes to a sequence of underwater  [`Location`](../../rs3cache/definitions/locations/struct.Location.html)s in `self`. Not implemented.
+    /// Deserializes to a sequence of underwater  [`Location`](../../rs3cache/definitions/locations/struct.Location.html)s in `self`.
     pub const WATER_LOCATIONS: u32 = 1;
     /// Deserializes to a sequence of all npcs in `self`.
     /// Only mapsquares which used to have a "zoom around" login screen,
@@ -230,6 +252,10 @@ impl MapFileType {
     pub const TILES: u32 = 3;
     /// Deserializes to the underwater [`Tiles`](../../rs3cache/definitions/tiles/struct.Tile.html) of `self`.
     pub const WATER_TILES: u32 = 4;
+
+    // The files below are not decoded, as there is no known reference for their layouts.
+    // They are kept as raw bytes, exposed with `MapSquare::unknown_file`, and left out of every export.
+
     pub const UNKNOWN_5: u32 = 5;
     pub const UNKNOWN_6: u32 = 6;
     pub const UNKNOWN_7: u32 = 7;
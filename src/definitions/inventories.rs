@@ -0,0 +1,152 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::{ConfigType, IndexType};
+
+/// Describes an inventory, such as the player's backpack, a bank or a shop.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Inventory {
+    /// Its id.
+    pub id: u32,
+    /// How many items fit in this inventory.
+    pub size: Option<u16>,
+    /// The items this inventory starts with, and how many of each.
+    pub stock: Option<Vec<Stock>>,
+}
+
+/// An item an [`Inventory`] starts with.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub struct Stock {
+    pub item: u16,
+    pub count: u16,
+}
+
+impl Inventory {
+    /// Returns a mapping of all [`Inventory`] configurations.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let inventories = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::INVENTORY)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "inventories" })?;
+        Ok(inventories)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut inventory = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(inventory);
+                        }
+                    }
+                    2 => inventory.size = Some(buffer.try_get_u16()?),
+                    4 => {
+                        let count = buffer.try_get_u8()?;
+                        let stock = (0..count).map(|_| {
+                            try {
+                                Stock {
+                                    item: buffer.try_get_u16()?,
+                                    count: buffer.try_get_u16()?,
+                                }
+                            }
+                        });
+                        inventory.stock = Some(stock.collect::<Result<Vec<Stock>, ReadError>>()?);
+                    }
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: inventory.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for Inventory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl Inventory {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Inventory({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("Inventory({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the inventories as `inventories.json`. Exposed as `--dump inventories`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let inventories = Inventory::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "inventories.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&inventories).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_inventories() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let inventories = Inventory::dump_all(&config)?;
+        assert!(inventories.values().any(|inventory| inventory.size.is_some()));
+        Ok(())
+    }
+}
@@ -12,12 +12,16 @@ use path_macro::path;
 #[cfg(feature = "pyo3")]
 use pyo3::prelude::*;
 use rs3cache_backend::{
-    buf::{BufExtra, JString},
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
     error::{self, CacheResult},
     index::CacheIndex,
 };
 use serde::Serialize;
 
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::definitions::params::ParamConfig;
+#[cfg(feature = "rs3")]
+use crate::definitions::quests::QuestConfig;
 use crate::{definitions::indextype::IndexType, structures::paramtable::ParamTable};
 /// Describes the properties of a given item.
 
@@ -36,6 +40,12 @@ pub struct ItemConfig {
     pub value: Option<i32>,
     pub equipslot_id: Option<u8>,
     pub equip_id: Option<u8>,
+    /// The name of the slot `equipslot_id` refers to, see [`WearposDefaults::slot_name`](crate::definitions::defaults::WearposDefaults::slot_name).
+    #[cfg(feature = "rs3")]
+    pub equipslot: Option<&'static str>,
+    /// The name of the slot `equip_id` refers to, see [`WearposDefaults::slot_name`](crate::definitions::defaults::WearposDefaults::slot_name).
+    #[cfg(feature = "rs3")]
+    pub equip: Option<&'static str>,
     pub unknown_15: Option<bool>,
     pub is_members: Option<bool>,
     pub multi_stack_size: Option<u16>,
@@ -74,6 +84,10 @@ pub struct ItemConfig {
     pub pick_size_shift: Option<u8>,
     pub unknown_bind_link: Option<u16>,
     pub bind_template: Option<u16>,
+    #[cfg(feature = "osrs")]
+    pub placeholder_id: Option<u16>,
+    #[cfg(feature = "osrs")]
+    pub placeholder_template: Option<u16>,
     pub ground_action_cursor: Option<[Option<u16>; 5]>,
     pub widget_action_cursor: Option<[Option<u16>; 5]>,
     pub dummy: Option<bool>,
@@ -103,89 +117,213 @@ impl ItemConfig {
                     .into_iter()
                     .map(move |(file_id, file)| (archive_id << 8 | file_id, file))
             })
-            .map(|(id, file)| (id, Self::deserialize(id, file)))
-            .collect::<BTreeMap<u32, Self>>();
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "item configs" })?;
+
+        #[cfg(feature = "rs3")]
+        let items = {
+            let wearpos = crate::definitions::defaults::WearposDefaults::load(config)?;
+            let mut items = items;
+            for item in items.values_mut() {
+                item.equipslot = item.equipslot_id.and_then(|slot| wearpos.slot_name(slot));
+                item.equip = item.equip_id.and_then(|slot| wearpos.slot_name(slot));
+            }
+            items
+        };
+        Ok(items)
+    }
+
+    /// Returns a mapping of all [`ItemConfig`]s, with noted, lent, bound and placeholder items resolved.
+    /// See [`ItemConfig::resolve`].
+    pub fn dump_resolved(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let raw = Self::dump_all(config)?;
+        let mut items = raw.clone();
+        for item in items.values_mut() {
+            item.resolve(&raw);
+        }
         Ok(items)
     }
 
-    fn deserialize(id: u32, mut buffer: Bytes) -> Self {
+    /// Copies the properties of the items this item is templated on, like the client does.
+    ///
+    /// For example, a noted item only refers to the unnoted item and the note template;
+    /// its name, value and members flag come from the former and its model from the latter.
+    pub fn resolve(&mut self, items: &BTreeMap<u32, Self>) {
+        let lookup = |link: Option<u16>, template: Option<u16>| Some((items.get(&(link? as u32))?, items.get(&(template? as u32))?));
+
+        if let Some((linked, template)) = lookup(self.note_data, self.note_template) {
+            self.copy_appearance(template);
+            self.name = linked.name.clone();
+            self.value = linked.value;
+            self.is_members = linked.is_members;
+            self.stackable = Some(true);
+        }
+        if let Some((linked, template)) = lookup(self.loan_id, self.loan_template) {
+            self.copy_appearance(template);
+            self.copy_equipment(linked);
+            self.value = Some(0);
+            self.widget_actions.get_or_insert_default()[4] = Some(JString::from(String::from("Discard")));
+        }
+        if let Some((linked, template)) = lookup(self.unknown_bind_link, self.bind_template) {
+            self.copy_appearance(template);
+            self.copy_equipment(linked);
+            self.value = Some(0);
+        }
+        #[cfg(feature = "osrs")]
+        if let Some((linked, template)) = lookup(self.placeholder_id, self.placeholder_template) {
+            self.copy_appearance(template);
+            self.name = linked.name.clone();
+            self.value = Some(0);
+            self.is_members = None;
+            self.is_tradeable = None;
+            self.stackable = Some(true);
+        }
+    }
+
+    /// Copies the inventory model of `template`.
+    fn copy_appearance(&mut self, template: &Self) {
+        self.base_model = template.base_model;
+        self.rotation = template.rotation;
+        self.translation = template.translation;
+        self.model_yaw = template.model_yaw;
+        self.colour_replacements = template.colour_replacements.clone();
+        self.textures = template.textures.clone();
+        self.recolour_palette = template.recolour_palette.clone();
+    }
+
+    /// Copies everything about `linked` that is relevant when it is worn.
+    fn copy_equipment(&mut self, linked: &Self) {
+        self.name = linked.name.clone();
+        self.is_members = linked.is_members;
+        self.equipslot_id = linked.equipslot_id;
+        self.equip_id = linked.equip_id;
+        #[cfg(feature = "rs3")]
+        {
+            self.equipslot = linked.equipslot;
+            self.equip = linked.equip;
+        }
+        self.male_models = linked.male_models;
+        self.female_models = linked.female_models;
+        self.male_head_models = linked.male_head_models;
+        self.female_head_models = linked.female_head_models;
+        self.male_translate = linked.male_translate;
+        self.female_translate = linked.female_translate;
+        self.team = linked.team;
+        self.ground_actions = linked.ground_actions.clone();
+        self.widget_actions = linked.widget_actions.clone();
+        self.params = linked.params.clone();
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
         let mut item = Self { id, ..Default::default() };
 
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
         loop {
-            match buffer.get_u8() {
-                0 => {
-                    debug_assert!(!buffer.has_remaining());
-                    break item;
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(item);
+                        }
+                    }
+                    1 => item.base_model = buffer.try_get_smart32()?,
+                    2 => item.name = Some(buffer.try_get_string()?),
+                    3 => item.buff_effect = Some(buffer.try_get_string()?),
+                    4 => item.rotation.get_or_insert_default().yaw = buffer.try_get_u16()?,
+                    5 => item.rotation.get_or_insert_default().pitch = buffer.try_get_u16()?,
+                    6 => item.rotation.get_or_insert_default().roll = buffer.try_get_u16()?,
+                    7 => item.translation.get_or_insert_default().x = buffer.try_get_u16()?,
+                    8 => item.translation.get_or_insert_default().y = buffer.try_get_u16()?,
+                    11 => item.stackable = Some(true),
+                    12 => item.value = Some(buffer.try_get_i32()?),
+                    13 => item.equipslot_id = Some(buffer.try_get_u8()?),
+                    14 => item.equip_id = Some(buffer.try_get_u8()?),
+                    15 => item.unknown_15 = Some(true),
+                    16 => item.is_members = Some(true),
+                    23 => item.male_models.get_or_insert_default()[0] = buffer.try_get_smart32()?,
+                    24 => item.male_models.get_or_insert_default()[1] = buffer.try_get_smart32()?,
+                    25 => item.female_models.get_or_insert_default()[0] = buffer.try_get_smart32()?,
+                    26 => item.female_models.get_or_insert_default()[1] = buffer.try_get_smart32()?,
+                    27 => item.unknown_27 = Some(buffer.try_get_u8()?),
+                    opcode @ 30..=34 => {
+                        item.ground_actions.get_or_insert([None, None, None, None, None])[opcode as usize - 30] = Some(buffer.try_get_string()?)
+                    }
+                    opcode @ 35..=39 => {
+                        item.widget_actions.get_or_insert([None, None, None, None, None])[opcode as usize - 35] = Some(buffer.try_get_string()?)
+                    }
+                    40 => item.colour_replacements = Some(ColourReplacements::deserialize(&mut buffer)?),
+                    41 => item.textures = Some(Textures::deserialize(&mut buffer)?),
+                    42 => item.recolour_palette = Some(RecolourPalette::deserialize(&mut buffer)?),
+                    44 => item.recolour_indices = Some(buffer.try_get_masked_index()?),
+                    45 => item.retexture_indices = Some(buffer.try_get_masked_index()?),
+                    65 => item.is_tradeable = Some(true),
+                    69 => item.ge_buy_limit = Some(buffer.try_get_i32()?),
+                    78 => item.male_models.get_or_insert_default()[2] = buffer.try_get_smart32()?,
+                    79 => item.female_models.get_or_insert_default()[2] = buffer.try_get_smart32()?,
+                    90 => item.male_head_models.get_or_insert_default()[0] = buffer.try_get_smart32()?,
+                    91 => item.female_head_models.get_or_insert_default()[0] = buffer.try_get_smart32()?,
+                    92 => item.male_head_models.get_or_insert_default()[1] = buffer.try_get_smart32()?,
+                    93 => item.female_head_models.get_or_insert_default()[1] = buffer.try_get_smart32()?,
+                    94 => item.category = Some(buffer.try_get_u16()?),
+                    95 => item.model_yaw = Some(buffer.try_get_u16()?),
+                    96 => item.dummy_item = Some(buffer.try_get_u8()?),
+                    97 => item.note_data = Some(buffer.try_get_u16()?),
+                    98 => item.note_template = Some(buffer.try_get_u16()?),
+                    opcode @ 100..=109 => {
+                        item.stack_info.get_or_insert_default()[opcode as usize - 100] = Some((buffer.try_get_u16()?, buffer.try_get_u16()?))
+                    }
+                    opcode @ 110..=112 => item.scale.get_or_insert_default()[opcode as usize - 110] = Some(buffer.try_get_u16()?),
+                    113 => item.ambiance = Some(buffer.try_get_i8()?),
+                    114 => item.contrast = Some(buffer.try_get_i8()?),
+                    115 => item.team = Some(buffer.try_get_u8()?),
+                    121 => item.loan_id = Some(buffer.try_get_u16()?),
+                    122 => item.loan_template = Some(buffer.try_get_u16()?),
+                    125 => item.male_translate = Some(buffer.try_get_uint(3)? as u32),
+                    126 => item.female_translate = Some(buffer.try_get_uint(3)? as u32),
+                    132 => item.quests = Some(Quests::deserialize(&mut buffer)?),
+                    134 => item.pick_size_shift = Some(buffer.try_get_u8()?),
+                    139 => item.unknown_bind_link = Some(buffer.try_get_u16()?),
+                    140 => item.bind_template = Some(buffer.try_get_u16()?),
+                    #[cfg(feature = "osrs")]
+                    148 => item.placeholder_id = Some(buffer.try_get_u16()?),
+                    #[cfg(feature = "osrs")]
+                    149 => item.placeholder_template = Some(buffer.try_get_u16()?),
+                    opcode @ 142..=146 => item.ground_action_cursor.get_or_insert_default()[opcode as usize - 142] = Some(buffer.try_get_u16()?),
+                    opcode @ 150..=154 => item.widget_action_cursor.get_or_insert_default()[opcode as usize - 150] = Some(buffer.try_get_u16()?),
+                    157 => item.randomize_ground_pos = Some(true),
+                    161 => item.combine_info = Some(buffer.try_get_u16()?),
+                    162 => item.combine_template = Some(buffer.try_get_u16()?),
+                    163 => item.combine_num_required = Some(buffer.try_get_u16()?),
+                    164 => item.combine_shard_name = Some(buffer.try_get_string()?),
+                    165 => item.never_stackable = Some(true),
+                    167 => item.unknown_167 = Some(true),
+                    168 => item.unknown_168 = Some(true),
+                    249 => item.params = Some(ParamTable::deserialize(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
                 }
-                1 => item.base_model = Some(buffer.get_smart32().unwrap()),
-                2 => item.name = Some(buffer.get_string()),
-                3 => item.buff_effect = Some(buffer.get_string()),
-                4 => item.rotation.get_or_insert_default().yaw = buffer.get_u16(),
-                5 => item.rotation.get_or_insert_default().pitch = buffer.get_u16(),
-                6 => item.rotation.get_or_insert_default().roll = buffer.get_u16(),
-                7 => item.translation.get_or_insert_default().x = buffer.get_u16(),
-                8 => item.translation.get_or_insert_default().y = buffer.get_u16(),
-                11 => item.stackable = Some(true),
-                12 => item.value = Some(buffer.get_i32()),
-                13 => item.equipslot_id = Some(buffer.get_u8()),
-                14 => item.equip_id = Some(buffer.get_u8()),
-                15 => item.unknown_15 = Some(true),
-                16 => item.is_members = Some(true),
-                23 => item.male_models.get_or_insert_default()[0] = Some(buffer.get_smart32().unwrap()),
-                24 => item.male_models.get_or_insert_default()[1] = Some(buffer.get_smart32().unwrap()),
-                25 => item.female_models.get_or_insert_default()[0] = Some(buffer.get_smart32().unwrap()),
-                26 => item.female_models.get_or_insert_default()[1] = Some(buffer.get_smart32().unwrap()),
-                27 => item.unknown_27 = Some(buffer.get_u8()),
-                opcode @ 30..=34 => {
-                    item.ground_actions.get_or_insert([None, None, None, None, None])[opcode as usize - 30] = Some(buffer.get_string())
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
                 }
-                opcode @ 35..=39 => {
-                    item.widget_actions.get_or_insert([None, None, None, None, None])[opcode as usize - 35] = Some(buffer.get_string())
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: item.to_string(),
+                    })
                 }
-                40 => item.colour_replacements = Some(ColourReplacements::deserialize(&mut buffer)),
-                41 => item.textures = Some(Textures::deserialize(&mut buffer)),
-                42 => item.recolour_palette = Some(RecolourPalette::deserialize(&mut buffer)),
-                44 => item.recolour_indices = Some(buffer.get_masked_index()),
-                45 => item.retexture_indices = Some(buffer.get_masked_index()),
-                65 => item.is_tradeable = Some(true),
-                69 => item.ge_buy_limit = Some(buffer.get_i32()),
-                78 => item.male_models.get_or_insert_default()[2] = Some(buffer.get_smart32().unwrap()),
-                79 => item.female_models.get_or_insert_default()[2] = Some(buffer.get_smart32().unwrap()),
-                90 => item.male_head_models.get_or_insert_default()[0] = Some(buffer.get_smart32().unwrap()),
-                91 => item.female_head_models.get_or_insert_default()[0] = Some(buffer.get_smart32().unwrap()),
-                92 => item.male_head_models.get_or_insert_default()[1] = Some(buffer.get_smart32().unwrap()),
-                93 => item.female_head_models.get_or_insert_default()[1] = Some(buffer.get_smart32().unwrap()),
-                94 => item.category = Some(buffer.get_u16()),
-                95 => item.model_yaw = Some(buffer.get_u16()),
-                96 => item.dummy_item = Some(buffer.get_u8()),
-                97 => item.note_data = Some(buffer.get_u16()),
-                98 => item.note_template = Some(buffer.get_u16()),
-                opcode @ 100..=109 => item.stack_info.get_or_insert_default()[opcode as usize - 100] = Some((buffer.get_u16(), buffer.get_u16())),
-                opcode @ 110..=112 => item.scale.get_or_insert_default()[opcode as usize - 110] = Some(buffer.get_u16()),
-                113 => item.ambiance = Some(buffer.get_i8()),
-                114 => item.contrast = Some(buffer.get_i8()),
-                115 => item.team = Some(buffer.get_u8()),
-                121 => item.loan_id = Some(buffer.get_u16()),
-                122 => item.loan_template = Some(buffer.get_u16()),
-                125 => item.male_translate = Some(buffer.get_uint(3) as u32),
-                126 => item.female_translate = Some(buffer.get_uint(3) as u32),
-                132 => item.quests = Some(Quests::deserialize(&mut buffer)),
-                134 => item.pick_size_shift = Some(buffer.get_u8()),
-                139 => item.unknown_bind_link = Some(buffer.get_u16()),
-                140 => item.bind_template = Some(buffer.get_u16()),
-                opcode @ 142..=146 => item.ground_action_cursor.get_or_insert_default()[opcode as usize - 142] = Some(buffer.get_u16()),
-                opcode @ 150..=154 => item.widget_action_cursor.get_or_insert_default()[opcode as usize - 150] = Some(buffer.get_u16()),
-                157 => item.randomize_ground_pos = Some(true),
-                161 => item.combine_info = Some(buffer.get_u16()),
-                162 => item.combine_template = Some(buffer.get_u16()),
-                163 => item.combine_num_required = Some(buffer.get_u16()),
-                164 => item.combine_shard_name = Some(buffer.get_string()),
-                165 => item.never_stackable = Some(true),
-                167 => item.unknown_167 = Some(true),
-                168 => item.unknown_168 = Some(true),
-                249 => item.params = Some(ParamTable::deserialize(&mut buffer)),
-
-                missing => unimplemented!("ItemConfig::deserialize cannot deserialize opcode {} in id {}", missing, id),
             }
         }
     }
@@ -216,9 +354,10 @@ pub mod item_config_fields {
 
     use std::{collections::BTreeMap, iter};
 
-    use bytes::{Buf, Bytes};
+    use bytes::Bytes;
     #[cfg(feature = "pyo3")]
     use pyo3::prelude::*;
+    use rs3cache_backend::buf::{BufExtra, ReadError};
     use serde::Serialize;
 
     #[cfg_attr(feature = "pyo3", pyclass(frozen, from_py_object))]
@@ -252,10 +391,12 @@ pub mod item_config_fields {
     }
 
     impl ColourReplacements {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let count = buffer.get_u8() as usize;
-            let colours = iter::repeat_with(|| (buffer.get_u16(), buffer.get_u16())).take(count).collect::<Vec<_>>();
-            Self { colours }
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let count = buffer.try_get_u8()? as usize;
+            let colours = iter::repeat_with(|| Ok((buffer.try_get_u16()?, buffer.try_get_u16()?)))
+                .take(count)
+                .collect::<Result<_, ReadError>>()?;
+            Ok(Self { colours })
         }
     }
 
@@ -275,12 +416,12 @@ pub mod item_config_fields {
     }
 
     impl Textures {
-        pub fn deserialize(buffer: &mut Bytes) -> Textures {
-            let count = buffer.get_u8() as usize;
-            let textures = iter::repeat_with(|| (buffer.get_u16(), buffer.get_u16()))
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Textures, ReadError> {
+            let count = buffer.try_get_u8()? as usize;
+            let textures = iter::repeat_with(|| Ok((buffer.try_get_u16()?, buffer.try_get_u16()?)))
                 .take(count)
-                .collect::<BTreeMap<_, _>>();
-            Textures { textures }
+                .collect::<Result<_, ReadError>>()?;
+            Ok(Textures { textures })
         }
     }
 
@@ -291,10 +432,10 @@ pub mod item_config_fields {
     }
 
     impl Quests {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let count = buffer.get_u8() as usize;
-            let quests = iter::repeat_with(|| buffer.get_u16()).take(count).collect();
-            Self { quests }
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let count = buffer.try_get_u8()? as usize;
+            let quests = iter::repeat_with(|| buffer.try_get_u16()).take(count).collect::<Result<_, ReadError>>()?;
+            Ok(Self { quests })
         }
     }
 
@@ -319,10 +460,10 @@ pub mod item_config_fields {
     }
 
     impl StackInfo {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let unknown_1 = buffer.get_u16();
-            let unknown_2 = buffer.get_u16();
-            Self { unknown_1, unknown_2 }
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let unknown_1 = buffer.try_get_u16()?;
+            let unknown_2 = buffer.try_get_u16()?;
+            Ok(Self { unknown_1, unknown_2 })
         }
     }
 
@@ -342,11 +483,11 @@ pub mod item_config_fields {
     }
 
     impl RecolourPalette {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let count = buffer.get_u8() as usize;
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let count = buffer.try_get_u8()? as usize;
 
-            let palette = iter::repeat_with(|| buffer.get_i8()).take(count).collect::<Vec<_>>();
-            Self { palette }
+            let palette = iter::repeat_with(|| buffer.try_get_i8()).take(count).collect::<Result<_, ReadError>>()?;
+            Ok(Self { palette })
         }
     }
 }
@@ -356,8 +497,24 @@ use item_config_fields::*;
 /// Save the item configs as `item_configs.json`. Exposed as `--dump item_configs`.
 pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
-    let mut item_configs = ItemConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let item_configs = if config.resolve_items {
+        ItemConfig::dump_resolved(config)?
+    } else {
+        ItemConfig::dump_all(config)?
+    };
+    let mut item_configs = item_configs.into_values().collect::<Vec<_>>();
     item_configs.sort_unstable_by_key(|loc| loc.id);
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    ParamConfig::resolve_tables(config, item_configs.iter_mut().filter_map(|item| item.params.as_mut()))?;
+
+    #[cfg(feature = "rs3")]
+    let item_configs = {
+        let quests = QuestConfig::dump_all(config)?;
+        item_configs
+            .iter()
+            .map(|item| QuestConfig::annotate(item, &[("/quests/quests", "names")], &quests))
+            .collect::<Vec<_>>()
+    };
 
     let path = path!(config.output / "item_configs.json");
     let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
@@ -366,3 +523,116 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> Option<JString<Bytes>> {
+        Some(JString::from(name.to_owned()))
+    }
+
+    /// An equippable item and a template to draw it with.
+    fn items() -> BTreeMap<u32, ItemConfig> {
+        let sword = ItemConfig {
+            id: 1,
+            name: name("Sword"),
+            value: Some(100),
+            is_members: Some(true),
+            equipslot_id: Some(3),
+            male_models: Some([Some(20), None, None]),
+            ..Default::default()
+        };
+        let template = ItemConfig {
+            id: 2,
+            base_model: Some(10),
+            name: name("null"),
+            ..Default::default()
+        };
+        [sword, template].into_iter().map(|item| (item.id, item)).collect()
+    }
+
+    fn resolved(mut item: ItemConfig) -> ItemConfig {
+        item.resolve(&items());
+        item
+    }
+
+    #[test]
+    fn resolve_noted() {
+        let noted = resolved(ItemConfig {
+            id: 3,
+            note_data: Some(1),
+            note_template: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(noted.name.as_deref(), Some("Sword"));
+        assert_eq!(noted.value, Some(100));
+        assert_eq!(noted.is_members, Some(true));
+        assert_eq!(noted.stackable, Some(true));
+        assert_eq!(noted.base_model, Some(10));
+        assert_eq!(noted.equipslot_id, None);
+    }
+
+    #[test]
+    fn resolve_lent() {
+        let lent = resolved(ItemConfig {
+            id: 3,
+            loan_id: Some(1),
+            loan_template: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(lent.name.as_deref(), Some("Sword"));
+        assert_eq!(lent.value, Some(0));
+        assert_eq!(lent.base_model, Some(10));
+        assert_eq!(lent.equipslot_id, Some(3));
+        assert_eq!(lent.male_models, Some([Some(20), None, None]));
+
+        // The sword has no actions of its own, but a lent item can always be discarded.
+        let actions = lent.widget_actions.expect("lent items have widget actions");
+        assert_eq!(actions[4].as_deref(), Some("Discard"));
+        assert!(actions[..4].iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn resolve_bound() {
+        let bound = resolved(ItemConfig {
+            id: 3,
+            unknown_bind_link: Some(1),
+            bind_template: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(bound.name.as_deref(), Some("Sword"));
+        assert_eq!(bound.value, Some(0));
+        assert_eq!(bound.base_model, Some(10));
+        assert_eq!(bound.equipslot_id, Some(3));
+        assert_eq!(bound.widget_actions, None);
+    }
+
+    #[cfg(feature = "osrs")]
+    #[test]
+    fn resolve_placeholder() {
+        let placeholder = resolved(ItemConfig {
+            id: 3,
+            placeholder_id: Some(1),
+            placeholder_template: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(placeholder.name.as_deref(), Some("Sword"));
+        assert_eq!(placeholder.value, Some(0));
+        assert_eq!(placeholder.is_members, None);
+        assert_eq!(placeholder.stackable, Some(true));
+        assert_eq!(placeholder.base_model, Some(10));
+    }
+
+    #[test]
+    fn resolve_missing_link() {
+        let item = resolved(ItemConfig {
+            id: 3,
+            note_data: Some(99),
+            note_template: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(item.name, None);
+        assert_eq!(item.base_model, None);
+    }
+}
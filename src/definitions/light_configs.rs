@@ -0,0 +1,138 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::{ConfigType, IndexType};
+
+/// Describes how a light source flickers over time.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct LightConfig {
+    /// Its id.
+    pub id: u32,
+    /// The shape of the wave describing the light's intensity over time.
+    pub waveform: Option<u8>,
+    /// How fast the intensity changes.
+    pub frequency: Option<u16>,
+    /// How much the intensity changes.
+    pub amplitude: Option<u16>,
+    pub offset: Option<i16>,
+}
+
+impl LightConfig {
+    /// Returns a mapping of all [`LightConfig`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let lights = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::LIGHT)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "light configs" })?;
+        Ok(lights)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut light = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(light);
+                        }
+                    }
+                    1 => light.waveform = Some(buffer.try_get_u8()?),
+                    2 => light.frequency = Some(buffer.try_get_u16()?),
+                    3 => light.amplitude = Some(buffer.try_get_u16()?),
+                    4 => light.offset = Some(buffer.try_get_u16()? as i16),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: light.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for LightConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl LightConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("LightConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("LightConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the light configs as `light_configs.json`. Exposed as `--dump light_configs`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let lights = LightConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "light_configs.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&lights).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_light_configs() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let lights = LightConfig::dump_all(&config)?;
+        assert!(lights.values().any(|light| light.waveform.is_some()));
+        Ok(())
+    }
+}
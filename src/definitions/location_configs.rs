@@ -19,8 +19,12 @@ use rs3cache_backend::{
 use serde::Serialize;
 
 #[cfg(any(feature = "rs3", feature = "osrs"))]
-use crate::definitions::indextype::IndexType;
-use crate::structures::paramtable::ParamTable;
+use crate::definitions::{indextype::IndexType, params::ParamConfig};
+use crate::{
+    definitions::varbit_configs::VarbitConfig,
+    structures::paramtable::ParamTable,
+    types::variables::VarState,
+};
 
 /// Describes the properties of a given [`Location`](crate::definitions::locations::Location).
 
@@ -431,7 +435,7 @@ impl LocationConfig {
                         loc.unknown_204 = Some(out)
                     }
                     205 => loc.model_morphs = Some(ModelMorphs::deserialize(&mut buffer)?),
-                    249 => loc.params = Some(ParamTable::deserialize(&mut buffer)),
+                    249 => loc.params = Some(ParamTable::deserialize(&mut buffer)?),
                     opcode => {
                         println!("{loc}");
                         do yeet OpcodeNotImplemented::new(opcode)
@@ -455,6 +459,32 @@ impl LocationConfig {
             }
         }
     }
+
+    /// The [`MapLabelConfig`](crate::definitions::maplabel_configs::MapLabelConfig) this location refers to, if any.
+    pub fn maplabel_id(&self) -> Option<u16> {
+        #[cfg(all(feature = "osrs", not(feature = "2008_3_shim")))]
+        {
+            self.maparea_id
+        }
+
+        #[cfg(not(all(feature = "osrs", not(feature = "2008_3_shim"))))]
+        {
+            self.mapfunction
+        }
+    }
+
+    /// Returns the id of the location the client shows for this location, given the state of the player's variables.
+    ///
+    /// This is its own id if it does not morph, and `None` if it morphs into nothing.
+    pub fn morph(&self, state: &VarState, varbit_configs: &BTreeMap<u32, VarbitConfig>) -> Option<u32> {
+        if let Some(morphs) = &self.morphs_1 {
+            morphs.resolve(state, varbit_configs)
+        } else if let Some(morphs) = &self.morphs_2 {
+            morphs.resolve(state, varbit_configs)
+        } else {
+            Some(self.id)
+        }
+    }
 }
 
 impl Display for LocationConfig {
@@ -474,7 +504,10 @@ pub mod location_config_fields {
     use rs3cache_backend::buf::{BufExtra, JString, ReadError};
     use serde::Serialize;
 
-    use crate::types::variables::{Varbit, Varp, VarpOrVarbit};
+    use crate::{
+        definitions::varbit_configs::VarbitConfig,
+        types::variables::{VarState, Varbit, Varp, VarpOrVarbit},
+    };
 
     #[cfg(any(feature = "rs3", feature = "2011_11_shim"))]
     type IdType = u32;
@@ -532,6 +565,11 @@ pub mod location_config_fields {
 
             Ok(Self { var, ids })
         }
+
+        /// Returns the id this location morphs into, given the state of the player's variables.
+        pub fn resolve(&self, state: &VarState, varbit_configs: &BTreeMap<u32, VarbitConfig>) -> Option<u32> {
+            state.morph(self.var, &self.ids, None, varbit_configs).map(u32::from)
+        }
     }
 
     /// Like [`LocationMorphTable`], but with a default value.
@@ -600,6 +638,11 @@ pub mod location_config_fields {
 
             Ok(Self { var, ids, default })
         }
+
+        /// Returns the id this location morphs into, given the state of the player's variables.
+        pub fn resolve(&self, state: &VarState, varbit_configs: &BTreeMap<u32, VarbitConfig>) -> Option<u32> {
+            state.morph(self.var, &self.ids, self.default, varbit_configs).map(u32::from)
+        }
     }
 
     #[cfg_attr(feature = "pyo3", pyclass(frozen, from_py_object))]
@@ -1340,7 +1383,9 @@ use location_config_fields::*;
 /// Save the location configs as `location_configs.json`. Exposed as `--dump location_configs`.
 pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
-    let loc_configs = LocationConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let mut loc_configs = LocationConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    ParamConfig::resolve_tables(config, loc_configs.iter_mut().filter_map(|loc| loc.params.as_mut()))?;
     let path = path!(config.output / "location_configs.json");
     let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
     let data = serde_json::to_string_pretty(&loc_configs).unwrap();
@@ -110,11 +110,15 @@ pub struct Location {
 }
 
 impl Location {
-    // todo: fix this with water tiles
+    /// Constructor for the underwater [`Location`]s of a mapsquare.
+    ///
+    /// Their planes are adjusted with the underwater tiles, if the mapsquare has them.
     #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
-    pub(crate) fn dump_water_locations(i: u8, j: u8, buffer: Bytes) -> Vec<Self> {
-        let blanks = TileArray::default((4, 64, 64));
-        Self::dump(i, j, &blanks, buffer)
+    pub(crate) fn dump_water_locations(i: u8, j: u8, water_tiles: Option<&TileArray>, buffer: Bytes) -> Vec<Self> {
+        match water_tiles {
+            Some(water_tiles) => Self::dump(i, j, water_tiles, buffer),
+            None => Self::dump(i, j, &TileArray::default((4, 64, 64)), buffer),
+        }
     }
 
     /// Constructor for [`Location`].
@@ -1,5 +1,6 @@
 use std::{
     collections::BTreeMap,
+    fmt::{self, Display, Formatter},
     fs::{self, File},
     io::Write,
 };
@@ -10,15 +11,24 @@ use path_macro::path;
 #[cfg(feature = "pyo3")]
 use pyo3::prelude::*;
 use rs3cache_backend::{
-    buf::{BufExtra, JString},
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
     error::{self, CacheResult},
     index::CacheIndex,
 };
 use serde::Serialize;
 
 use crate::{
-    definitions::indextype::{ConfigType, IndexType},
+    definitions::{
+        indextype::{ConfigType, IndexType},
+        locations::{Location, Watery},
+    },
     structures::paramtable::ParamTable,
+    types::geojson::{Feature, Geometry},
+};
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::{
+    definitions::{location_configs::LocationConfig, mapsquares::MapSquares, params::ParamConfig},
+    types::geojson::FeatureCollection,
 };
 
 /// Map element on the ingame world map.
@@ -85,48 +95,113 @@ pub struct MapLabelConfig {
 impl MapLabelConfig {
     /// Returns a mapping of all [`MapLabelConfig`]s.
     pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, MapLabelConfig>> {
-        Ok(CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+        let maplabels = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
             .archive(ConfigType::MAPLABELS)?
             .take_files()
             .into_iter()
-            .map(|(file_id, file)| (file_id, MapLabelConfig::deserialize(file_id, file)))
-            .collect())
+            .map(|(file_id, file)| MapLabelConfig::deserialize(file_id, file).map(|maplabel| (file_id, maplabel)))
+            .collect::<Result<BTreeMap<u32, MapLabelConfig>, ReadError>>()
+            .context(error::Read { what: "map label configs" })?;
+        Ok(maplabels)
     }
 
-    fn deserialize(id: u32, mut buffer: Bytes) -> MapLabelConfig {
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<MapLabelConfig, ReadError> {
         let mut maplabel = MapLabelConfig { id, ..Default::default() };
 
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
         loop {
-            match buffer.get_u8() {
-                0 => {
-                    debug_assert!(!buffer.has_remaining(), "{buffer:?}");
-                    break maplabel;
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(maplabel);
+                        }
+                    }
+                    1 => maplabel.sprite = buffer.try_get_smart32()?,
+                    2 => maplabel.hover_sprite = buffer.try_get_smart32()?,
+                    3 => maplabel.text = Some(buffer.try_get_string()?),
+                    4 => maplabel.label_colour_1 = Some(buffer.try_get_array()?),
+                    5 => maplabel.label_colour_2 = Some(buffer.try_get_array()?),
+                    6 => maplabel.font_size = Some(buffer.try_get_u8()?),
+                    7 => maplabel.unknown_7 = Some(buffer.try_get_u8()?),
+                    8 => maplabel.unknown_8 = Some(buffer.try_get_u8()?),
+                    9 => maplabel.toggle_1 = Some(Toggle::deserialize(&mut buffer)?),
+                    10 => maplabel.rightclick_1 = Some(buffer.try_get_string()?),
+                    15 => maplabel.polygon = Some(Polygon::deserialize(&mut buffer)?),
+                    17 => maplabel.rightclick_2 = Some(buffer.try_get_string()?),
+                    19 => maplabel.category = Some(buffer.try_get_u16()?),
+                    20 => maplabel.toggle_2 = Some(Toggle::deserialize(&mut buffer)?),
+                    21 => maplabel.unknown_21 = Some(buffer.try_get_array()?),
+                    22 => maplabel.unknown_22 = Some(buffer.try_get_array()?),
+                    25 => maplabel.background_sprite = buffer.try_get_smart32()?,
+                    26 => maplabel.legacy_switch = Some(LegacySwitch::deserialize(&mut buffer)?),
+                    28 => maplabel.unknown_28 = Some(buffer.try_get_u8()?),
+                    30 => maplabel.unknown_30 = Some(buffer.try_get_u8()?),
+                    249 => maplabel.params = Some(ParamTable::deserialize(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: maplabel.to_string(),
+                    })
                 }
-                1 => maplabel.sprite = Some(buffer.get_smart32().unwrap()),
-                2 => maplabel.hover_sprite = Some(buffer.get_smart32().unwrap()),
-                3 => maplabel.text = Some(buffer.get_string()),
-                4 => maplabel.label_colour_1 = Some(buffer.get_rgb()),
-                5 => maplabel.label_colour_2 = Some(buffer.get_rgb()),
-                6 => maplabel.font_size = Some(buffer.get_u8()),
-                7 => maplabel.unknown_7 = Some(buffer.get_u8()),
-                8 => maplabel.unknown_8 = Some(buffer.get_u8()),
-                9 => maplabel.toggle_1 = Some(Toggle::deserialize(&mut buffer)),
-                10 => maplabel.rightclick_1 = Some(buffer.get_string()),
-                15 => maplabel.polygon = Some(Polygon::deserialize(&mut buffer)),
-                17 => maplabel.rightclick_2 = Some(buffer.get_string()),
-                19 => maplabel.category = Some(buffer.get_u16()),
-                20 => maplabel.toggle_2 = Some(Toggle::deserialize(&mut buffer)),
-                21 => maplabel.unknown_21 = Some(buffer.get_array()),
-                22 => maplabel.unknown_22 = Some(buffer.get_array()),
-                25 => maplabel.background_sprite = Some(buffer.get_smart32().unwrap()),
-                26 => maplabel.legacy_switch = Some(LegacySwitch::deserialize(&mut buffer)),
-                28 => maplabel.unknown_28 = Some(buffer.get_u8()),
-                30 => maplabel.unknown_30 = Some(buffer.get_u8()),
-                249 => maplabel.params = Some(ParamTable::deserialize(&mut buffer)),
-                other => unimplemented!("{}", other),
             }
         }
     }
+
+    /// Creates a GeoJSON feature of this label, positioned at `location`.
+    ///
+    /// This is a polygon if the label has one, and a point at the center of the location's tile otherwise.
+    /// The label's params, such as its tooltip, are included as they are, because the cache does not name them.
+    pub fn to_feature(&self, location: &Location) -> Feature {
+        let x = (location.i as u32 * 64 + location.x as u32) as f64 + 0.5;
+        let y = (location.j as u32 * 64 + location.y as u32) as f64 + 0.5;
+        let plane = match location.plane {
+            Watery::True(plane) => plane.saturating_sub(1),
+            Watery::False(plane) => plane,
+        };
+
+        let geometry = match &self.polygon {
+            Some(polygon) => Geometry::polygon(polygon.points.iter().map(|point| [x + point.dx as f64, y + point.dy as f64]).collect()),
+            None => Geometry::Point([x, y]),
+        };
+
+        let mut properties = serde_json::Map::new();
+        properties.insert("id".into(), self.id.into());
+        properties.insert("location".into(), location.id.into());
+        properties.insert("plane".into(), plane.into());
+        if let Some(text) = &self.text {
+            properties.insert("text".into(), text.as_ref().into());
+        }
+        if let Some(sprite) = self.sprite {
+            properties.insert("sprite".into(), sprite.into());
+        }
+        if let Some(category) = self.category {
+            properties.insert("category".into(), category.into());
+        }
+        if let Some(params) = &self.params {
+            properties.insert("params".into(), serde_json::to_value(&params.params).unwrap());
+        }
+
+        Feature { geometry, properties }
+    }
 }
 
 ///Save the maplabels as `maplabels.json`. Exposed as `--dump maplabels`.
@@ -134,6 +209,8 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
     let mut labels = MapLabelConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
     labels.sort_unstable_by_key(|loc| loc.id);
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    ParamConfig::resolve_tables(config, labels.iter_mut().filter_map(|label| label.params.as_mut()))?;
     let path = path!(config.output / "map_labels.json");
 
     let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
@@ -142,15 +219,50 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     Ok(())
 }
 
+/// Save the map labels, positioned by the locations referring to them, as `map_labels.geojson`. Exposed as `--dump geojson`.
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+pub fn export_geojson(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let labels = MapLabelConfig::dump_all(config)?;
+    let location_configs = LocationConfig::dump_all(config)?;
+
+    let mut features = Vec::new();
+    for square in MapSquares::new(config)? {
+        let Some(locations) = square?.take_locations() else { continue };
+        for location in &locations {
+            let label = location_configs
+                .get(&location.id)
+                .and_then(LocationConfig::maplabel_id)
+                .and_then(|label_id| labels.get(&(label_id as u32)));
+            if let Some(label) = label {
+                features.push(label.to_feature(location));
+            }
+        }
+    }
+
+    let path = path!(config.output / "map_labels.geojson");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&FeatureCollection { features }).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+    Ok(())
+}
+
+impl Display for MapLabelConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
 /// Defines the structs used as fields of [`MapLabelConfig`],
 pub mod maplabel_config_fields {
 
-    use std::iter;
+    use std::{iter, panic::Location};
 
-    use bytes::{Buf, Bytes};
+    use bytes::Bytes;
     use itertools::izip;
     #[cfg(feature = "pyo3")]
     use pyo3::prelude::*;
+    use rs3cache_backend::buf::{BufExtra, ReadError};
     use serde::Serialize;
 
     use crate::types::variables::{Varbit, Varp, VarpOrVarbit};
@@ -171,25 +283,36 @@ pub mod maplabel_config_fields {
     }
 
     impl Polygon {
-        pub fn deserialize(buffer: &mut Bytes) -> Polygon {
-            let point_count = buffer.get_u8() as usize;
-            let xy = iter::repeat_with(|| (buffer.get_i16(), buffer.get_i16()))
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Polygon, ReadError> {
+            let point_count = buffer.try_get_u8()? as usize;
+            let xy = iter::repeat_with(|| Ok((buffer.try_get_u16()? as i16, buffer.try_get_u16()? as i16)))
                 .take(point_count)
-                .collect::<Vec<(i16, i16)>>();
-
-            let colour = [buffer.get_u8(), buffer.get_u8(), buffer.get_u8(), buffer.get_u8()];
-            assert_eq!(buffer.get_u8(), 1_u8);
+                .collect::<Result<Vec<(i16, i16)>, ReadError>>()?;
+
+            let colour = buffer.try_get_array()?;
+            match buffer.try_get_u8()? {
+                1 => {}
+                other => {
+                    return Err(ReadError::Unsupported {
+                        location: Location::caller(),
+                        what: "polygon",
+                        reason: format!("expected a separator of 1, found {other}"),
+                    })
+                }
+            }
 
-            let background_colour = [buffer.get_u8(), buffer.get_u8(), buffer.get_u8(), buffer.get_u8()];
+            let background_colour = buffer.try_get_array()?;
 
-            let planes = iter::repeat_with(|| buffer.get_u8()).take(point_count).collect::<Vec<_>>();
+            let planes = iter::repeat_with(|| buffer.try_get_u8())
+                .take(point_count)
+                .collect::<Result<Vec<_>, ReadError>>()?;
             let points = izip!(planes, xy).map(|(plane, (dx, dy))| PolygonPoint { plane, dx, dy }).collect();
 
-            Polygon {
+            Ok(Polygon {
                 colour,
                 background_colour,
                 points,
-            }
+            })
         }
     }
 
@@ -210,15 +333,15 @@ pub mod maplabel_config_fields {
     }
 
     impl Toggle {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let varbit = Varbit::new(buffer.get_u16());
-            let varp = Varp::new(buffer.get_u16());
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let varbit = Varbit::new(buffer.try_get_u16()?);
+            let varp = Varp::new(buffer.try_get_u16()?);
             let var = VarpOrVarbit::new(varp, varbit);
 
-            let lower = buffer.get_u32();
-            let upper = buffer.get_u32();
+            let lower = buffer.try_get_u32()?;
+            let upper = buffer.try_get_u32()?;
 
-            Self { var, lower, upper }
+            Ok(Self { var, lower, upper })
         }
     }
 
@@ -242,22 +365,22 @@ pub mod maplabel_config_fields {
     }
 
     impl LegacySwitch {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let varbit = Varbit::new(buffer.get_u16());
-            let varp = Varp::new(buffer.get_u16());
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let varbit = Varbit::new(buffer.try_get_u16()?);
+            let varp = Varp::new(buffer.try_get_u16()?);
             let var = VarpOrVarbit::new(varp, varbit);
 
             // always 0 or 1 (boolean)
-            let value = buffer.get_u8();
-            let default_reference = buffer.get_u16();
-            let legacy_reference = buffer.get_u16();
+            let value = buffer.try_get_u8()?;
+            let default_reference = buffer.try_get_u16()?;
+            let legacy_reference = buffer.try_get_u16()?;
 
-            Self {
+            Ok(Self {
                 var,
                 value,
                 default_reference,
                 legacy_reference,
-            }
+            })
         }
     }
 
@@ -289,4 +412,43 @@ mod tests {
         MapLabelConfig::dump_all(&config)?;
         Ok(())
     }
+
+    fn location(i: u8, j: u8, x: u8, y: u8) -> Location {
+        Location {
+            plane: Watery::False(1),
+            i,
+            j,
+            x,
+            y,
+            id: 0,
+            r#type: 10,
+            rotation: 0,
+        }
+    }
+
+    #[test]
+    fn label_is_centered_on_its_tile() {
+        let label = MapLabelConfig { id: 5, ..Default::default() };
+        let feature = label.to_feature(&location(50, 50, 10, 20));
+
+        assert_eq!(feature.geometry, Geometry::Point([3210.5, 3220.5]));
+        assert_eq!(feature.properties["plane"], 1);
+    }
+
+    #[test]
+    fn label_polygon_is_offset_from_its_tile() {
+        let point = |dx, dy| PolygonPoint { plane: 0, dx, dy };
+        let label = MapLabelConfig {
+            polygon: Some(Polygon {
+                colour: [0; 4],
+                background_colour: [0; 4],
+                points: vec![point(0, 0), point(4, 0), point(0, -2)],
+            }),
+            ..Default::default()
+        };
+        let feature = label.to_feature(&location(50, 50, 10, 20));
+
+        let ring = vec![[3210.5, 3220.5], [3214.5, 3220.5], [3210.5, 3218.5], [3210.5, 3220.5]];
+        assert_eq!(feature.geometry, Geometry::Polygon(vec![ring]));
+    }
 }
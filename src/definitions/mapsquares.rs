@@ -35,9 +35,17 @@ use rs3cache_backend::{
 };
 use rs3cache_utils::rangeclamp::RangeClamp;
 #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
-use {crate::definitions::indextype::MapFileType, bytes::Buf, rs3cache_backend::arc::Archive, rs3cache_utils::lazy::Lazy};
+use {
+    crate::definitions::{indextype::MapFileType, npcs::Npc},
+    bytes::{Buf, Bytes},
+    rs3cache_backend::arc::Archive,
+    rs3cache_utils::lazy::Lazy,
+    std::{collections::BTreeMap, convert::Infallible, sync::Arc},
+};
 
 pub use self::iterator::*;
+#[cfg(feature = "rs3")]
+use crate::definitions::{locations::Watery, worldmaps::Paste};
 use crate::definitions::{
     locations::Location,
     tiles::{Tile, TileArray},
@@ -46,7 +54,7 @@ use crate::definitions::{
 #[cfg(feature = "osrs")]
 const MAX_REGIONS: u32 = 25287;
 /// Represents a section of the game map
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MapSquare {
     /// The horizontal [`MapSquare`] coordinate.
     ///
@@ -73,13 +81,39 @@ pub struct MapSquare {
     #[cfg(feature = "osrs")]
     pub xtea: Option<Xtea>,
 
+    /// The environment file of this [`MapSquare`].
+    ///
+    /// Its layout is not known yet, so it is kept as is.
+    #[cfg(all(feature = "osrs", not(feature = "2013_4_shim")))]
+    environment: Option<bytes::Bytes>,
+
     /// All water locations in this [`MapSquare`].
     ///
     /// Locations can overlap on surrounding mapsquares.
     #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
-    water_locations: Option<Lazy<(bytes::Bytes, u8, u8), Vec<Location>, CacheError>>,
+    water_locations: Option<Lazy<(Bytes, Option<WaterTiles>, u8, u8), Vec<Location>, Infallible>>,
+
+    /// All npcs in this [`MapSquare`].
+    #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+    npcs: Option<Lazy<(Bytes, u8, u8), Vec<Npc>, CacheError>>,
+
+    /// Data on the underwater tiles it contains.
+    ///
+    /// These are shared with the `water_locations`, which need them to adjust their planes.
+    #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+    water_tiles: Option<WaterTiles>,
+
+    /// The files of this [`MapSquare`] that are not understood yet, by their [`MapFileType`].
+    ///
+    /// These are only exposed with [`MapSquare::unknown_file`], and are not part of any export.
+    #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+    unknown_files: BTreeMap<u32, Bytes>,
 }
 
+/// The lazily decoded underwater tiles of a [`MapSquare`].
+#[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+type WaterTiles = Arc<Lazy<Bytes, TileArray, CacheError>>;
+
 /// Iterator over a columns of planes with their x, y coordinates
 pub type ColumnIter<'c> = Zip<LanesIter<'c, Tile, Dim<[usize; 2]>>, Product<Range<u32>, Range<u32>>>;
 
@@ -103,7 +137,7 @@ impl MapSquare {
         assert!(i < 0x7F, "Index out of range.");
         let archive_id = (i as u32) | (j as u32) << 7;
         let archive = CacheIndex::new(crate::definitions::indextype::IndexType::MAPSV2, config.input.clone())?.archive(archive_id)?;
-        Ok(Self::from_archive(archive))
+        Self::from_archive(archive)
     }
 
     #[cfg(all(feature = "osrs", not(feature = "2013_4_shim")))]
@@ -117,9 +151,10 @@ impl MapSquare {
             .file(&0)
             .context(rs3cache_backend::index::Other)
             .context(error::Integrity)?;
-        let _env = env.map(|k| index.archive(k));
+        // Like the locations, failing to read this should not prevent using the rest of the mapsquare.
+        let environment = env.and_then(|k| index.archive(k).ok()).and_then(|arch| arch.file(&0));
 
-        let tiles = Tile::dump(&mut tile_bytes);
+        let tiles = Tile::dump(&mut tile_bytes).context(error::Read { what: "tiles" })?;
         let locations = match land {
             Ok(land) => Some(Location::dump(i, j, &tiles, land)),
             // most likely xtea error...
@@ -135,6 +170,7 @@ impl MapSquare {
             tiles: Some(tiles),
             locations,
             xtea,
+            environment,
         })
     }
 
@@ -150,7 +186,7 @@ impl MapSquare {
         let mut tile_bytes = archive.file(&0).context(rs3cache_backend::index::Other).context(error::Integrity)?;
         assert_ne!(tile_bytes.len(), 0);
 
-        let tiles = Tile::dump(&mut tile_bytes);
+        let tiles = Tile::dump(&mut tile_bytes).context(error::Read { what: "tiles" })?;
 
         let locations = Location::dump(i, j, &tiles, location_bytes);
 
@@ -160,6 +196,7 @@ impl MapSquare {
             tiles: Some(tiles),
             locations: Some(locations),
             xtea: None,
+            environment: None,
         })
     }
 
@@ -176,7 +213,7 @@ impl MapSquare {
             .context(rs3cache_backend::index::Other)
             .context(error::Integrity)?;
 
-        let tiles = Tile::dump(&mut tile_bytes);
+        let tiles = Tile::dump(&mut tile_bytes).context(error::Read { what: "tiles" })?;
         let locations = Location::dump(i, j, &tiles, land);
 
         Ok(MapSquare {
@@ -188,14 +225,14 @@ impl MapSquare {
     }
 
     #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
-    pub(crate) fn from_archive(archive: Archive) -> MapSquare {
+    pub(crate) fn from_archive(archive: Archive) -> CacheResult<MapSquare> {
         let i = (archive.archive_id() & 0x7F) as u8;
         let j = (archive.archive_id() >> 7) as u8;
         let mut tile_bytes = archive.file(&MapFileType::TILES);
 
         let (tiles, members, locations) = match tile_bytes {
             Some(ref mut tile_bytes) => {
-                let tiles = Tile::dump(tile_bytes);
+                let tiles = Tile::dump(tile_bytes).context(error::Read { what: "tiles" })?;
                 let members = tile_bytes.get_u64();
                 let locations = archive.file(&MapFileType::LOCATIONS).map(|file| Location::dump(i, j, &tiles, file));
                 (Some(tiles), Some(members), locations)
@@ -203,17 +240,117 @@ impl MapSquare {
             None => (None, None, None),
         };
 
-        let bytes = archive.file(&MapFileType::WATER_LOCATIONS);
-        let water_locations = bytes.map(|bytes| Lazy::new((bytes, i, j), |(bytes, i, j)| Ok(Location::dump_water_locations(i, j, bytes))));
+        let water_tiles = archive.file(&MapFileType::WATER_TILES).map(|bytes| {
+            Arc::new(Lazy::new(bytes, |mut bytes| {
+                Tile::dump(&mut bytes).context(error::Read { what: "water tiles" })
+            }))
+        });
+
+        // If the water tiles cannot be decoded, `water_locations()` reports that instead.
+        let water_locations = archive.file(&MapFileType::WATER_LOCATIONS).map(|bytes| {
+            Lazy::new((bytes, water_tiles.clone(), i, j), |(bytes, water_tiles, i, j)| {
+                let water_tiles = water_tiles.as_deref().and_then(|water_tiles| water_tiles.as_ref().ok());
+                Ok(Location::dump_water_locations(i, j, water_tiles, bytes))
+            })
+        });
+
+        let npcs = archive.file(&MapFileType::NPCS).map(|bytes| {
+            Lazy::new((bytes, i, j), |(bytes, i, j)| {
+                Npc::deserialize(i, j, bytes).context(error::Read { what: "npcs" })
+            })
+        });
+
+        let unknown_files = (MapFileType::UNKNOWN_5..=MapFileType::UNKNOWN_9)
+            .filter_map(|file| archive.file(&file).map(|bytes| (file, bytes)))
+            .collect();
 
-        MapSquare {
+        Ok(MapSquare {
             i,
             j,
             tiles,
             members,
             locations,
             water_locations,
+            npcs,
+            water_tiles,
+            unknown_files,
+        })
+    }
+
+    /// Creates the [`MapSquare`] at `i, j` of a world map other than the "real" one,
+    /// by copying the tiles and locations of the squares and chunks that `pastes` refer to.
+    ///
+    /// Returns `None` if none of the sources exist.
+    #[cfg(feature = "rs3")]
+    pub(crate) fn pasted(i: u8, j: u8, pastes: &[Paste], index: &CacheIndex<Initial>) -> Option<MapSquare> {
+        let mut tiles = TileArray::default((4, 64, 64));
+        let mut locations = Vec::new();
+        let mut sources = HashMap::new();
+        let mut pasted = false;
+
+        for paste in pastes {
+            let (src_x, src_y, dst_x, dst_y, size) = match (&paste.src_chunk, &paste.dst_chunk) {
+                (Some(src), Some(dst)) => (src.x * 8, src.y * 8, dst.x * 8, dst.y * 8, 8),
+                _ => (0, 0, 0, 0, 64),
+            };
+            let source = sources.entry((paste.src_i, paste.src_j)).or_insert_with(|| {
+                let archive_id = paste.src_i as u32 | (paste.src_j as u32) << 7;
+                index.archive(archive_id).ok().and_then(|archive| MapSquare::from_archive(archive).ok())
+            });
+            let Some(source) = source else { continue };
+            pasted = true;
+
+            // Pastes can move their planes up or down.
+            let shift = |plane: u8| {
+                let shifted = plane as i16 - paste.src_plane as i16 + paste.dst_plane as i16;
+                (0..=3).contains(&shifted).then_some(shifted as u8)
+            };
+            let src_planes = paste.src_plane..paste.src_plane.saturating_add(paste.n_planes);
+
+            if let Some(src_tiles) = source.tiles() {
+                for src_plane in src_planes.clone().filter(|plane| *plane <= 3) {
+                    let Some(dst_plane) = shift(src_plane) else { continue };
+                    for (x, y) in iproduct!(0..size as usize, 0..size as usize) {
+                        tiles[[dst_plane as usize, dst_x as usize + x, dst_y as usize + y]] =
+                            src_tiles[[src_plane as usize, src_x as usize + x, src_y as usize + y]];
+                    }
+                }
+            }
+
+            for location in source.locations().into_iter().flatten() {
+                if !(src_x..src_x + size).contains(&location.x) || !(src_y..src_y + size).contains(&location.y) {
+                    continue;
+                }
+                if !src_planes.contains(&location.plane.inner()) {
+                    continue;
+                }
+                let plane = match location.plane {
+                    Watery::True(plane) => shift(plane).map(Watery::True),
+                    Watery::False(plane) => shift(plane).map(Watery::False),
+                };
+                let Some(plane) = plane else { continue };
+                locations.push(Location {
+                    plane,
+                    i,
+                    j,
+                    x: location.x - src_x + dst_x,
+                    y: location.y - src_y + dst_y,
+                    ..*location
+                });
+            }
         }
+
+        pasted.then(|| MapSquare {
+            i,
+            j,
+            tiles: Some(tiles),
+            members: None,
+            locations: Some(locations),
+            water_locations: None,
+            npcs: None,
+            water_tiles: None,
+            unknown_files: BTreeMap::new(),
+        })
     }
 
     /// Iterator over a columns of planes with their x, y coordinates
@@ -241,10 +378,54 @@ impl MapSquare {
         self.locations
     }
 
-    /// Returns a view over the `locations` field, if present.
+    /// Returns the raw environment file, if present.
+    #[cfg(all(feature = "osrs", not(feature = "2013_4_shim")))]
+    pub fn environment(&self) -> Option<&bytes::Bytes> {
+        self.environment.as_ref()
+    }
+
+    /// Returns a view over the `water_locations` field, if present.
+    ///
+    /// These are decoded on first access, against the [`water_tiles`](MapSquare::water_tiles).
+    /// If those cannot be decoded, their error is returned instead.
+    #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+    pub fn water_locations(&self) -> Option<Result<&[Location], &CacheError>> {
+        let water_locations = self.water_locations.as_ref()?;
+        if let Some(Err(e)) = self.water_tiles() {
+            return Some(Err(e));
+        }
+        match water_locations.get() {
+            Ok(water_locations) => Some(Ok(water_locations)),
+            Err(never) => match *never {},
+        }
+    }
+
+    /// Returns a view over the `npcs` field, if present.
+    ///
+    /// These are decoded on first access.
+    #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+    pub fn npcs(&self) -> Option<&Result<Vec<Npc>, CacheError>> {
+        self.npcs.as_deref()
+    }
+
+    /// Take its npcs, consuming `self`.
+    #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+    pub fn take_npcs(self) -> Option<CacheResult<Vec<Npc>>> {
+        self.npcs.map(Lazy::into_inner)
+    }
+
+    /// Returns a view over the `water_tiles` field, if present.
+    ///
+    /// These are decoded on first access.
+    #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+    pub fn water_tiles(&self) -> Option<&Result<TileArray, CacheError>> {
+        self.water_tiles.as_deref()
+    }
+
+    /// Returns the raw bytes of a file that is not decoded, such as [`MapFileType::UNKNOWN_5`].
     #[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
-    pub fn water_locations(&self) -> Option<&Result<Vec<Location>, CacheError>> {
-        self.water_locations.as_deref()
+    pub fn unknown_file(&self, file: u32) -> Option<&Bytes> {
+        self.unknown_files.get(&file)
     }
 }
 
@@ -450,24 +631,55 @@ pub fn export_locations_by_square(config: &crate::cli::Config) -> CacheResult<()
 }
 
 /// Saves all occurences of every object id as a `json` file to the folder `out/data/rs3/locations`.
+///
+/// On osrs, the environment file of each mapsquare is saved alongside as `{i}_{j}.env`.
 pub fn export_tiles_by_square(config: &crate::cli::Config) -> CacheResult<()> {
     let out = path_macro::path!(config.output / "tiles");
 
     fs::create_dir_all(&out).with_context(|| error::Io { path: out.clone() })?;
-    MapSquares::new(config)?.into_iter().par_bridge().for_each(|sq| {
-        let sq = sq.expect("error deserializing mapsquare");
+    MapSquares::new(config)?.into_iter().par_bridge().try_for_each(|sq| {
+        let sq = sq?;
         let i = sq.i;
         let j = sq.j;
+        #[cfg(all(feature = "osrs", not(feature = "2013_4_shim")))]
+        if let Some(environment) = sq.environment() {
+            let path = path!(&out / format!("{i}_{j}.env"));
+            let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+            file.write_all(environment).context(error::Io { path })?;
+        }
         if let Some(tiles) = sq.take_tiles() {
             if !tiles.is_empty() {
-                let mut file = File::create(path!(&out / format!("{i}_{j}.json"))).unwrap();
+                let path = path!(&out / format!("{i}_{j}.json"));
+                let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
                 let data = serde_json::to_string_pretty(&tiles).unwrap();
-                file.write_all(data.as_bytes()).unwrap();
+                file.write_all(data.as_bytes()).context(error::Io { path })?;
             }
         }
-    });
+        CacheResult::Ok(())
+    })
+}
 
-    Ok(())
+/// Saves all npcs of every [`MapSquare`] as a `json` file to the folder `out/data/rs3/npcs`.
+#[cfg(any(feature = "rs3", feature = "2013_4_shim"))]
+pub fn export_npcs_by_square(config: &crate::cli::Config) -> CacheResult<()> {
+    let out = path_macro::path!(config.output / "npcs");
+
+    fs::create_dir_all(&out).with_context(|| error::Io { path: out.clone() })?;
+    MapSquares::new(config)?.into_iter().par_bridge().try_for_each(|sq| {
+        let sq = sq?;
+        let i = sq.i;
+        let j = sq.j;
+        if let Some(npcs) = sq.take_npcs() {
+            let npcs = npcs?;
+            if !npcs.is_empty() {
+                let path = path!(&out / format!("{i}_{j}.json"));
+                let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+                let data = serde_json::to_string_pretty(&npcs).unwrap();
+                file.write_all(data.as_bytes()).context(error::Io { path })?;
+            }
+        }
+        Ok(())
+    })
 }
 
 #[cfg(all(test, any(feature = "rs3", feature = "osrs")))]
@@ -562,6 +774,23 @@ mod map_tests {
         Ok(())
     }
 
+    #[test]
+    fn npcs_50_50() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        // Lumbridge had a "zoom around" login screen, so it has an npc file.
+        let square = MapSquare::new(50, 50, &config)?;
+        if let Some(water_tiles) = square.water_tiles() {
+            assert!(water_tiles.is_ok());
+        }
+        let npcs = square.take_npcs().expect("50, 50 has no npc file")?;
+        assert!(!npcs.is_empty());
+        assert!(npcs.iter().all(|npc| npc.i == 50 && npc.j == 50));
+        // Hans, who walks around Lumbridge castle.
+        assert!(npcs.iter().any(|npc| npc.id == 0), "{npcs:?}");
+        Ok(())
+    }
+
     #[test]
     fn members() -> CacheResult<()> {
         let config = crate::cli::Config::env();
@@ -576,4 +805,32 @@ mod map_tests {
         }
         Ok(())
     }
+    #[test]
+    fn water_locations_use_water_tiles() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+        let mapsquares = MapSquares::new(&config)?.into_iter();
+
+        let mut adjusted = 0;
+        for sq in mapsquares {
+            let sq = sq?;
+            let (Some(Ok(water_tiles)), Some(water_locations)) = (sq.water_tiles(), sq.water_locations()) else {
+                continue;
+            };
+            let water_locations = water_locations.unwrap();
+
+            for loc in water_locations {
+                // The plane of a water location is adjusted by the settings of the water tile on plane 1 beneath it.
+                let bridged = water_tiles[[1, loc.x as usize, loc.y as usize]].settings.unwrap_or(0) & 0x2 != 0;
+                let expected = if bridged {
+                    Watery::True(loc.plane.inner())
+                } else {
+                    Watery::False(loc.plane.inner())
+                };
+                assert_eq!(loc.plane, expected, "{loc:?}");
+            }
+            adjusted += water_locations.iter().filter(|loc| matches!(loc.plane, Watery::True(_))).count();
+        }
+        assert!(adjusted > 0, "no water locations were adjusted by their water tiles");
+        Ok(())
+    }
 }
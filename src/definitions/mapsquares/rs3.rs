@@ -1,4 +1,6 @@
 use core::ops::RangeInclusive;
+#[cfg(feature = "rs3")]
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 use itertools::iproduct;
@@ -7,6 +9,8 @@ use rs3cache_backend::{
     index::{self, CacheIndex},
 };
 
+#[cfg(feature = "rs3")]
+use crate::definitions::worldmaps::{MapPastes, Paste};
 use crate::definitions::{
     indextype::IndexType,
     mapsquares::{GroupMapSquare, MapSquare, MapSquares},
@@ -22,7 +26,7 @@ impl MapSquares {
         let archive_id = (i as u32) | (j as u32) << 7;
         let archive = self.index.archive(archive_id)?;
 
-        Ok(MapSquare::from_archive(archive))
+        MapSquare::from_archive(archive)
     }
 }
 /// Iterates over all [`MapSquare`]s in arbitrary order.
@@ -51,6 +55,12 @@ pub struct GroupMapSquareIterator {
     range_i: RangeInclusive<i32>,
     range_j: RangeInclusive<i32>,
     state: std::vec::IntoIter<(u8, u8)>,
+    /// The pastes of the world map being iterated over, by their destination, if it is not the "real" one.
+    #[cfg(feature = "rs3")]
+    pastes: Option<BTreeMap<(u8, u8), Vec<Paste>>>,
+    /// The pasted mapsquares that have been built, and may be needed again by the next groups.
+    #[cfg(feature = "rs3")]
+    built: BTreeMap<(u8, u8), Option<MapSquare>>,
 }
 
 impl GroupMapSquareIterator {
@@ -69,6 +79,40 @@ impl GroupMapSquareIterator {
             range_i,
             range_j,
             state,
+            #[cfg(feature = "rs3")]
+            pastes: None,
+            #[cfg(feature = "rs3")]
+            built: BTreeMap::new(),
+        })
+    }
+
+    /// Iterates over the mapsquares of the world map that `map` describes, rather than those of the game map.
+    ///
+    /// These are iterated over in order, so that every pasted mapsquare is only built once.
+    #[cfg(feature = "rs3")]
+    pub fn pasted(
+        range_i: RangeInclusive<i32>,
+        range_j: RangeInclusive<i32>,
+        map: MapPastes,
+        config: &crate::cli::Config,
+    ) -> CacheResult<GroupMapSquareIterator> {
+        let index = CacheIndex::new(IndexType::MAPSV2, config.input.clone())?;
+
+        let mut pastes = BTreeMap::<(u8, u8), Vec<Paste>>::new();
+        for paste in map.pastes {
+            if let (Ok(i), Ok(j)) = (u8::try_from(paste.dst_i), u8::try_from(paste.dst_j)) {
+                pastes.entry((i, j)).or_default().push(paste);
+            }
+        }
+        let state = pastes.keys().copied().collect::<Vec<_>>().into_iter();
+
+        Ok(GroupMapSquareIterator {
+            index,
+            range_i,
+            range_j,
+            state,
+            pastes: Some(pastes),
+            built: BTreeMap::new(),
         })
     }
 
@@ -86,6 +130,10 @@ impl GroupMapSquareIterator {
             range_i,
             range_j,
             state: coordinates.into_iter(),
+            #[cfg(feature = "rs3")]
+            pastes: None,
+            #[cfg(feature = "rs3")]
+            built: BTreeMap::new(),
         })
     }
 }
@@ -97,15 +145,37 @@ impl Iterator for GroupMapSquareIterator {
         self.state.next().map(|(core_i, core_j)| {
             let i = core_i as i32;
             let j = core_j as i32;
-            let group_ids = iproduct!(self.range_i.clone(), self.range_j.clone())
+            let coordinates = iproduct!(self.range_i.clone(), self.range_j.clone())
                 .map(|(di, dj)| (i + di, j + dj))
-                .filter(|(i, j)| *i >= 0 && *j >= 0)
-                .map(|(i, j)| (i + (j << 7)) as u32);
+                .filter(|(i, j)| *i >= 0 && *j >= 0);
+
+            #[cfg(feature = "rs3")]
+            if let Some(pastes) = &self.pastes {
+                let index = &self.index;
+                let built = &mut self.built;
+                let mapsquares = coordinates
+                    .filter_map(|(i, j)| {
+                        let (i, j) = (u8::try_from(i).ok()?, u8::try_from(j).ok()?);
+                        built
+                            .entry((i, j))
+                            .or_insert_with(|| MapSquare::pasted(i, j, pastes.get(&(i, j))?, index))
+                            .clone()
+                    })
+                    .map(|sq| ((sq.i, sq.j), sq))
+                    .collect::<HashMap<_, _>>();
+
+                // The groups are in order, so later groups will not need the squares west of this one.
+                let west = i + *self.range_i.start();
+                built.retain(|&(i, _), _| i as i32 >= west);
+                return GroupMapSquare { core_i, core_j, mapsquares };
+            }
+
+            let group_ids = coordinates.map(|(i, j)| (i + (j << 7)) as u32);
 
             let archives = group_ids.filter_map(|archive_id| self.index.archive(archive_id).ok());
 
             let mapsquares = archives
-                .map(MapSquare::from_archive)
+                .filter_map(|archive| MapSquare::from_archive(archive).ok())
                 .map(|sq| ((sq.i, sq.j), sq))
                 .collect::<HashMap<_, _>>();
 
@@ -0,0 +1,236 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+    panic::Location,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, ReadError},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::{
+    billboards::BillboardConfig,
+    indextype::IndexType,
+    particles::{ParticleEffectorConfig, ParticleEmitterConfig},
+};
+
+/// Describes how a surface is shaded, and which [`Texture`](crate::definitions::textures::Texture)s it uses.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct MaterialConfig {
+    /// Its id.
+    pub id: u32,
+    pub version: u8,
+    /// Shader flags. The lower bits indicate which of the optional fields are present, see [`MaterialConfig::deserialize`].
+    pub flags: u32,
+    /// The texture providing colour.
+    pub diffuse: Option<u32>,
+    /// The texture providing surface normals.
+    pub normal: Option<u32>,
+    /// The texture combining metalness, roughness and similar properties.
+    pub compound: Option<u32>,
+    /// How fast the texture scrolls horizontally.
+    pub speed_u: Option<i16>,
+    /// How fast the texture scrolls vertically.
+    pub speed_v: Option<i16>,
+    /// How transparency is handled: opaque, cutoff or blended.
+    pub alpha_mode: u8,
+    /// Pixels less opaque than this are discarded, if the alpha mode is cutoff.
+    pub alpha_cutoff: Option<u8>,
+    /// The colour used when textures are disabled, in the client's 16 bit HSL format.
+    pub base_colour: Option<u16>,
+}
+
+impl MaterialConfig {
+    const HAS_DIFFUSE: u32 = 0x1;
+    const HAS_NORMAL: u32 = 0x2;
+    const HAS_COMPOUND: u32 = 0x4;
+    const HAS_UV_ANIMATION: u32 = 0x8;
+    const HAS_BASE_COLOUR: u32 = 0x10;
+
+    const ALPHA_CUTOFF: u8 = 1;
+
+    /// Returns a mapping of all [`MaterialConfig`]s.
+    ///
+    /// This fails if any material cannot be decoded, including materials with a version other than 0.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let mut materials = BTreeMap::new();
+        for archive in CacheIndex::new(IndexType::MATERIALS, config.input.clone())? {
+            let archive = archive?;
+            let id = archive.archive_id();
+            if let Some(file) = archive.file(&0) {
+                let material = Self::deserialize(id, file).context(error::Read { what: "materials" })?;
+                materials.insert(id, material);
+            }
+        }
+        Ok(materials)
+    }
+
+    /// Decodes a material.
+    ///
+    /// This layout is not taken from a client; it was inferred from the cache itself.
+    /// Decoding therefore fails on anything it does not expect: a version other than 0,
+    /// or bytes left over after the fields that the flags announce.
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let version = buffer.try_get_u8()?;
+        if version != 0 {
+            return Err(ReadError::Unsupported {
+                location: Location::caller(),
+                what: "material",
+                reason: format!("version {version}"),
+            });
+        }
+
+        let flags = buffer.try_get_u32()?;
+        let mut material = Self {
+            id,
+            version,
+            flags,
+            ..Default::default()
+        };
+
+        if flags & Self::HAS_DIFFUSE != 0 {
+            material.diffuse = Some(buffer.try_get_u32()?);
+        }
+        if flags & Self::HAS_NORMAL != 0 {
+            material.normal = Some(buffer.try_get_u32()?);
+        }
+        if flags & Self::HAS_COMPOUND != 0 {
+            material.compound = Some(buffer.try_get_u32()?);
+        }
+        if flags & Self::HAS_UV_ANIMATION != 0 {
+            material.speed_u = Some(buffer.try_get_u16()? as i16);
+            material.speed_v = Some(buffer.try_get_u16()? as i16);
+        }
+        material.alpha_mode = buffer.try_get_u8()?;
+        if material.alpha_mode == Self::ALPHA_CUTOFF {
+            material.alpha_cutoff = Some(buffer.try_get_u8()?);
+        }
+        if flags & Self::HAS_BASE_COLOUR != 0 {
+            material.base_colour = Some(buffer.try_get_u16()?);
+        }
+
+        if buffer.has_remaining() {
+            return Err(NotExhausted::new(buffer));
+        }
+        Ok(material)
+    }
+
+    /// The ids of the textures this material uses.
+    pub fn texture_ids(&self) -> impl Iterator<Item = u32> {
+        [self.diffuse, self.normal, self.compound].into_iter().flatten()
+    }
+}
+
+impl Display for MaterialConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl MaterialConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("MaterialConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("MaterialConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Everything that refers to a given texture.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct TextureReferences {
+    pub materials: Vec<u32>,
+    pub particle_emitters: Vec<u32>,
+    pub billboards: Vec<u32>,
+}
+
+/// Cross references textures to the materials, particle emitters and billboards using them.
+pub fn texture_references(
+    materials: &BTreeMap<u32, MaterialConfig>,
+    emitters: &BTreeMap<u32, ParticleEmitterConfig>,
+    billboards: &BTreeMap<u32, BillboardConfig>,
+) -> BTreeMap<u32, TextureReferences> {
+    let mut references = BTreeMap::<u32, TextureReferences>::new();
+    for material in materials.values() {
+        for texture_id in material.texture_ids() {
+            references.entry(texture_id).or_default().materials.push(material.id);
+        }
+    }
+    for emitter in emitters.values() {
+        if let Some(texture_id) = emitter.texture {
+            references.entry(texture_id as u32).or_default().particle_emitters.push(emitter.id);
+        }
+    }
+    for billboard in billboards.values() {
+        if let Some(texture_id) = billboard.texture {
+            references.entry(texture_id as u32).or_default().billboards.push(billboard.id);
+        }
+    }
+    references
+}
+
+/// Saves the materials as `materials.json`, the particle emitters and effectors as `particle_emitters.json`
+/// and `particle_effectors.json`, the billboards as `billboards.json`,
+/// and what refers to each texture as `texture_references.json`. Exposed as `--dump materials`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+
+    let materials = MaterialConfig::dump_all(config)?;
+    let emitters = ParticleEmitterConfig::dump_all(config)?;
+    let effectors = ParticleEffectorConfig::dump_all(config)?;
+    let billboards = BillboardConfig::dump_all(config)?;
+    let references = texture_references(&materials, &emitters, &billboards);
+
+    let write = |name: &str, data: String| -> CacheResult<()> {
+        let path = path!(config.output / name);
+        let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+        file.write_all(data.as_bytes()).context(error::Io { path })?;
+        Ok(())
+    };
+
+    write("materials.json", serde_json::to_string_pretty(&materials.values().collect::<Vec<_>>()).unwrap())?;
+    write("particle_emitters.json", serde_json::to_string_pretty(&emitters.values().collect::<Vec<_>>()).unwrap())?;
+    write("particle_effectors.json", serde_json::to_string_pretty(&effectors.values().collect::<Vec<_>>()).unwrap())?;
+    write("billboards.json", serde_json::to_string_pretty(&billboards.values().collect::<Vec<_>>()).unwrap())?;
+    write("texture_references.json", serde_json::to_string_pretty(&references).unwrap())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_materials() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        // `dump_all` fails if any material in the index cannot be decoded.
+        let materials = MaterialConfig::dump_all(&config)?;
+
+        let files = CacheIndex::new(IndexType::MATERIALS, config.input.clone())?
+            .into_iter()
+            .filter_map(|archive| archive.map(|archive| archive.file(&0)).transpose())
+            .collect::<CacheResult<Vec<_>>>()?
+            .len();
+        assert_eq!(materials.len(), files);
+        assert!(materials.values().all(|material| material.version == 0));
+        assert!(materials.values().any(|material| material.diffuse.is_some()));
+        Ok(())
+    }
+}
@@ -1,20 +1,27 @@
 use std::{
     fs::{self, File},
     io::Write,
-    process::Command,
+    panic::Location,
 };
 
+use ::error::Context;
 use bytes::{Buf, Bytes};
 use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
 use path_macro::path;
-use rs3cache_backend::{buf::BufExtra, error::CacheResult, index::CacheIndex};
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use rs3cache_backend::{
+    buf::{BufExtra, Eof, ReadError},
+    error::{self, CacheResult},
+    index::{CacheIndex, Initial},
+};
 
 use crate::definitions::{
     enums::{Enum, Value},
     indextype::IndexType,
 };
 
+/// Saves all music tracks as `music/{name}.ogg`. Exposed as `--dump music`.
 pub fn export_each(config: &crate::cli::Config) -> CacheResult<()> {
     let enum_archives = CacheIndex::new(IndexType::ENUM_CONFIG, config.input.clone())?;
     let archive = enum_archives.archive(5)?;
@@ -22,87 +29,344 @@ pub fn export_each(config: &crate::cli::Config) -> CacheResult<()> {
     let music_indices = Enum::deserialize(5 << 8 | 71, archive.file(&71).unwrap());
     let audio_archives = CacheIndex::new(IndexType::AUDIOSTREAMS, config.input.clone())?;
 
+    let folder = path!(config.output / "music");
+    fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
+
     let progress = ProgressBar::new(music_names.variants.len() as u64).with_style(
         ProgressStyle::with_template(&format!("   {} [{{bar:30}}] {{pos}}/{{len}}: music", style("Dumping").cyan().bright()))
             .unwrap()
             .progress_chars("=> "),
     );
 
-    for (archive_id, name) in music_names.variants.into_iter() {
-        let name = match name {
-            Value::String(s) if s.chars().all(|c| c == ' ') => format!("Unnamed track {archive_id}"),
-            // Check for bad filenames
-            // Almost never happens, so we check first before possibly creating a new string
-            Value::String(s) if s.chars().any(|c| ['?', '/', '\\'].contains(&c)) => s.chars().filter(|c| ['?', '/', '\\'].contains(c)).collect(),
-            Value::String(s) => (*s).to_owned(),
-            _ => unreachable!(),
-        };
-
-        let music_archive_id = match music_indices.variants.get(&archive_id) {
-            Some(Value::Integer(i)) => *i as u32,
-            Some(_) => unreachable!(),
-            None => {
-                progress.println(format!("    {} unable to create `{name}` is not present", style("Warning").yellow()));
-                progress.inc(1);
-                continue;
+    // Reading from the cache happens sequentially, inside the bridged iterator.
+    // Stitching the chunks together and writing them out happens in parallel.
+    let reader = progress.clone();
+    music_names
+        .variants
+        .into_iter()
+        .filter_map(move |(archive_id, name)| {
+            let name = match name {
+                Value::String(s) if s.chars().all(|c| c == ' ') => format!("Unnamed track {archive_id}"),
+                // Check for bad filenames
+                // Almost never happens, so we check first before possibly creating a new string
+                Value::String(s) if s.chars().any(|c| ['?', '/', '\\'].contains(&c)) => {
+                    s.chars().filter(|c| !['?', '/', '\\'].contains(c)).collect()
+                }
+                Value::String(s) => (*s).to_owned(),
+                _ => unreachable!(),
+            };
+
+            let music_archive_id = match music_indices.variants.get(&archive_id) {
+                Some(Value::Integer(i)) => *i as u32,
+                Some(_) => unreachable!(),
+                None => {
+                    reader.println(format!("    {} unable to create `{name}` is not present", style("Warning").yellow()));
+                    reader.inc(1);
+                    return None;
+                }
+            };
+
+            match read_chunks(&audio_archives, music_archive_id) {
+                Ok(chunks) => Some((name, chunks)),
+                Err(reason) => {
+                    reader.println(format!("    {} `{name}` {reason}", style("Warning").yellow()));
+                    reader.inc(1);
+                    None
+                }
             }
-        };
-
-        let mut data = match audio_archives.archive(music_archive_id) {
-            Ok(file) => file.file(&0).unwrap(),
-            _ => {
-                // Seems like things are lazily loaded.
-                progress.println(format!("    {} `{name}` is not present", style("Warning").yellow()));
-                progress.inc(1);
-                continue;
+        })
+        .par_bridge()
+        .try_for_each(|(name, chunks)| {
+            match assemble(chunks) {
+                Ok(data) => {
+                    let path = path!(&folder / format!("{name}.ogg"));
+                    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+                    file.write_all(&data).context(error::Io { path })?;
+                }
+                Err(e) => progress.println(format!("    {} `{name}` could not be assembled: {e}", style("Warning").yellow())),
+            }
+            progress.inc(1);
+            CacheResult::Ok(())
+        })?;
+
+    progress.println(format!("    {} music", style("Dumped").green().bright()));
+    progress.finish_and_clear();
+
+    Ok(())
+}
+
+/// Reads all the chunks that make up a music track.
+///
+/// The first chunk has its [`Jaga`] header removed.
+pub(crate) fn read_chunks(audio_archives: &CacheIndex<Initial>, music_archive_id: u32) -> Result<Vec<Bytes>, String> {
+    let fetch = |archive_id| audio_archives.archive(archive_id).ok().and_then(|archive| archive.file(&0));
+
+    // Seems like things are lazily loaded.
+    let mut data = fetch(music_archive_id).ok_or("is not present")?;
+    let jaga = decode_first(&mut data).map_err(|e| format!("has a malformed header: {e}"))?;
+
+    let mut chunks = vec![data];
+
+    // The first one is the one that's already read
+    for chunk in jaga.chunks.iter().skip(1) {
+        let data = fetch(chunk.archive_id).ok_or_else(|| format!("is missing chunk {}", chunk.archive_id))?;
+        if data.len() != chunk.length as usize {
+            return Err(format!(
+                "has a chunk {} of length {}, expected {}",
+                chunk.archive_id,
+                data.len(),
+                chunk.length
+            ));
+        }
+        chunks.push(data);
+    }
+    Ok(chunks)
+}
+
+/// Concatenates the Ogg streams of a track's chunks into a single Ogg Vorbis stream.
+///
+/// Every chunk is a stream of its own, so simply concatenating them does not work.
+/// Instead, chunks that repeat the identification and setup headers of the preceding chunk (or have none)
+/// are merged into it: their pages are renumbered, moved to its serial, and have their granule positions
+/// offset by the length of the preceding chunks, and their repeated headers are dropped.
+///
+/// A chunk with different headers cannot be decoded with those of the preceding chunk,
+/// so it starts a new logical stream, chained after the preceding one.
+pub fn assemble(chunks: impl IntoIterator<Item = Bytes>) -> Result<Vec<u8>, ReadError> {
+    let mut links: Vec<OggStream> = Vec::new();
+
+    for chunk in chunks {
+        let stream = OggStream::deserialize(chunk)?;
+        match links.last_mut() {
+            Some(link) if stream.headers.is_empty() || link.shares_setup(&stream) => link.append(stream),
+            _ => links.push(stream),
+        }
+    }
+
+    let serial = links.first().and_then(OggStream::serial).unwrap_or_default();
+    let mut out = Vec::with_capacity(links.iter().flat_map(OggStream::iter).map(OggPage::len).sum());
+    for (n, link) in links.iter_mut().enumerate() {
+        // Every link of a chained stream needs a serial of its own.
+        link.write(serial.wrapping_add(n as u32), &mut out);
+    }
+    Ok(out)
+}
+
+/// The pages of a single logical Ogg Vorbis stream.
+#[derive(Debug, Clone)]
+struct OggStream {
+    /// The pages with the identification, comment and setup headers.
+    headers: Vec<OggPage>,
+    /// The pages with audio.
+    pages: Vec<OggPage>,
+}
+
+impl OggStream {
+    fn deserialize(mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut headers = Vec::new();
+        let mut pages = Vec::new();
+
+        while buffer.has_remaining() {
+            let page = OggPage::deserialize(&mut buffer)?;
+            if pages.is_empty() && page.is_header() {
+                headers.push(page);
+            } else {
+                pages.push(page);
             }
-        };
-
-        let jaga = decode_first(&mut data);
-        let out = path_macro::path!(config.output / "music" / name);
-        fs::create_dir_all(&out).unwrap();
-
-        let file_name = path!(&out / format!("{music_archive_id}.ogg"));
-        let mut file = File::create(&file_name).unwrap();
-        file.write_all(&data).unwrap();
-
-        // Prepare a process that invokes `Sox` to concatenate all these files.
-        // This is really scuffed, but SoX seems to be only program capable of handling it
-        // Simply concatenating the files should normally work for .ogg files, but not for these...
-        // See also https://github.com/villermen/runescape-cache-tools/issues/8
-        let mut command = Command::new("sox");
-        command.arg(file_name);
-
-        // The first one is the one that's already written
-        for chunk in jaga.chunks.iter().skip(1) {
-            let archive_id = chunk.archive_id;
-            let more_data = audio_archives.archive(archive_id).unwrap().file(&0).unwrap();
-            assert_eq!(more_data.len(), chunk.length as usize);
-            let file_name = path!(&out / format!("{archive_id}.ogg"));
-            let mut file = File::create(&file_name).unwrap();
-            file.write_all(&more_data).unwrap();
-            command.arg(file_name);
         }
 
-        command.arg(path!(config.output / "music" / format!("{name}.ogg")));
-        if let Err(e) = command.output() {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                progress.println(format!(
-                    "    {} The `sox` program is required to concatenate audio files. Please ensure it is installed{}.",
-                    style("Error").red(),
-                    if cfg!(windows) { "and visible in PATH" } else { "" }
-                ));
-                progress.finish_and_clear();
-                return Ok(());
+        Ok(Self { headers, pages })
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &OggPage> {
+        self.headers.iter().chain(&self.pages)
+    }
+
+    fn serial(&self) -> Option<u32> {
+        self.iter().next().map(|page| page.serial)
+    }
+
+    /// The packets in the header pages, split on their lacing values.
+    fn header_packets(&self) -> Vec<Vec<u8>> {
+        let mut packets = Vec::new();
+        let mut packet = Vec::new();
+
+        for page in &self.headers {
+            let mut body = &page.body[..];
+            for &lacing in page.segments.iter() {
+                let (segment, rest) = body.split_at(lacing as usize);
+                packet.extend_from_slice(segment);
+                body = rest;
+                if lacing < 255 {
+                    packets.push(std::mem::take(&mut packet));
+                }
             }
-            panic!("{e}");
         }
-        progress.inc(1);
+        packets
     }
-    progress.println(format!("    {} music", style("Dumped").green().bright()));
-    progress.finish_and_clear();
 
-    Ok(())
+    /// Whether `other` has the same identification and setup headers as this stream,
+    /// and its audio can be decoded with them. The comment headers may differ.
+    fn shares_setup(&self, other: &Self) -> bool {
+        match (&self.header_packets()[..], &other.header_packets()[..]) {
+            ([identification, _, setup], [other_identification, _, other_setup]) => identification == other_identification && setup == other_setup,
+            _ => false,
+        }
+    }
+
+    /// Appends the audio of `other` to this stream, dropping its headers.
+    fn append(&mut self, other: Self) {
+        let granule_offset = self
+            .pages
+            .iter()
+            .rev()
+            .map(|page| page.granule_position)
+            .find(|&granule| granule != OggPage::NO_GRANULE)
+            .unwrap_or(0);
+
+        self.pages.extend(other.pages.into_iter().map(|mut page| {
+            if page.granule_position != OggPage::NO_GRANULE {
+                page.granule_position += granule_offset;
+            }
+            page
+        }));
+    }
+
+    /// Writes this stream to `out` under `serial`, with its pages renumbered
+    /// and only its first and last page marked as such.
+    fn write(&mut self, serial: u32, out: &mut Vec<u8>) {
+        let page_count = self.headers.len() + self.pages.len();
+        for (sequence, page) in self.headers.iter_mut().chain(&mut self.pages).enumerate() {
+            page.serial = serial;
+            page.sequence = sequence as u32;
+            page.header_type &= !(OggPage::BOS | OggPage::EOS);
+            if sequence == 0 {
+                page.header_type |= OggPage::BOS;
+            }
+            if sequence + 1 == page_count {
+                page.header_type |= OggPage::EOS;
+            }
+            page.write(out);
+        }
+    }
+}
+
+/// A single page of an Ogg bitstream.
+///
+/// See <https://xiph.org/ogg/doc/framing.html>.
+#[derive(Debug, Clone)]
+pub struct OggPage {
+    pub header_type: u8,
+    pub granule_position: u64,
+    pub serial: u32,
+    pub sequence: u32,
+    segments: Bytes,
+    body: Bytes,
+}
+
+impl OggPage {
+    const CAPTURE_PATTERN: &'static [u8] = b"OggS";
+    const HEADER_LEN: usize = 27;
+
+    /// The page contains data of a packet continued from the previous page.
+    pub const CONTINUED: u8 = 0x01;
+    /// The page is the first page of the stream.
+    pub const BOS: u8 = 0x02;
+    /// The page is the last page of the stream.
+    pub const EOS: u8 = 0x04;
+    /// No packets finish on the page.
+    pub const NO_GRANULE: u64 = u64::MAX;
+
+    pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+        let header = buffer.try_get_array::<{ Self::HEADER_LEN }>()?;
+        let (capture_pattern, mut fields) = header.split_at(Self::CAPTURE_PATTERN.len());
+        if capture_pattern != Self::CAPTURE_PATTERN {
+            return Err(ReadError::BadMagic {
+                location: Location::caller(),
+                expected: Self::CAPTURE_PATTERN,
+                found: capture_pattern.to_vec(),
+            });
+        }
+
+        let _version = fields.get_u8();
+        let header_type = fields.get_u8();
+        let granule_position = fields.get_u64_le();
+        let serial = fields.get_u32_le();
+        let sequence = fields.get_u32_le();
+        let _crc = fields.get_u32_le();
+        let segment_count = fields.get_u8() as usize;
+
+        if buffer.remaining() < segment_count {
+            return Err(Eof::new(segment_count, buffer.remaining()));
+        }
+        let segments = buffer.split_to(segment_count);
+
+        let body_len = segments.iter().map(|&len| len as usize).sum();
+        if buffer.remaining() < body_len {
+            return Err(Eof::new(body_len, buffer.remaining()));
+        }
+        let body = buffer.split_to(body_len);
+
+        Ok(Self {
+            header_type,
+            granule_position,
+            serial,
+            sequence,
+            segments,
+            body,
+        })
+    }
+
+    /// Whether this page belongs to the identification, comment or setup headers of a Vorbis stream.
+    fn is_header(&self) -> bool {
+        let starts_header = matches!(self.body.first(), Some(1 | 3 | 5)) && self.body.get(1..7) == Some(&b"vorbis"[..]);
+        self.granule_position == 0 && (self.header_type & (Self::BOS | Self::CONTINUED) != 0 || starts_header)
+    }
+
+    /// The length of this page once written.
+    fn len(&self) -> usize {
+        Self::HEADER_LEN + self.segments.len() + self.body.len()
+    }
+
+    /// Writes this page to `out`, with a freshly calculated checksum.
+    pub fn write(&self, out: &mut Vec<u8>) {
+        let start = out.len();
+        out.extend_from_slice(Self::CAPTURE_PATTERN);
+        out.push(0);
+        out.push(self.header_type);
+        out.extend_from_slice(&self.granule_position.to_le_bytes());
+        out.extend_from_slice(&self.serial.to_le_bytes());
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.push(self.segments.len() as u8);
+        out.extend_from_slice(&self.segments);
+        out.extend_from_slice(&self.body);
+
+        let crc = crc32(&out[start..]);
+        out[start + 22..start + 26].copy_from_slice(&crc.to_le_bytes());
+    }
+}
+
+/// Lookup table for the CRC used by Ogg, with polynomial 0x04C11DB7.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut r = (i as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            r = if r & 0x8000_0000 != 0 { (r << 1) ^ 0x04C1_1DB7 } else { r << 1 };
+            bit += 1;
+        }
+        table[i] = r;
+        i += 1;
+    }
+    table
+};
+
+fn crc32(data: &[u8]) -> u32 {
+    data.iter()
+        .fold(0, |crc, &byte| (crc << 8) ^ CRC_TABLE[((crc >> 24) as u8 ^ byte) as usize])
 }
 
 #[derive(Debug, Clone)]
@@ -111,7 +375,7 @@ pub struct Jaga {
     pub int_2: u32,
     pub sample_frequency: u32,
     pub int_3: u32,
-    chunks: Vec<ChunkDescriptor>,
+    pub chunks: Vec<ChunkDescriptor>,
 }
 
 #[derive(Debug, Clone)]
@@ -121,28 +385,120 @@ pub struct ChunkDescriptor {
     pub archive_id: u32,
 }
 
-fn decode_first(buffer: &mut Bytes) -> Jaga {
-    let signature = buffer.get_array::<4>();
-    assert_eq!(&signature, b"JAGA");
+fn decode_first(buffer: &mut Bytes) -> Result<Jaga, ReadError> {
+    let signature = buffer.try_get_array::<4>()?;
+    if &signature != b"JAGA" {
+        return Err(ReadError::BadMagic {
+            location: Location::caller(),
+            expected: b"JAGA",
+            found: signature.to_vec(),
+        });
+    }
 
-    let int_1 = buffer.get_u32();
-    let int_2 = buffer.get_u32();
-    let sample_frequency = buffer.get_u32();
-    let int_3 = buffer.get_u32();
-    let chunk_count = buffer.get_u32();
+    let int_1 = buffer.try_get_u32()?;
+    let int_2 = buffer.try_get_u32()?;
+    let sample_frequency = buffer.try_get_u32()?;
+    let int_3 = buffer.try_get_u32()?;
+    let chunk_count = buffer.try_get_u32()?;
     let chunks = (0..chunk_count)
-        .map(|position| ChunkDescriptor {
-            position,
-            length: buffer.get_u32(),
-            archive_id: buffer.get_u32(),
+        .map(|position| {
+            Ok(ChunkDescriptor {
+                position,
+                length: buffer.try_get_u32()?,
+                archive_id: buffer.try_get_u32()?,
+            })
         })
-        .collect();
+        .collect::<Result<_, ReadError>>()?;
 
-    Jaga {
+    Ok(Jaga {
         int_1,
         int_2,
         sample_frequency,
         int_3,
         chunks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc_matches_reference() {
+        // The standard check value for this crc variant.
+        assert_eq!(crc32(b"123456789"), 0x89A1_897F);
+    }
+
+    /// Creates a page holding `packets`, which must be shorter than 255 bytes each.
+    fn page(header_type: u8, granule_position: u64, serial: u32, sequence: u32, packets: &[&[u8]]) -> OggPage {
+        OggPage {
+            header_type,
+            granule_position,
+            serial,
+            sequence,
+            segments: packets.iter().map(|packet| packet.len() as u8).collect(),
+            body: packets.concat().into(),
+        }
+    }
+
+    /// Creates a chunk with its own headers and two audio pages, as they are stored in the cache.
+    fn chunk(serial: u32, setup: &[u8], granules: [u64; 2]) -> Bytes {
+        let mut out = Vec::new();
+        page(OggPage::BOS, 0, serial, 0, &[b"\x01vorbis identification"]).write(&mut out);
+        page(0, 0, serial, 1, &[b"\x03vorbis comment", setup]).write(&mut out);
+        page(0, granules[0], serial, 2, &[b"audio"]).write(&mut out);
+        page(OggPage::EOS, granules[1], serial, 3, &[b"more audio"]).write(&mut out);
+        out.into()
+    }
+
+    /// Splits an assembled stream into its pages, checking the checksum of each.
+    fn pages(data: Vec<u8>) -> Vec<OggPage> {
+        let mut buffer = Bytes::from(data);
+        let mut pages = Vec::new();
+        while buffer.has_remaining() {
+            let raw = buffer.clone();
+            let page = OggPage::deserialize(&mut buffer).unwrap();
+
+            let mut raw = raw[..page.len()].to_vec();
+            let crc = u32::from_le_bytes(raw[22..26].try_into().unwrap());
+            raw[22..26].fill(0);
+            assert_eq!(crc, crc32(&raw), "bad checksum on page {}", page.sequence);
+
+            pages.push(page);
+        }
+        pages
+    }
+
+    #[test]
+    fn assemble_merges_chunks_with_the_same_setup() {
+        let setup = b"\x05vorbis setup";
+        let data = assemble([chunk(7, setup, [100, 200]), chunk(8, setup, [50, 120])]).unwrap();
+        let pages = pages(data);
+
+        let sequences = pages.iter().map(|page| page.sequence).collect::<Vec<_>>();
+        assert_eq!(sequences, [0, 1, 2, 3, 4, 5]);
+        let granules = pages.iter().map(|page| page.granule_position).collect::<Vec<_>>();
+        assert_eq!(granules, [0, 0, 100, 200, 250, 320]);
+        assert!(pages.iter().all(|page| page.serial == 7));
+
+        let flags = pages.iter().map(|page| page.header_type).collect::<Vec<_>>();
+        assert_eq!(flags, [OggPage::BOS, 0, 0, 0, 0, OggPage::EOS]);
+        assert_eq!(&pages[4].body[..], b"audio");
+    }
+
+    #[test]
+    fn assemble_chains_chunks_with_a_different_setup() {
+        let data = assemble([chunk(7, b"\x05vorbis setup", [100, 200]), chunk(7, b"\x05vorbis other setup", [50, 120])]).unwrap();
+        let pages = pages(data);
+
+        let sequences = pages.iter().map(|page| page.sequence).collect::<Vec<_>>();
+        assert_eq!(sequences, [0, 1, 2, 3, 0, 1, 2, 3]);
+        let granules = pages.iter().map(|page| page.granule_position).collect::<Vec<_>>();
+        assert_eq!(granules, [0, 0, 100, 200, 0, 0, 50, 120]);
+        let serials = pages.iter().map(|page| page.serial).collect::<Vec<_>>();
+        assert_eq!(serials, [7, 7, 7, 7, 8, 8, 8, 8]);
+
+        let flags = pages.iter().map(|page| page.header_type).collect::<Vec<_>>();
+        assert_eq!(flags, [OggPage::BOS, 0, 0, OggPage::EOS, OggPage::BOS, 0, 0, OggPage::EOS]);
     }
 }
@@ -1,7 +1,11 @@
 use bytes::{Buf, Bytes};
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::buf::{BufExtra, ReadError};
 use serde::Serialize;
 
 /// A non player character.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
 #[derive(Copy, Clone, Debug, Serialize)]
 pub struct Npc {
     /// The plane a.k.a elevation.
@@ -25,27 +29,23 @@ pub struct Npc {
 
 impl Npc {
     /// Constructor for [`Npc`].
-    pub fn deserialize(i: u8, j: u8, mut buffer: Bytes) -> Vec<Npc> {
-        let length = buffer.remaining();
+    pub fn deserialize(i: u8, j: u8, mut buffer: Bytes) -> Result<Vec<Npc>, ReadError> {
+        let mut npcs = Vec::with_capacity(buffer.remaining() / 4);
 
-        let mut npcs = Vec::with_capacity(length / 4);
-
-        for _ in 0..(length / 4) {
-            let value = buffer.get_u16();
+        while buffer.has_remaining() {
+            let value = buffer.try_get_u16()?;
 
             let plane = (value >> 14) as u8;
             let x = (value >> 7 & 0x3F) as u8;
             let y = (value & 0x3F) as u8;
 
-            let id = buffer.get_u16() as u32;
+            let id = buffer.try_get_u16()? as u32;
 
             let npc = Npc { plane, i, j, x, y, id };
 
             npcs.push(npc);
         }
 
-        debug_assert!(!buffer.has_remaining());
-
-        npcs
+        Ok(npcs)
     }
 }
@@ -32,7 +32,7 @@ pub struct Overlay {
     pub texture: Option<u8>,
 
     #[cfg(any(feature = "rs3", feature = "2008_3_shim"))]
-    op_3: Option<u16>,
+    pub texture_id: Option<u16>,
 
     op_5: Option<bool>,
     /// Secondary colour of the [`Overlay`] configuration.
@@ -91,7 +91,7 @@ impl Overlay {
                 #[cfg(feature = "osrs")]
                 2 => overlay.texture = Some(buffer.get_u8()),
                 #[cfg(any(feature = "rs3", feature = "2008_3_shim"))]
-                3 => overlay.op_3 = Some(buffer.get_u16()),
+                3 => overlay.texture_id = Some(buffer.get_u16()),
                 5 => overlay.op_5 = Some(true),
                 7 => overlay.secondary_colour = Some(buffer.get_rgb()),
                 #[cfg(any(feature = "rs3", feature = "2008_3_shim"))]
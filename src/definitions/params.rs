@@ -0,0 +1,207 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::{
+    definitions::indextype::{ConfigType, IndexType},
+    structures::paramtable::ParamTable,
+    types::typed_value::ScriptType,
+};
+
+/// Describes a key of a [`ParamTable`](crate::structures::paramtable::ParamTable).
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ParamConfig {
+    /// Its id.
+    pub id: u32,
+    /// The type of the values, as the character the client uses for it.
+    pub type_char: Option<u8>,
+    /// The value used if a [`ParamTable`](crate::structures::paramtable::ParamTable) does not have this key.
+    pub default_int: Option<i32>,
+    /// Whether the param is disabled on free to play worlds. This defaults to `true`.
+    pub autodisable: Option<bool>,
+    /// The value used if a [`ParamTable`](crate::structures::paramtable::ParamTable) does not have this key.
+    pub default_string: Option<JString<Bytes>>,
+    /// The type of the values.
+    #[cfg(feature = "rs3")]
+    pub type_id: Option<u16>,
+}
+
+impl ParamConfig {
+    /// Returns a mapping of all [`ParamConfig`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let params = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::PARAMS)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "params" })?;
+        Ok(params)
+    }
+
+    /// The type of the values.
+    pub fn script_type(&self) -> ScriptType {
+        #[cfg(feature = "rs3")]
+        if let Some(type_id) = self.type_id {
+            return ScriptType::from_id(type_id);
+        }
+        self.type_char.map(ScriptType::from_char).unwrap_or_default()
+    }
+
+    /// Resolves the values of `tables` if `--resolve-params` was passed, see [`ParamTable::resolve`].
+    pub fn resolve_tables<'a>(config: &crate::cli::Config, tables: impl IntoIterator<Item = &'a mut ParamTable>) -> CacheResult<()> {
+        if config.resolve_params {
+            let params = Self::dump_all(config)?;
+            for table in tables {
+                table.resolve(&params);
+            }
+        }
+        Ok(())
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut param = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(param);
+                        }
+                    }
+                    1 => param.type_char = Some(buffer.try_get_u8()?),
+                    2 => param.default_int = Some(buffer.try_get_i32()?),
+                    4 => param.autodisable = Some(false),
+                    5 => param.default_string = Some(buffer.try_get_string()?),
+                    #[cfg(feature = "rs3")]
+                    101 => param.type_id = Some(buffer.try_get_unsigned_smart()?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: param.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for ParamConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl ParamConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("ParamConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("ParamConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the param configs as `params.json`. Exposed as `--dump params`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let params = ParamConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "params.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&params).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{structures::paramtable::Param, types::typed_value::TypedValue};
+
+    #[test]
+    fn decode_params() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let params = ParamConfig::dump_all(&config)?;
+        assert!(params.values().any(|param| param.type_char.is_some()));
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_only_present_keys() {
+        let configs = BTreeMap::from([
+            (
+                1,
+                ParamConfig {
+                    id: 1,
+                    type_char: Some(b'i'),
+                    default_int: Some(0),
+                    ..Default::default()
+                },
+            ),
+            (
+                2,
+                ParamConfig {
+                    id: 2,
+                    type_char: Some(b'i'),
+                    default_int: Some(7),
+                    ..Default::default()
+                },
+            ),
+            (3, ParamConfig { id: 3, ..Default::default() }),
+        ]);
+        let mut table = ParamTable {
+            params: BTreeMap::from([(1, Param::Integer(3))]),
+            typed: None,
+        };
+        table.resolve(&configs);
+
+        let typed = table.typed.as_ref().unwrap();
+        assert_eq!(typed.len(), 1);
+        assert_eq!(typed.get(&1), Some(&TypedValue::Integer(3)));
+
+        assert_eq!(table.get_or_default(1, &configs), Some(Param::Integer(3)));
+        assert_eq!(table.get_or_default(2, &configs), Some(Param::Integer(7)));
+        assert_eq!(table.get_or_default(3, &configs), None);
+    }
+}
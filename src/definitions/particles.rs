@@ -0,0 +1,326 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::IndexType;
+
+/// Reads a list of magnets, referring to [`ParticleEffectorConfig`]s.
+fn read_magnets(buffer: &mut Bytes) -> Result<Vec<u16>, ReadError> {
+    let count = buffer.try_get_u8()?;
+    (0..count).map(|_| buffer.try_get_u16()).collect()
+}
+
+/// Describes an emitter of particles, which can be attached to models.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ParticleEmitterConfig {
+    /// Its id.
+    pub id: u32,
+    /// The minimum and maximum horizontal angles particles are emitted at.
+    pub angle_h: Option<[u16; 2]>,
+    /// The minimum and maximum vertical angles particles are emitted at.
+    pub angle_v: Option<[u16; 2]>,
+    pub unknown_2: Option<u8>,
+    /// The minimum and maximum speed of particles.
+    pub speed: Option<[i32; 2]>,
+    pub unknown_4: Option<(u8, i8)>,
+    /// The size of particles.
+    pub size: Option<u16>,
+    /// The minimum and maximum starting colour of particles, as ARGB.
+    pub start_colour: Option<[u32; 2]>,
+    /// The minimum and maximum lifetime of particles.
+    pub lifetime: Option<[u16; 2]>,
+    /// The minimum and maximum number of particles emitted per tick.
+    pub rate: Option<[u16; 2]>,
+    /// Effectors affecting only these particles.
+    pub local_magnets: Option<Vec<u16>>,
+    /// Effectors affecting these and other particles.
+    pub global_magnets: Option<Vec<u16>>,
+    /// The minimum graphics setting at which this emitter is active.
+    pub minimum_setting: Option<i8>,
+    pub unknown_13: Option<i8>,
+    pub unknown_14: Option<u16>,
+    /// The texture drawn for each particle.
+    pub texture: Option<u16>,
+    pub activation: Option<Activation>,
+    /// The speed particles have at the end of their life.
+    pub end_speed: Option<u16>,
+    /// The colour particles have at the end of their life, as ARGB.
+    pub end_colour: Option<u32>,
+    pub unknown_19: Option<u8>,
+    /// When particles start fading to their end colour, as a percentage of their lifetime.
+    pub colour_fading: Option<u8>,
+    /// When particles start fading to their end transparency, as a percentage of their lifetime.
+    pub alpha_fading: Option<u8>,
+    /// The size particles have at the end of their life.
+    pub end_size: Option<i32>,
+    /// When particles start changing to their end size, as a percentage of their lifetime.
+    pub size_fading: Option<u8>,
+    /// Whether colour variance is applied independently to each channel.
+    pub independent_colour_variance: Option<bool>,
+    pub general_magnets: Option<Vec<u16>>,
+    pub unknown_26: Option<bool>,
+    pub unknown_27: Option<u16>,
+    pub unknown_28: Option<u8>,
+    pub unknown_29: Option<i16>,
+    pub unknown_30: Option<bool>,
+    /// The minimum and maximum size of particles.
+    pub size_range: Option<[u16; 2]>,
+    pub unknown_32: Option<bool>,
+    pub unknown_33: Option<bool>,
+    pub unknown_34: Option<bool>,
+}
+
+/// Describes when an emitter is active.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub struct Activation {
+    pub active_first: bool,
+    pub delay: u16,
+    pub duration: u16,
+    pub periodic: bool,
+}
+
+impl ParticleEmitterConfig {
+    /// Returns a mapping of all [`ParticleEmitterConfig`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let emitters = CacheIndex::new(IndexType::PARTICLES, config.input.clone())?
+            .archive(0)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "particle emitters" })?;
+        Ok(emitters)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut emitter = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(emitter);
+                        }
+                    }
+                    1 => {
+                        emitter.angle_h = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]);
+                        emitter.angle_v = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]);
+                    }
+                    2 => emitter.unknown_2 = Some(buffer.try_get_u8()?),
+                    3 => emitter.speed = Some([buffer.try_get_i32()?, buffer.try_get_i32()?]),
+                    4 => emitter.unknown_4 = Some((buffer.try_get_u8()?, buffer.try_get_i8()?)),
+                    5 => emitter.size = Some(buffer.try_get_u16()?),
+                    6 => emitter.start_colour = Some([buffer.try_get_u32()?, buffer.try_get_u32()?]),
+                    7 => emitter.lifetime = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]),
+                    8 => emitter.rate = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]),
+                    9 => emitter.local_magnets = Some(read_magnets(&mut buffer)?),
+                    10 => emitter.global_magnets = Some(read_magnets(&mut buffer)?),
+                    12 => emitter.minimum_setting = Some(buffer.try_get_i8()?),
+                    13 => emitter.unknown_13 = Some(buffer.try_get_i8()?),
+                    14 => emitter.unknown_14 = Some(buffer.try_get_u16()?),
+                    15 => emitter.texture = Some(buffer.try_get_u16()?),
+                    16 => {
+                        emitter.activation = Some(Activation {
+                            active_first: buffer.try_get_u8()? == 1,
+                            delay: buffer.try_get_u16()?,
+                            duration: buffer.try_get_u16()?,
+                            periodic: buffer.try_get_u8()? == 1,
+                        })
+                    }
+                    17 => emitter.end_speed = Some(buffer.try_get_u16()?),
+                    18 => emitter.end_colour = Some(buffer.try_get_u32()?),
+                    19 => emitter.unknown_19 = Some(buffer.try_get_u8()?),
+                    20 => emitter.colour_fading = Some(buffer.try_get_u8()?),
+                    21 => emitter.alpha_fading = Some(buffer.try_get_u8()?),
+                    22 => emitter.end_size = Some(buffer.try_get_i32()?),
+                    23 => emitter.size_fading = Some(buffer.try_get_u8()?),
+                    24 => emitter.independent_colour_variance = Some(false),
+                    25 => emitter.general_magnets = Some(read_magnets(&mut buffer)?),
+                    26 => emitter.unknown_26 = Some(false),
+                    27 => emitter.unknown_27 = Some(buffer.try_get_u16()?),
+                    28 => emitter.unknown_28 = Some(buffer.try_get_u8()?),
+                    29 => emitter.unknown_29 = Some(buffer.try_get_u16()? as i16),
+                    30 => emitter.unknown_30 = Some(true),
+                    31 => emitter.size_range = Some([buffer.try_get_u16()?, buffer.try_get_u16()?]),
+                    32 => emitter.unknown_32 = Some(false),
+                    33 => emitter.unknown_33 = Some(true),
+                    34 => emitter.unknown_34 = Some(false),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: emitter.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Describes a force acting on particles, such as gravity or wind.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ParticleEffectorConfig {
+    /// Its id.
+    pub id: u32,
+    pub unknown_1: Option<u16>,
+    pub unknown_2: Option<u8>,
+    /// The direction of the force.
+    pub direction: Option<[i32; 3]>,
+    pub falloff: Option<(u8, i32)>,
+    pub unknown_5: Option<u8>,
+    pub unknown_6: Option<u8>,
+    /// How far the force reaches.
+    pub range: Option<u16>,
+    pub unknown_8: Option<bool>,
+    pub unknown_9: Option<u8>,
+}
+
+impl ParticleEffectorConfig {
+    /// Returns a mapping of all [`ParticleEffectorConfig`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let effectors = CacheIndex::new(IndexType::PARTICLES, config.input.clone())?
+            .archive(1)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "particle effectors" })?;
+        Ok(effectors)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut effector = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(effector);
+                        }
+                    }
+                    1 => effector.unknown_1 = Some(buffer.try_get_u16()?),
+                    2 => effector.unknown_2 = Some(buffer.try_get_u8()?),
+                    3 => effector.direction = Some([buffer.try_get_i32()?, buffer.try_get_i32()?, buffer.try_get_i32()?]),
+                    4 => effector.falloff = Some((buffer.try_get_u8()?, buffer.try_get_i32()?)),
+                    5 => effector.unknown_5 = Some(buffer.try_get_u8()?),
+                    6 => effector.unknown_6 = Some(buffer.try_get_u8()?),
+                    7 => effector.range = Some(buffer.try_get_u16()?),
+                    8 => effector.unknown_8 = Some(true),
+                    9 => effector.unknown_9 = Some(buffer.try_get_u8()?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: effector.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for ParticleEmitterConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+impl Display for ParticleEffectorConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl ParticleEmitterConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("ParticleEmitterConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("ParticleEmitterConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl ParticleEffectorConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("ParticleEffectorConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("ParticleEffectorConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_particles() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let emitters = ParticleEmitterConfig::dump_all(&config)?;
+        assert!(emitters.values().any(|emitter| emitter.texture.is_some()));
+        ParticleEffectorConfig::dump_all(&config)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    definitions::indextype::{ConfigType, IndexType},
+    structures::paramtable::ParamTable,
+    types::variables::{Varbit, Varp, VarpOrVarbit},
+};
+
+/// Describes a quest or miniquest.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct QuestConfig {
+    /// Its id.
+    pub id: u32,
+    /// Its name.
+    pub name: Option<JString<Bytes>>,
+    /// The name it is sorted by in the quest list.
+    pub sort_name: Option<JString<Bytes>>,
+    /// The varps tracking progress, and the values at which the quest is started and completed.
+    pub progress_varps: Option<Vec<VarpProgress>>,
+    /// The varbits tracking progress, and the values at which the quest is started and completed.
+    pub progress_varbits: Option<Vec<VarbitProgress>>,
+    pub unknown_5: Option<u16>,
+    /// Whether this is a quest, miniquest or saga.
+    pub r#type: Option<u8>,
+    /// From novice (0) to grandmaster (4), or special (250).
+    pub difficulty: Option<u8>,
+    /// Whether this quest is members only.
+    pub members: Option<bool>,
+    /// The quest points awarded on completion.
+    pub quest_points: Option<u8>,
+    /// The map squares the quest starts in.
+    pub start_locations: Option<Vec<u32>>,
+    /// From very short (0) to very very long (4).
+    pub length: Option<u8>,
+    /// The quests that must be completed first.
+    pub required_quests: Option<Vec<u16>>,
+    /// The skill levels required to start.
+    pub required_skills: Option<Vec<SkillRequirement>>,
+    /// The quest points required to start.
+    pub required_quest_points: Option<u16>,
+    /// The sprite shown in the quest list.
+    pub sprite: Option<u32>,
+    /// Other requirements, such as partial progress in other quests.
+    pub other_requirements: Option<Vec<VarRequirement>>,
+    #[serde(flatten)]
+    pub params: Option<ParamTable>,
+}
+
+/// Progress of a quest, tracked by a [`Varp`].
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct VarpProgress {
+    pub varp: Varp,
+    pub started: i32,
+    pub completed: i32,
+}
+
+/// Progress of a quest, tracked by a [`Varbit`].
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct VarbitProgress {
+    pub varbit: Varbit,
+    pub started: i32,
+    pub completed: i32,
+}
+
+/// A skill level required to start a quest.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct SkillRequirement {
+    pub skill: u8,
+    pub level: u8,
+}
+
+/// A requirement on the value of a variable, with a description shown in the quest journal.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[derive(Serialize, Clone, Debug)]
+pub struct VarRequirement {
+    #[serde(flatten)]
+    pub var: VarpOrVarbit,
+    pub min: i32,
+    pub max: i32,
+    pub description: JString<Bytes>,
+}
+
+impl VarRequirement {
+    fn deserialize(is_varbit: bool, buffer: &mut Bytes) -> Result<Self, ReadError> {
+        let id = buffer.try_get_u16()?;
+        let var = if is_varbit { VarpOrVarbit::Varbit(id) } else { VarpOrVarbit::Varp(id) };
+        let min = buffer.try_get_i32()?;
+        let max = buffer.try_get_i32()?;
+        let description = buffer.try_get_string()?;
+        Ok(Self { var, min, max, description })
+    }
+}
+
+/// Reads a string, prefixed by a version byte.
+fn read_padded_string(buffer: &mut Bytes) -> Result<JString<Bytes>, ReadError> {
+    buffer.try_get_u8()?;
+    buffer.try_get_string()
+}
+
+impl QuestConfig {
+    /// Returns a mapping of all [`QuestConfig`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let quests = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::QUESTS)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "quest configs" })?;
+        Ok(quests)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut quest = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(quest);
+                        }
+                    }
+                    1 => quest.name = Some(read_padded_string(&mut buffer)?),
+                    2 => quest.sort_name = Some(read_padded_string(&mut buffer)?),
+                    3 => {
+                        let count = buffer.try_get_u8()?;
+                        let progress = (0..count).map(|_| {
+                            try {
+                                VarpProgress {
+                                    varp: Varp::new(buffer.try_get_u16()?),
+                                    started: buffer.try_get_i32()?,
+                                    completed: buffer.try_get_i32()?,
+                                }
+                            }
+                        });
+                        quest.progress_varps = Some(progress.collect::<Result<Vec<VarpProgress>, ReadError>>()?);
+                    }
+                    4 => {
+                        let count = buffer.try_get_u8()?;
+                        let progress = (0..count).map(|_| {
+                            try {
+                                VarbitProgress {
+                                    varbit: Varbit::new(buffer.try_get_u16()?),
+                                    started: buffer.try_get_i32()?,
+                                    completed: buffer.try_get_i32()?,
+                                }
+                            }
+                        });
+                        quest.progress_varbits = Some(progress.collect::<Result<Vec<VarbitProgress>, ReadError>>()?);
+                    }
+                    5 => quest.unknown_5 = Some(buffer.try_get_u16()?),
+                    6 => quest.r#type = Some(buffer.try_get_u8()?),
+                    7 => quest.difficulty = Some(buffer.try_get_u8()?),
+                    8 => quest.members = Some(true),
+                    9 => quest.quest_points = Some(buffer.try_get_u8()?),
+                    10 => {
+                        let count = buffer.try_get_u8()?;
+                        quest.start_locations = Some((0..count).map(|_| buffer.try_get_u32()).collect::<Result<_, _>>()?);
+                    }
+                    11 => quest.length = Some(buffer.try_get_u8()?),
+                    13 => {
+                        let count = buffer.try_get_u8()?;
+                        quest.required_quests = Some((0..count).map(|_| buffer.try_get_u16()).collect::<Result<_, _>>()?);
+                    }
+                    14 => {
+                        let count = buffer.try_get_u8()?;
+                        let skills = (0..count).map(|_| {
+                            try {
+                                SkillRequirement {
+                                    skill: buffer.try_get_u8()?,
+                                    level: buffer.try_get_u8()?,
+                                }
+                            }
+                        });
+                        quest.required_skills = Some(skills.collect::<Result<Vec<SkillRequirement>, ReadError>>()?);
+                    }
+                    15 => quest.required_quest_points = Some(buffer.try_get_u16()?),
+                    17 => quest.sprite = buffer.try_get_smart32()?,
+                    18 | 19 => {
+                        let count = buffer.try_get_u8()?;
+                        let requirements = (0..count).map(|_| VarRequirement::deserialize(opcode == 19, &mut buffer));
+                        quest.other_requirements.get_or_insert_default().extend(requirements.collect::<Result<Vec<VarRequirement>, ReadError>>()?);
+                    }
+                    249 => quest.params = Some(ParamTable::deserialize(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: quest.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl QuestConfig {
+    /// Serializes `config` with the names of the quests it refers to, for exporting it.
+    ///
+    /// Each of `fields` is the JSON pointer to a list of quest ids in `config`,
+    /// and the key under which the names of those quests are added next to that list.
+    pub fn annotate(config: &impl Serialize, fields: &[(&str, &str)], quests: &BTreeMap<u32, Self>) -> Value {
+        let mut value = serde_json::to_value(config).unwrap();
+        for &(pointer, key) in fields {
+            let Some(ids) = value.pointer(pointer).and_then(Value::as_array) else { continue };
+            let names = ids
+                .iter()
+                .map(|id| {
+                    id.as_u64()
+                        .and_then(|id| quests.get(&(id as u32)))
+                        .and_then(|quest| quest.name.as_deref())
+                })
+                .collect::<Vec<_>>();
+            let names = serde_json::to_value(names).unwrap();
+
+            let (parent, _) = pointer.rsplit_once('/').unwrap_or_default();
+            if let Some(Value::Object(parent)) = value.pointer_mut(parent) {
+                parent.insert(key.to_owned(), names);
+            }
+        }
+        value
+    }
+}
+
+impl Display for QuestConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl QuestConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("QuestConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("QuestConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the quest configs as `quests.json`. Exposed as `--dump quests`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let quests = QuestConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "quests.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&quests).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_quests() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let quests = QuestConfig::dump_all(&config)?;
+        assert!(quests.values().any(|quest| quest.name.is_some()));
+        Ok(())
+    }
+    #[test]
+    fn annotate_adds_names_next_to_ids() {
+        #[derive(Serialize)]
+        struct Quests {
+            quests: Vec<u16>,
+        }
+        #[derive(Serialize)]
+        struct Config {
+            quests: Quests,
+            other_quests: Option<Vec<u32>>,
+        }
+
+        let quest = QuestConfig {
+            id: 1,
+            name: Some(JString::from(String::from("Cook's Assistant"))),
+            ..Default::default()
+        };
+        let quests = BTreeMap::from([(1, quest)]);
+        let config = Config {
+            quests: Quests { quests: vec![1, 2] },
+            other_quests: None,
+        };
+
+        let value = QuestConfig::annotate(&config, &[("/quests/quests", "names"), ("/other_quests", "other_names")], &quests);
+        assert_eq!(value["quests"]["names"], serde_json::json!(["Cook's Assistant", null]));
+        assert!(value.get("other_names").is_none());
+    }
+}
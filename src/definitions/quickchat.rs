@@ -0,0 +1,471 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+use rs3cache_backend::{
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::{
+    enums::{Enum, Value},
+    indextype::IndexType,
+};
+
+/// Ids of categories and phrases from [`GLOBAL_QUICKCHAT`](IndexType::GLOBAL_QUICKCHAT) have this bit set.
+pub const GLOBAL: u32 = 0x8000;
+
+/// A reference to a category or phrase, with the key that selects it in the menu.
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub struct Shortcut {
+    pub id: u32,
+    pub key: Option<char>,
+}
+
+impl Shortcut {
+    fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+        let id = buffer.try_get_u16()? as u32;
+        let key = match buffer.try_get_u8()? {
+            0 => None,
+            key => Some(key as char),
+        };
+        Ok(Self { id, key })
+    }
+
+    fn deserialize_list(buffer: &mut Bytes) -> Result<Vec<Self>, ReadError> {
+        let count = buffer.try_get_u8()?;
+        (0..count).map(|_| Self::deserialize(buffer)).collect()
+    }
+}
+
+/// A menu of the quickchat interface, containing other categories and phrases.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct QuickChatCategory {
+    /// Its id.
+    pub id: u32,
+    /// The name of the category.
+    pub description: Option<JString<Bytes>>,
+    /// The categories nested in this one.
+    pub subcategories: Vec<Shortcut>,
+    /// The phrases in this category.
+    pub phrases: Vec<Shortcut>,
+    pub unknown_4: Option<bool>,
+}
+
+impl QuickChatCategory {
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut category = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(category);
+                        }
+                    }
+                    1 => category.description = Some(buffer.try_get_string()?),
+                    2 => category.subcategories = Shortcut::deserialize_list(&mut buffer)?,
+                    3 => category.phrases = Shortcut::deserialize_list(&mut buffer)?,
+                    4 => category.unknown_4 = Some(true),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: category.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// A value that is filled in when a phrase is said.
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DynamicValue {
+    /// The player picks a value from an enum.
+    ListDialog { enum_id: u16 },
+    /// The player picks an item.
+    ObjDialog,
+    /// The player enters a number.
+    CountDialog,
+    /// The player's base level in a skill.
+    StatBase { skill: u16 },
+    /// A value from an enum, looked up by the value of a varp.
+    EnumString { enum_id: u16, varp: u16 },
+    /// A value from an enum, looked up by the value of a clan varp.
+    EnumStringClan { enum_id: u16, varp: u16 },
+    /// The value of a varp.
+    TostringVarp { varp: u16 },
+    /// The value of a varbit.
+    TostringVarbit { varbit: u16 },
+    /// The player picks a tradeable item.
+    ObjTradeDialog,
+    /// A value from an enum, looked up by the player's base level in a skill.
+    EnumStringStatbase { enum_id: u16, skill: u16 },
+    /// The number of players on the world.
+    AccGetcountWorld,
+    /// The mean combat level of the players nearby.
+    AccGetmeancombatlevel,
+    /// The value of a shared varp.
+    TostringShared { varp: u16 },
+    /// The player's combat level.
+    ActiveCombatLevel,
+}
+
+impl DynamicValue {
+    fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+        let command = buffer.try_get_u16()?;
+        let value = match command {
+            0 => Self::ListDialog {
+                enum_id: buffer.try_get_u16()?,
+            },
+            1 => Self::ObjDialog,
+            2 => Self::CountDialog,
+            4 => Self::StatBase {
+                skill: buffer.try_get_u16()?,
+            },
+            6 => Self::EnumString {
+                enum_id: buffer.try_get_u16()?,
+                varp: buffer.try_get_u16()?,
+            },
+            7 => Self::EnumStringClan {
+                enum_id: buffer.try_get_u16()?,
+                varp: buffer.try_get_u16()?,
+            },
+            8 => Self::TostringVarp {
+                varp: buffer.try_get_u16()?,
+            },
+            9 => Self::TostringVarbit {
+                varbit: buffer.try_get_u16()?,
+            },
+            10 => Self::ObjTradeDialog,
+            11 => Self::EnumStringStatbase {
+                enum_id: buffer.try_get_u16()?,
+                skill: buffer.try_get_u16()?,
+            },
+            12 => Self::AccGetcountWorld,
+            13 => Self::AccGetmeancombatlevel,
+            14 => Self::TostringShared {
+                varp: buffer.try_get_u16()?,
+            },
+            15 => Self::ActiveCombatLevel,
+            other => {
+                return Err(ReadError::Unsupported {
+                    location: std::panic::Location::caller(),
+                    what: "quickchat dynamic value",
+                    reason: format!("command {other}"),
+                })
+            }
+        };
+        Ok(value)
+    }
+
+    /// A plausible value, for documentation purposes.
+    fn example(&self, enums: &BTreeMap<u32, Enum>) -> String {
+        let first_of = |enum_id: u16| {
+            enums
+                .get(&(enum_id as u32))
+                .and_then(|e| e.variants.values().next())
+                .map(|value| match value {
+                    Value::String(s) => s.to_string(),
+                    Value::Integer(i) => i.to_string(),
+                })
+                .unwrap_or_else(|| format!("[enum {enum_id}]"))
+        };
+
+        match *self {
+            Self::ListDialog { enum_id }
+            | Self::EnumString { enum_id, .. }
+            | Self::EnumStringClan { enum_id, .. }
+            | Self::EnumStringStatbase { enum_id, .. } => first_of(enum_id),
+            Self::ObjDialog | Self::ObjTradeDialog => "Abyssal whip".to_owned(),
+            Self::CountDialog => "1000".to_owned(),
+            Self::StatBase { .. } => "99".to_owned(),
+            Self::TostringVarp { .. } | Self::TostringVarbit { .. } | Self::TostringShared { .. } => "10".to_owned(),
+            Self::AccGetcountWorld => "1".to_owned(),
+            Self::AccGetmeancombatlevel | Self::ActiveCombatLevel => "138".to_owned(),
+        }
+    }
+}
+
+/// A phrase that can be said with quickchat.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct QuickChatPhrase {
+    /// Its id.
+    pub id: u32,
+    /// The text of the phrase, split around its [`DynamicValue`]s.
+    pub template: Vec<String>,
+    /// Phrases offered as a response to this one.
+    pub responses: Option<Vec<u16>>,
+    /// The values filled into the template, in order.
+    pub dynamic_values: Vec<DynamicValue>,
+    /// Whether the phrase can be found by searching.
+    pub searchable: bool,
+}
+
+impl Default for QuickChatPhrase {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            template: Vec::new(),
+            responses: None,
+            dynamic_values: Vec::new(),
+            searchable: true,
+        }
+    }
+}
+
+impl QuickChatPhrase {
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut phrase = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(phrase);
+                        }
+                    }
+                    1 => phrase.template = buffer.try_get_string()?.split('<').map(str::to_owned).collect(),
+                    2 => {
+                        let count = buffer.try_get_u8()?;
+                        phrase.responses = Some((0..count).map(|_| buffer.try_get_u16()).collect::<Result<_, _>>()?);
+                    }
+                    3 => {
+                        let count = buffer.try_get_u8()?;
+                        phrase.dynamic_values = (0..count).map(|_| DynamicValue::deserialize(&mut buffer)).collect::<Result<_, _>>()?;
+                    }
+                    4 => phrase.searchable = false,
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: phrase.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Renders the phrase, with example values filled in.
+    ///
+    /// ```text
+    /// My current Attack level is 99.
+    /// ```
+    pub fn render_example(&self, enums: &BTreeMap<u32, Enum>) -> String {
+        let mut text = String::new();
+        let mut values = self.dynamic_values.iter();
+        for (i, part) in self.template.iter().enumerate() {
+            if i != 0 {
+                match values.next() {
+                    Some(value) => text.push_str(&value.example(enums)),
+                    None => text.push('<'),
+                }
+            }
+            text.push_str(part);
+        }
+        text
+    }
+}
+
+impl Display for QuickChatCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+impl Display for QuickChatPhrase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+/// All quickchat categories and phrases.
+///
+/// Those from [`GLOBAL_QUICKCHAT`](IndexType::GLOBAL_QUICKCHAT) have their ids offset by [`GLOBAL`].
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct QuickChat {
+    pub categories: BTreeMap<u32, QuickChatCategory>,
+    pub phrases: BTreeMap<u32, QuickChatPhrase>,
+}
+
+impl QuickChat {
+    /// Reads all quickchat categories and phrases.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<Self> {
+        let mut quickchat = Self::default();
+
+        for (index_id, offset) in [(IndexType::QUICKCHAT, 0), (IndexType::GLOBAL_QUICKCHAT, GLOBAL)] {
+            let index = CacheIndex::new(index_id, config.input.clone())?;
+
+            let categories = index
+                .archive(0)?
+                .take_files()
+                .into_iter()
+                .map(|(file_id, file)| QuickChatCategory::deserialize(offset | file_id, file).map(|item| (offset | file_id, item)))
+                .collect::<Result<Vec<_>, ReadError>>()
+                .context(error::Read { what: "quickchat categories" })?;
+            quickchat.categories.extend(categories);
+
+            let phrases = index
+                .archive(1)?
+                .take_files()
+                .into_iter()
+                .map(|(file_id, file)| QuickChatPhrase::deserialize(offset | file_id, file).map(|item| (offset | file_id, item)))
+                .collect::<Result<Vec<_>, ReadError>>()
+                .context(error::Read { what: "quickchat phrases" })?;
+            quickchat.phrases.extend(phrases);
+        }
+
+        Ok(quickchat)
+    }
+
+    /// Builds the menu tree, starting from every category that is not nested in another.
+    pub fn menus(&self, enums: &BTreeMap<u32, Enum>) -> Vec<Menu> {
+        let nested = self
+            .categories
+            .values()
+            .flat_map(|category| category.subcategories.iter().map(|shortcut| shortcut.id))
+            .collect::<std::collections::BTreeSet<_>>();
+
+        self.categories
+            .keys()
+            .filter(|id| !nested.contains(id))
+            .filter_map(|&id| self.menu(Shortcut { id, key: None }, enums, &mut Vec::new()))
+            .collect()
+    }
+
+    fn menu(&self, shortcut: Shortcut, enums: &BTreeMap<u32, Enum>, ancestors: &mut Vec<u32>) -> Option<Menu> {
+        // Guard against categories that contain themselves.
+        if ancestors.contains(&shortcut.id) {
+            return None;
+        }
+        let category = self.categories.get(&shortcut.id)?;
+
+        ancestors.push(shortcut.id);
+        let categories = category
+            .subcategories
+            .iter()
+            .filter_map(|&subcategory| self.menu(subcategory, enums, ancestors))
+            .collect();
+        ancestors.pop();
+
+        let phrases = category
+            .phrases
+            .iter()
+            .filter_map(|shortcut| {
+                self.phrases.get(&shortcut.id).map(|phrase| MenuPhrase {
+                    id: shortcut.id,
+                    key: shortcut.key,
+                    text: phrase.render_example(enums),
+                })
+            })
+            .collect();
+
+        Some(Menu {
+            id: shortcut.id,
+            key: shortcut.key,
+            description: category.description.as_deref().map(str::to_owned),
+            categories,
+            phrases,
+        })
+    }
+}
+
+/// A node of the quickchat menu tree.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct Menu {
+    pub id: u32,
+    pub key: Option<char>,
+    pub description: Option<String>,
+    pub categories: Vec<Menu>,
+    pub phrases: Vec<MenuPhrase>,
+}
+
+/// A phrase in the quickchat menu tree, rendered with example values.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct MenuPhrase {
+    pub id: u32,
+    pub key: Option<char>,
+    pub text: String,
+}
+
+/// Save the quickchat menu tree as `quickchat.json`. Exposed as `--dump quickchat`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+
+    let enums = Enum::dump_all(config)?;
+    let menus = QuickChat::dump_all(config)?.menus(&enums);
+
+    let path = path!(config.output / "quickchat.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&menus).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_quickchat() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let quickchat = QuickChat::dump_all(&config)?;
+        assert!(quickchat.phrases.values().any(|phrase| !phrase.dynamic_values.is_empty()));
+        assert!(!quickchat.menus(&BTreeMap::new()).is_empty());
+        Ok(())
+    }
+}
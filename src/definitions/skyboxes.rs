@@ -0,0 +1,141 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::{ConfigType, IndexType};
+
+/// Describes the sky drawn around a scene.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Skybox {
+    /// Its id.
+    pub id: u32,
+    /// The texture of the sky.
+    pub texture: Option<u16>,
+    /// The spheres making up the sky, drawn in order.
+    pub spheres: Option<Vec<u16>>,
+    /// How the sky is filled in where no sphere covers it.
+    pub fill_mode: Option<u8>,
+    pub unknown_4: Option<u8>,
+}
+
+impl Skybox {
+    /// Returns a mapping of all [`Skybox`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let skyboxs = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::SKYBOX)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "skyboxes" })?;
+        Ok(skyboxs)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut skybox = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(skybox);
+                        }
+                    }
+                    1 => skybox.texture = Some(buffer.try_get_u16()?),
+                    2 => {
+                        let count = buffer.try_get_u8()?;
+                        skybox.spheres = Some((0..count).map(|_| buffer.try_get_u16()).collect::<Result<_, _>>()?);
+                    }
+                    3 => skybox.fill_mode = Some(buffer.try_get_u8()?),
+                    4 => skybox.unknown_4 = Some(buffer.try_get_u8()?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: skybox.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for Skybox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl Skybox {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Skybox({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("Skybox({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the skyboxes as `skyboxes.json`. Exposed as `--dump skyboxes`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let skyboxs = Skybox::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "skyboxes.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&skyboxs).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_skyboxes() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let skyboxs = Skybox::dump_all(&config)?;
+        assert!(skyboxs.values().any(|skybox| skybox.texture.is_some()));
+        Ok(())
+    }
+}
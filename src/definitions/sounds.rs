@@ -0,0 +1,774 @@
+//! Sound effects.
+//!
+//! Modern sound effects are stored as Ogg Vorbis files in the [`VORBIS`](IndexType::VORBIS) index.
+//! Older ones are instead synthesized by the client, from the [`SynthSound`] definitions in the
+//! [`SYNTH_SOUNDS`](IndexType::SYNTH_SOUNDS) index.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Write,
+    sync::LazyLock,
+};
+
+use ::error::Context;
+use bytes::Bytes;
+use path_macro::path;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use rs3cache_backend::{
+    buf::{BufExtra, Eof, ReadError},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::IndexType;
+
+/// The sample rate of synthesized sounds.
+pub const SAMPLE_RATE: u32 = 22050;
+
+/// A synthesized sound effect, made up of up to ten [`Instrument`]s.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct SynthSound {
+    /// Its id.
+    pub id: u32,
+    pub instruments: Vec<Option<Instrument>>,
+    /// Start of the loop, in milliseconds.
+    pub loop_start: u16,
+    /// End of the loop, in milliseconds.
+    pub loop_end: u16,
+}
+
+impl SynthSound {
+    /// Returns a mapping of all [`SynthSound`]s.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let archives = CacheIndex::new(IndexType::SYNTH_SOUNDS, config.input.clone())?.into_iter();
+        let sounds = archives
+            .map(Result::unwrap)
+            .filter_map(|archive| {
+                let id = archive.archive_id();
+                archive.file(&0).map(|file| Self::deserialize(id, file).map(|sound| (id, sound)))
+            })
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "synth sounds" })?;
+        Ok(sounds)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let instruments = (0..10)
+            .map(|_| {
+                // The first byte of an instrument is never zero, so it doubles as a presence flag.
+                if peek(&buffer)? == 0 {
+                    buffer.try_get_u8()?;
+                    Ok(None)
+                } else {
+                    Instrument::deserialize(&mut buffer).map(Some)
+                }
+            })
+            .collect::<Result<_, ReadError>>()?;
+        let loop_start = buffer.try_get_u16()?;
+        let loop_end = buffer.try_get_u16()?;
+        Ok(Self {
+            id,
+            instruments,
+            loop_start,
+            loop_end,
+        })
+    }
+
+    /// Mixes all the instruments together, as signed 8-bit samples at [`SAMPLE_RATE`].
+    pub fn mix(&self) -> Vec<i8> {
+        let instruments = self.instruments.iter().flatten();
+        let length = instruments.clone().map(|ins| ins.duration as usize + ins.offset as usize).max().unwrap_or(0);
+        let mut out = vec![0i8; length * SAMPLE_RATE as usize / 1000];
+
+        for instrument in instruments {
+            let offset = instrument.offset as usize * SAMPLE_RATE as usize / 1000;
+            let samples = instrument.synthesize();
+            for (mixed, sample) in out[offset..].iter_mut().zip(samples) {
+                *mixed = ((sample >> 8) + *mixed as i32).clamp(i8::MIN as i32, i8::MAX as i32) as i8;
+            }
+        }
+        out
+    }
+
+    /// Renders this sound effect as a mono, 8-bit WAV file.
+    pub fn to_wav(&self) -> Vec<u8> {
+        let samples = self.mix();
+        let len = samples.len() as u32;
+
+        let mut out = Vec::with_capacity(44 + samples.len());
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + len).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        // PCM, mono
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        // byte rate, block align and bits per sample
+        out.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&8u16.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&len.to_le_bytes());
+        // 8-bit WAV samples are unsigned.
+        out.extend(samples.into_iter().map(|sample| sample as u8 ^ 0x80));
+        out
+    }
+}
+
+/// A single voice of a [`SynthSound`].
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct Instrument {
+    pub pitch: Envelope,
+    pub volume: Envelope,
+    pub pitch_modifier: Option<(Envelope, Envelope)>,
+    pub volume_modifier: Option<(Envelope, Envelope)>,
+    pub gate: Option<(Envelope, Envelope)>,
+    pub oscillators: Vec<Oscillator>,
+    pub delay_time: u16,
+    pub delay_decay: u16,
+    /// Duration, in milliseconds.
+    pub duration: u16,
+    /// Time before this instrument starts playing, in milliseconds.
+    pub offset: u16,
+    pub filter: Filter,
+    pub filter_envelope: Envelope,
+}
+
+/// One of the waves an [`Instrument`] is made of.
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct Oscillator {
+    pub volume: u16,
+    pub pitch: i16,
+    pub delay: u16,
+}
+
+impl Instrument {
+    fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+        let pitch = Envelope::deserialize(buffer)?;
+        let volume = Envelope::deserialize(buffer)?;
+        let pitch_modifier = Envelope::deserialize_pair(buffer)?;
+        let volume_modifier = Envelope::deserialize_pair(buffer)?;
+        let gate = Envelope::deserialize_pair(buffer)?;
+
+        let mut oscillators = Vec::new();
+        for _ in 0..10 {
+            let volume = buffer.try_get_unsigned_smart()?;
+            if volume == 0 {
+                break;
+            }
+            let pitch = buffer.try_get_signed_smart()?;
+            let delay = buffer.try_get_unsigned_smart()?;
+            oscillators.push(Oscillator { volume, pitch, delay });
+        }
+
+        let delay_time = buffer.try_get_unsigned_smart()?;
+        let delay_decay = buffer.try_get_unsigned_smart()?;
+        let duration = buffer.try_get_u16()?;
+        let offset = buffer.try_get_u16()?;
+
+        let mut filter_envelope = Envelope::default();
+        let filter = Filter::deserialize(buffer, &mut filter_envelope)?;
+
+        Ok(Self {
+            pitch,
+            volume,
+            pitch_modifier,
+            volume_modifier,
+            gate,
+            oscillators,
+            delay_time,
+            delay_decay,
+            duration,
+            offset,
+            filter,
+            filter_envelope,
+        })
+    }
+
+    /// Renders this instrument, as samples clamped to the range of an `i16`.
+    pub fn synthesize(&self) -> Vec<i32> {
+        let sample_count = self.duration as usize * SAMPLE_RATE as usize / 1000;
+        let mut samples = vec![0i32; sample_count];
+        if self.duration < 10 {
+            return samples;
+        }
+        let n = sample_count as i32;
+        let samples_per_ms = sample_count as f64 / self.duration as f64;
+
+        let mut pitch = self.pitch.state();
+        let mut volume = self.volume.state();
+
+        let mut pitch_modifier = self.pitch_modifier.as_ref().map(|envelopes| Modulator::new(envelopes, samples_per_ms));
+        let mut volume_modifier = self.volume_modifier.as_ref().map(|envelopes| Modulator::new(envelopes, samples_per_ms));
+
+        let oscillators = self
+            .oscillators
+            .iter()
+            .take(5)
+            .map(|osc| {
+                let delay = (osc.delay as f64 * samples_per_ms) as usize;
+                let volume_step = ((osc.volume as i32) << 14) / 100;
+                let pitch_step = ((self.pitch.end as f64 - self.pitch.start as f64) * 32.768 * 1.0057929410678534f64.powi(osc.pitch as i32)
+                    / samples_per_ms) as i32;
+                let pitch_base_step = (self.pitch.start as f64 * 32.768 / samples_per_ms) as i32;
+                (delay, volume_step, pitch_step, pitch_base_step)
+            })
+            .collect::<Vec<_>>();
+        let mut phases = vec![0i32; oscillators.len()];
+
+        for i in 0..sample_count {
+            let mut pitch_value = pitch.step(n);
+            let mut volume_value = volume.step(n);
+
+            if let Some(modulator) = &mut pitch_modifier {
+                pitch_value = pitch_value.wrapping_add(modulator.step(n) >> 1);
+            }
+
+            if let Some(modulator) = &mut volume_modifier {
+                volume_value = volume_value.wrapping_mul((modulator.step(n) >> 1) + 32768) >> 15;
+            }
+
+            for (&(delay, volume_step, pitch_step, pitch_base_step), phase) in oscillators.iter().zip(&mut phases) {
+                if let Some(sample) = samples.get_mut(i + delay) {
+                    *sample = sample.wrapping_add(wave(*phase, volume_value.wrapping_mul(volume_step) >> 15, self.pitch.form));
+                    *phase = phase.wrapping_add((pitch_value.wrapping_mul(pitch_step) >> 16).wrapping_add(pitch_base_step));
+                }
+            }
+        }
+
+        if let Some((on, off)) = &self.gate {
+            let mut on_state = on.state();
+            let mut off_state = off.state();
+            let mut counter = 0;
+            let mut muted = true;
+            for sample in samples.iter_mut() {
+                let on_value = on_state.step(n);
+                let off_value = off_state.step(n);
+                let value = if muted { on_value } else { off_value };
+                let threshold = (value.wrapping_mul(on.end.wrapping_sub(on.start)) >> 8).wrapping_add(on.start);
+                counter += 256;
+                if counter >= threshold {
+                    counter = 0;
+                    muted = !muted;
+                }
+                if muted {
+                    *sample = 0;
+                }
+            }
+        }
+
+        if self.delay_time > 0 && self.delay_decay > 0 {
+            let delay = (self.delay_time as f64 * samples_per_ms) as usize;
+            for i in delay..sample_count {
+                samples[i] = samples[i].wrapping_add(samples[i - delay].wrapping_mul(self.delay_decay as i32) / 100);
+            }
+        }
+
+        if self.filter.pairs.iter().any(|&pairs| pairs > 0) {
+            self.apply_filter(&mut samples);
+        }
+
+        for sample in samples.iter_mut() {
+            *sample = (*sample).clamp(i16::MIN as i32, i16::MAX as i32);
+        }
+        samples
+    }
+
+    fn apply_filter(&self, samples: &mut [i32]) {
+        let len = samples.len();
+        let n = len as i32 + 1;
+        let mut envelope = self.filter_envelope.state();
+        let mut coefficients = Coefficients::default();
+
+        let mut interpolation = envelope.step(n) as f32 / 65536.0;
+        let mut forward = coefficients.compute(&self.filter, 0, interpolation);
+        let mut backward = coefficients.compute(&self.filter, 1, interpolation);
+        if len < forward + backward {
+            return;
+        }
+
+        let mul = |sample: i32, coefficient: i32| ((sample as i64 * coefficient as i64) >> 16) as i32;
+
+        let mut i = 0;
+        let mut limit = backward.min(len - forward);
+        while i < limit {
+            let mut value = mul(samples[i + forward], coefficients.forward_multiplier);
+            for j in 0..forward {
+                value = value.wrapping_add(mul(samples[i + forward - 1 - j], coefficients.coefficients[0][j]));
+            }
+            for j in 0..i {
+                value = value.wrapping_sub(mul(samples[i - 1 - j], coefficients.coefficients[1][j]));
+            }
+            samples[i] = value;
+            interpolation = envelope.step(n) as f32 / 65536.0;
+            i += 1;
+        }
+
+        limit = 128;
+        loop {
+            limit = limit.min(len.saturating_sub(forward));
+            while i < limit {
+                let mut value = mul(samples[i + forward], coefficients.forward_multiplier);
+                for j in 0..forward {
+                    value = value.wrapping_add(mul(samples[i + forward - 1 - j], coefficients.coefficients[0][j]));
+                }
+                for j in 0..backward.min(i) {
+                    value = value.wrapping_sub(mul(samples[i - 1 - j], coefficients.coefficients[1][j]));
+                }
+                samples[i] = value;
+                interpolation = envelope.step(n) as f32 / 65536.0;
+                i += 1;
+            }
+
+            if i >= len.saturating_sub(forward) {
+                while i < len {
+                    let mut value = 0i32;
+                    for j in (i + forward - len)..forward {
+                        value = value.wrapping_add(mul(samples[i + forward - 1 - j], coefficients.coefficients[0][j]));
+                    }
+                    for j in 0..backward.min(i) {
+                        value = value.wrapping_sub(mul(samples[i - 1 - j], coefficients.coefficients[1][j]));
+                    }
+                    samples[i] = value;
+                    envelope.step(n);
+                    i += 1;
+                }
+                break;
+            }
+
+            forward = coefficients.compute(&self.filter, 0, interpolation);
+            backward = coefficients.compute(&self.filter, 1, interpolation);
+            limit += 128;
+        }
+    }
+}
+
+/// Modulates the pitch or volume of an [`Instrument`].
+struct Modulator<'a> {
+    rate: EnvelopeState<'a>,
+    amplitude: EnvelopeState<'a>,
+    step: i32,
+    base_step: i32,
+    form: u8,
+    phase: i32,
+}
+
+impl<'a> Modulator<'a> {
+    fn new((rate, amplitude): &'a (Envelope, Envelope), samples_per_ms: f64) -> Self {
+        Self {
+            rate: rate.state(),
+            amplitude: amplitude.state(),
+            step: ((rate.end as f64 - rate.start as f64) * 32.768 / samples_per_ms) as i32,
+            base_step: (rate.start as f64 * 32.768 / samples_per_ms) as i32,
+            form: rate.form,
+            phase: 0,
+        }
+    }
+
+    fn step(&mut self, n: i32) -> i32 {
+        let rate = self.rate.step(n);
+        let amplitude = self.amplitude.step(n);
+        let value = wave(self.phase, amplitude, self.form);
+        self.phase = self.phase.wrapping_add(self.base_step).wrapping_add(rate.wrapping_mul(self.step) >> 16);
+        value
+    }
+}
+
+fn peek(buffer: &Bytes) -> Result<u8, ReadError> {
+    buffer.first().copied().ok_or_else(|| Eof::new(1, 0))
+}
+
+/// Evaluates a waveform of the given `form` at `phase`.
+fn wave(phase: i32, amplitude: i32, form: u8) -> i32 {
+    static SINE: LazyLock<Vec<i32>> = LazyLock::new(|| (0..32768).map(|i| ((i as f64 / 5215.1903).sin() * 16384.0) as i32).collect());
+    static NOISE: LazyLock<Vec<i32>> = LazyLock::new(|| {
+        // This must be the same sequence of numbers as `new java.util.Random(0)` produces.
+        let mut seed: u64 = 0x5DEECE66D;
+        (0..32768)
+            .map(|_| {
+                seed = seed.wrapping_mul(0x5DEECE66D).wrapping_add(0xB) & ((1 << 48) - 1);
+                let next = (seed >> 16) as u32 as i32;
+                (next & 2) - 1
+            })
+            .collect()
+    });
+
+    match form {
+        1 if phase & 0x7FFF < 0x4000 => amplitude,
+        1 => amplitude.wrapping_neg(),
+        2 => SINE[(phase & 0x7FFF) as usize].wrapping_mul(amplitude) >> 14,
+        3 => (amplitude.wrapping_mul(phase & 0x7FFF) >> 14).wrapping_sub(amplitude),
+        4 => amplitude.wrapping_mul(NOISE[(phase / 2607 & 0x7FFF) as usize]),
+        _ => 0,
+    }
+}
+
+/// A piecewise linear function over the duration of an [`Instrument`].
+#[derive(Serialize, Clone, Debug)]
+pub struct Envelope {
+    /// The waveform, if this envelope controls a wave.
+    pub form: u8,
+    pub start: i32,
+    pub end: i32,
+    /// Pairs of durations and the value reached at that time.
+    pub segments: Vec<(u16, u16)>,
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self {
+            form: 0,
+            start: 0,
+            end: 0,
+            segments: vec![(0, 0), (65535, 65535)],
+        }
+    }
+}
+
+impl Envelope {
+    fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+        let form = buffer.try_get_u8()?;
+        let start = buffer.try_get_i32()?;
+        let end = buffer.try_get_i32()?;
+        let segments = Self::deserialize_segments(buffer)?;
+        Ok(Self { form, start, end, segments })
+    }
+
+    /// Reads two envelopes, if present.
+    fn deserialize_pair(buffer: &mut Bytes) -> Result<Option<(Self, Self)>, ReadError> {
+        if peek(buffer)? == 0 {
+            buffer.try_get_u8()?;
+            Ok(None)
+        } else {
+            Ok(Some((Self::deserialize(buffer)?, Self::deserialize(buffer)?)))
+        }
+    }
+
+    fn deserialize_segments(buffer: &mut Bytes) -> Result<Vec<(u16, u16)>, ReadError> {
+        let count = buffer.try_get_u8()? as usize;
+        std::iter::repeat_with(|| try { (buffer.try_get_u16()?, buffer.try_get_u16()?) })
+            .take(count)
+            .collect::<Result<_, ReadError>>()
+    }
+
+    fn state(&self) -> EnvelopeState<'_> {
+        EnvelopeState {
+            envelope: self,
+            ticks: 0,
+            segment: 0,
+            step: 0,
+            amplitude: 0,
+            position: 0,
+        }
+    }
+}
+
+struct EnvelopeState<'a> {
+    envelope: &'a Envelope,
+    ticks: i32,
+    segment: usize,
+    step: i32,
+    amplitude: i32,
+    position: i32,
+}
+
+impl EnvelopeState<'_> {
+    /// Advances the envelope by one sample, out of `n`.
+    fn step(&mut self, n: i32) -> i32 {
+        let segments = &self.envelope.segments;
+        if self.position >= self.ticks {
+            let (_, value) = segments.get(self.segment).copied().unwrap_or_default();
+            self.amplitude = (value as i32) << 15;
+            self.segment = (self.segment + 1).min(segments.len().saturating_sub(1));
+
+            let (duration, value) = segments.get(self.segment).copied().unwrap_or_default();
+            self.ticks = (duration as f64 / 65536.0 * n as f64) as i32;
+            if self.ticks > self.position {
+                self.step = (((value as i32) << 15) - self.amplitude) / (self.ticks - self.position);
+            }
+        }
+        self.amplitude = self.amplitude.wrapping_add(self.step);
+        self.position += 1;
+        self.amplitude.wrapping_sub(self.step) >> 15
+    }
+}
+
+/// An IIR filter with up to four pole and zero pairs, interpolated over the duration of an [`Instrument`].
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Filter {
+    /// The number of forward and backward pairs.
+    pub pairs: [u8; 2],
+    pub unity: [u16; 2],
+    /// Indexed by direction, then by start or end.
+    pub phases: [[Vec<u16>; 2]; 2],
+    /// Indexed by direction, then by start or end.
+    pub magnitudes: [[Vec<u16>; 2]; 2],
+}
+
+impl Filter {
+    fn deserialize(buffer: &mut Bytes, envelope: &mut Envelope) -> Result<Self, ReadError> {
+        let mut filter = Self::default();
+
+        let pairs = buffer.try_get_u8()?;
+        filter.pairs = [pairs >> 4, pairs & 0xF];
+        if pairs == 0 {
+            return Ok(filter);
+        }
+
+        filter.unity = [buffer.try_get_u16()?, buffer.try_get_u16()?];
+        let mask = buffer.try_get_u8()?;
+
+        for direction in 0..2 {
+            for _ in 0..filter.pairs[direction] {
+                filter.phases[direction][0].push(buffer.try_get_u16()?);
+                filter.magnitudes[direction][0].push(buffer.try_get_u16()?);
+            }
+        }
+
+        for direction in 0..2 {
+            for pair in 0..filter.pairs[direction] as usize {
+                let (phase, magnitude) = if mask & (1 << (direction * 4) << pair) != 0 {
+                    (buffer.try_get_u16()?, buffer.try_get_u16()?)
+                } else {
+                    (filter.phases[direction][0][pair], filter.magnitudes[direction][0][pair])
+                };
+                filter.phases[direction][1].push(phase);
+                filter.magnitudes[direction][1].push(magnitude);
+            }
+        }
+
+        if mask != 0 || filter.unity[1] != filter.unity[0] {
+            envelope.segments = Envelope::deserialize_segments(buffer)?;
+        }
+
+        Ok(filter)
+    }
+
+    fn magnitude(&self, direction: usize, pair: usize, interpolation: f32) -> f32 {
+        let [start, end] = &self.magnitudes[direction];
+        let magnitude = start[pair] as f32 + interpolation * (end[pair] as f32 - start[pair] as f32);
+        let magnitude = magnitude * 0.0015258789;
+        1.0 - 10f64.powf(-magnitude as f64 / 20.0) as f32
+    }
+
+    fn phase(&self, direction: usize, pair: usize, interpolation: f32) -> f32 {
+        let [start, end] = &self.phases[direction];
+        let phase = start[pair] as f32 + interpolation * (end[pair] as f32 - start[pair] as f32);
+        let phase = phase * 1.2207031e-4;
+        let frequency = 32.703197 * 2f64.powf(phase as f64) as f32;
+        frequency * std::f32::consts::PI / 11025.0
+    }
+}
+
+/// The coefficients of a [`Filter`] at some point in time.
+#[derive(Default)]
+struct Coefficients {
+    minimised: [[f32; 8]; 2],
+    coefficients: [[i32; 8]; 2],
+    forward_multiplier: i32,
+}
+
+impl Coefficients {
+    /// Recomputes the coefficients for `direction`, returning how many there are.
+    fn compute(&mut self, filter: &Filter, direction: usize, interpolation: f32) -> usize {
+        let mut forward_minimised = 0.0;
+        if direction == 0 {
+            let unity = filter.unity[0] as f32 + (filter.unity[1] as f32 - filter.unity[0] as f32) * interpolation;
+            let unity = unity * 0.0030517578;
+            forward_minimised = 0.1f64.powf(unity as f64 / 20.0) as f32;
+            self.forward_multiplier = (forward_minimised * 65536.0) as i32;
+        }
+
+        let pairs = (filter.pairs[direction] as usize).min(4);
+        if pairs == 0 {
+            return 0;
+        }
+
+        let minimised = &mut self.minimised[direction];
+        let magnitude = filter.magnitude(direction, 0, interpolation);
+        minimised[0] = -2.0 * magnitude * (filter.phase(direction, 0, interpolation) as f64).cos() as f32;
+        minimised[1] = magnitude * magnitude;
+
+        for pair in 1..pairs {
+            let magnitude = filter.magnitude(direction, pair, interpolation);
+            let a = -2.0 * magnitude * (filter.phase(direction, pair, interpolation) as f64).cos() as f32;
+            let b = magnitude * magnitude;
+            minimised[pair * 2 + 1] = minimised[pair * 2 - 1] * b;
+            minimised[pair * 2] = minimised[pair * 2 - 1] * a + minimised[pair * 2 - 2] * b;
+            for k in (2..pair * 2).rev() {
+                minimised[k] += minimised[k - 1] * a + minimised[k - 2] * b;
+            }
+            minimised[1] += minimised[0] * a + b;
+            minimised[0] += a;
+        }
+
+        if direction == 0 {
+            for coefficient in &mut minimised[..pairs * 2] {
+                *coefficient *= forward_minimised;
+            }
+        }
+
+        for (coefficient, minimised) in self.coefficients[direction].iter_mut().zip(&minimised[..pairs * 2]) {
+            *coefficient = (minimised * 65536.0) as i32;
+        }
+        pairs * 2
+    }
+}
+
+/// Save the synthesized sound effects as `synth_sounds/{id}.wav`. Exposed as `--dump synth_sounds`.
+pub fn export_synth(config: &crate::cli::Config) -> CacheResult<()> {
+    let folder = path!(config.output / "synth_sounds");
+    fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
+
+    SynthSound::dump_all(config)?.into_values().par_bridge().try_for_each(|sound| {
+        let path = path!(&folder / format!("{}.wav", sound.id));
+        let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+        file.write_all(&sound.to_wav()).context(error::Io { path })?;
+        Ok(())
+    })
+}
+
+/// Save the sound effects as `sounds/{id}.ogg`. Exposed as `--dump sounds`.
+#[cfg(feature = "rs3")]
+pub fn export_vorbis(config: &crate::cli::Config) -> CacheResult<()> {
+    use console::style;
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    use crate::definitions::music;
+
+    let folder = path!(config.output / "sounds");
+    fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
+
+    let index = CacheIndex::new(IndexType::VORBIS, config.input.clone())?;
+    let ids = index.metadatas().keys().copied().collect::<Vec<_>>();
+
+    let progress = ProgressBar::new(ids.len() as u64).with_style(
+        ProgressStyle::with_template(&format!("   {} [{{bar:30}}] {{pos}}/{{len}}: sounds", style("Dumping").cyan().bright()))
+            .unwrap()
+            .progress_chars("=> "),
+    );
+
+    // Reading from the cache happens sequentially, inside the bridged iterator.
+    // Stitching the chunks together and writing them out happens in parallel.
+    let reader = progress.clone();
+    ids.into_iter()
+        .filter_map(move |id| {
+            // This index is not necessarily complete, so archives may be missing.
+            let Some(data) = index.archive(id).ok().and_then(|archive| archive.file(&0)) else {
+                reader.inc(1);
+                return None;
+            };
+            if data.starts_with(b"JAGA") {
+                match music::read_chunks(&index, id) {
+                    Ok(chunks) => Some((id, music::assemble(chunks))),
+                    Err(reason) => {
+                        reader.println(format!("    {} sound {id} {reason}", style("Warning").yellow()));
+                        reader.inc(1);
+                        None
+                    }
+                }
+            } else {
+                Some((id, Ok(data.to_vec())))
+            }
+        })
+        .par_bridge()
+        .try_for_each(|(id, data)| {
+            match data {
+                Ok(data) => {
+                    let path = path!(&folder / format!("{id}.ogg"));
+                    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+                    file.write_all(&data).context(error::Io { path })?;
+                }
+                Err(e) => progress.println(format!("    {} sound {id} could not be assembled: {e}", style("Warning").yellow())),
+            }
+            progress.inc(1);
+            CacheResult::Ok(())
+        })?;
+
+    progress.println(format!("    {} sounds", style("Dumped").green().bright()));
+    progress.finish_and_clear();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_synth_sounds() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let sounds = SynthSound::dump_all(&config)?;
+        let sound = sounds.values().find(|sound| sound.instruments.iter().any(Option::is_some)).unwrap();
+        let wav = sound.to_wav();
+        assert_eq!(&wav[..4], b"RIFF");
+        assert!(wav.len() > 44, "{sound:?}");
+        Ok(())
+    }
+
+    fn envelope(form: u8, start: i32, end: i32, value: u16) -> Envelope {
+        Envelope {
+            form,
+            start,
+            end,
+            segments: vec![(0, value), (65535, value)],
+        }
+    }
+
+    /// A square wave at full volume, whose phase advances by `2751 * 32.768 / 22 = 4097` per sample.
+    fn square_wave() -> SynthSound {
+        let instrument = Instrument {
+            pitch: envelope(1, 2751, 2751, 0),
+            volume: envelope(0, 0, 0, 65535),
+            pitch_modifier: None,
+            volume_modifier: None,
+            gate: None,
+            oscillators: vec![Oscillator {
+                volume: 100,
+                pitch: 0,
+                delay: 0,
+            }],
+            delay_time: 0,
+            delay_decay: 0,
+            duration: 10,
+            offset: 0,
+            filter: Filter::default(),
+            filter_envelope: Envelope::default(),
+        };
+        let mut instruments = vec![None; 10];
+        instruments[0] = Some(instrument);
+        SynthSound {
+            id: 0,
+            instruments,
+            loop_start: 0,
+            loop_end: 0,
+        }
+    }
+
+    #[test]
+    fn synthesize_square_wave() {
+        let sound = square_wave();
+        let samples = sound.instruments[0].as_ref().unwrap().synthesize();
+
+        // The wave is high while `phase & 0x7FFF < 0x4000`, which flips every four samples.
+        // `65535 * (100 << 14) / 100 >> 15` is 32767.
+        let expected = (0..220).map(|i| if i % 8 < 4 { 32767 } else { -32767 }).collect::<Vec<i32>>();
+        assert_eq!(samples, expected);
+    }
+
+    #[test]
+    fn square_wave_to_wav() {
+        let wav = square_wave().to_wav();
+
+        assert_eq!(wav.len(), 44 + 220);
+        assert_eq!(&wav[40..44], &220u32.to_le_bytes());
+        let expected = (0..220).map(|i| if i % 8 < 4 { 0xFF } else { 0x00 }).collect::<Vec<u8>>();
+        assert_eq!(&wav[44..], expected);
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+#[cfg(feature = "osrs")]
+use crate::definitions::indextype::ConfigType;
+use crate::definitions::{
+    indextype::IndexType,
+    location_configs::location_config_fields::{ColourReplacements, Textures},
+};
+
+/// Describes a spot animation, also known as a graphic.
+///
+/// These are used by item effects, spells, npc attacks and so on.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SpotAnimConfig {
+    /// Its id.
+    pub id: u32,
+    /// The model this spot animation uses.
+    pub model: Option<u32>,
+    /// The animation sequence that is played on the model.
+    pub sequence: Option<u32>,
+    /// Horizontal scale, where 128 is the model's original size.
+    pub scale_xy: Option<u16>,
+    /// Vertical scale, where 128 is the model's original size.
+    pub scale_z: Option<u16>,
+    /// Rotation of the model, in units of 1/2048 of a full turn.
+    pub rotation: Option<u16>,
+    pub ambient: Option<u8>,
+    pub contrast: Option<u8>,
+    #[cfg(feature = "rs3")]
+    pub unknown_mode: Option<u8>,
+    /// Set alongside [`unknown_mode`](SpotAnimConfig::unknown_mode) by opcodes 9, 14, 15 and 16.
+    #[cfg(feature = "rs3")]
+    pub unknown_mode_value: Option<u32>,
+    #[cfg(feature = "rs3")]
+    pub unknown_10: Option<bool>,
+    #[serde(flatten)]
+    pub colour_replacements: Option<ColourReplacements>,
+    #[serde(flatten)]
+    pub textures: Option<Textures>,
+    #[cfg(feature = "rs3")]
+    pub unknown_44: Option<u16>,
+    #[cfg(feature = "rs3")]
+    pub unknown_45: Option<u16>,
+    #[cfg(feature = "rs3")]
+    pub unknown_46: Option<bool>,
+}
+
+impl SpotAnimConfig {
+    /// Returns a mapping of all [spot animation configurations](SpotAnimConfig)
+    #[cfg(feature = "rs3")]
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let archives = CacheIndex::new(IndexType::SPOT_CONFIG, config.input.clone())?.into_iter();
+        let spotanims = archives
+            .map(Result::unwrap)
+            .flat_map(|archive| {
+                let archive_id = archive.archive_id();
+                archive
+                    .take_files()
+                    .into_iter()
+                    .map(move |(file_id, file)| (archive_id << 8 | file_id, file))
+            })
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "spotanim configs" })?;
+        Ok(spotanims)
+    }
+
+    /// Returns a mapping of all [spot animation configurations](SpotAnimConfig)
+    #[cfg(feature = "osrs")]
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let spotanims = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+            .archive(ConfigType::SPOTANIM)?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "spotanim configs" })?;
+        Ok(spotanims)
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut spotanim = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(spotanim);
+                        }
+                    }
+                    #[cfg(feature = "rs3")]
+                    1 => spotanim.model = buffer.try_get_smart32()?,
+                    #[cfg(feature = "osrs")]
+                    1 => spotanim.model = Some(buffer.try_get_u16()? as u32),
+                    #[cfg(feature = "rs3")]
+                    2 => spotanim.sequence = buffer.try_get_smart32()?,
+                    #[cfg(feature = "osrs")]
+                    2 => spotanim.sequence = Some(buffer.try_get_u16()? as u32),
+                    4 => spotanim.scale_xy = Some(buffer.try_get_u16()?),
+                    5 => spotanim.scale_z = Some(buffer.try_get_u16()?),
+                    6 => spotanim.rotation = Some(buffer.try_get_u16()?),
+                    7 => spotanim.ambient = Some(buffer.try_get_u8()?),
+                    8 => spotanim.contrast = Some(buffer.try_get_u8()?),
+                    #[cfg(feature = "rs3")]
+                    9 => {
+                        spotanim.unknown_mode = Some(3);
+                        spotanim.unknown_mode_value = Some(8224);
+                    }
+                    #[cfg(feature = "rs3")]
+                    10 => spotanim.unknown_10 = Some(true),
+                    #[cfg(feature = "rs3")]
+                    11 => spotanim.unknown_mode = Some(1),
+                    #[cfg(feature = "rs3")]
+                    12 => spotanim.unknown_mode = Some(4),
+                    #[cfg(feature = "rs3")]
+                    13 => spotanim.unknown_mode = Some(5),
+                    #[cfg(feature = "rs3")]
+                    14 => {
+                        spotanim.unknown_mode = Some(2);
+                        spotanim.unknown_mode_value = Some(buffer.try_get_u8()? as u32 * 256);
+                    }
+                    #[cfg(feature = "rs3")]
+                    15 => {
+                        spotanim.unknown_mode = Some(3);
+                        spotanim.unknown_mode_value = Some(buffer.try_get_u16()? as u32);
+                    }
+                    #[cfg(feature = "rs3")]
+                    16 => {
+                        spotanim.unknown_mode = Some(3);
+                        spotanim.unknown_mode_value = Some(buffer.try_get_u32()?);
+                    }
+                    40 => spotanim.colour_replacements = Some(ColourReplacements::deserialize(&mut buffer)?),
+                    41 => spotanim.textures = Some(Textures::deserialize(&mut buffer)?),
+                    #[cfg(feature = "rs3")]
+                    44 => spotanim.unknown_44 = Some(buffer.try_get_masked_index()?),
+                    #[cfg(feature = "rs3")]
+                    45 => spotanim.unknown_45 = Some(buffer.try_get_masked_index()?),
+                    #[cfg(feature = "rs3")]
+                    46 => spotanim.unknown_46 = Some(true),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: spotanim.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for SpotAnimConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl SpotAnimConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("SpotAnimConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("SpotAnimConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the spot animation configs as `spotanim_configs.json`. Exposed as `--dump spotanim_configs`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let spotanims = SpotAnimConfig::dump_all(config)?.into_values().collect::<Vec<_>>();
+    let path = path!(config.output / "spotanim_configs.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&spotanims).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_spotanims() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let spotanims = SpotAnimConfig::dump_all(&config)?;
+        assert!(spotanims.values().any(|spotanim| spotanim.model.is_some()));
+        Ok(())
+    }
+}
@@ -226,6 +226,59 @@ pub fn dumps(scale: u32, ids: Vec<u32>, config: &crate::cli::Config) -> CacheRes
     Ok(sprites)
 }
 
+/// Loads the first frame of the sprite with the given id, or `None` if the cache does not have it.
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+pub fn load(id: u32, config: &crate::cli::Config) -> CacheResult<Option<Sprite>> {
+    let index = CacheIndex::new(IndexType::SPRITES, config.input.clone())?;
+    if !index.metadatas().keys().any(|&archive_id| archive_id == id) {
+        return Ok(None);
+    }
+    let Some(file) = index.archive(id)?.file(&0) else { return Ok(None) };
+    let mut frames = deserialize(file).context(Read { what: "sprite frames" })?;
+    Ok(frames.remove(&0))
+}
+
+/// Describes where the frames of a sprite are placed, which [`deserialize`] does not retain.
+#[derive(Debug, Clone, Default)]
+pub struct SpriteLayout {
+    /// The width of the area all frames are placed in.
+    pub width: u16,
+    /// The height of the area all frames are placed in.
+    pub height: u16,
+    /// The position of each frame within that area.
+    pub offsets: Vec<(u16, u16)>,
+}
+
+/// Reads the [`SpriteLayout`] of a sprite.
+pub fn deserialize_layout(buffer: Bytes) -> Result<SpriteLayout, ReadError> {
+    let mut buffer = Cursor::new(buffer);
+
+    buffer.seek(SeekFrom::End(-2)).context(FileSeek)?;
+
+    let data = buffer.try_get_u16()?;
+    let format = data >> 15;
+    let count = (data & 0x7FFF) as usize;
+
+    if format != 0 {
+        return Ok(SpriteLayout::default());
+    }
+
+    buffer.seek(SeekFrom::End(-7 - (count as i64) * 8)).context(FileSeek)?;
+
+    let width = buffer.try_get_u16()?;
+    let height = buffer.try_get_u16()?;
+    let _palette_count = buffer.try_get_u8()?;
+
+    let min_xs = iter::repeat_with(|| buffer.try_get_u16()).take(count).collect::<Result<Vec<_>, _>>()?;
+    let min_ys = iter::repeat_with(|| buffer.try_get_u16()).take(count).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SpriteLayout {
+        width,
+        height,
+        offsets: min_xs.into_iter().zip(min_ys).collect(),
+    })
+}
+
 pub fn deserialize(buffer: Bytes) -> Result<BTreeMap<usize, Sprite>, ReadError> {
     let mut buffer = Cursor::new(buffer);
 
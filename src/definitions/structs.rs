@@ -2,6 +2,7 @@
 
 use std::{
     collections::BTreeMap,
+    fmt::{self, Display, Formatter},
     fs::{self, File},
     io::Write,
 };
@@ -12,11 +13,14 @@ use path_macro::path;
 #[cfg(feature = "pyo3")]
 use pyo3::prelude::*;
 use rs3cache_backend::{
+    buf::{BufExtra, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
     error::{self, CacheResult},
     index::CacheIndex,
 };
 use serde::Serialize;
 
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::definitions::params::ParamConfig;
 use crate::{definitions::indextype::IndexType, structures::paramtable::ParamTable};
 
 /// Describes the properties of a given item.
@@ -46,29 +50,54 @@ impl Struct {
                     .into_iter()
                     .map(move |(file_id, file)| (archive_id << 5 | file_id, file))
             })
-            .map(|(id, file)| (id, Self::deserialize(id, file)))
-            .collect::<BTreeMap<u32, Self>>();
+            .map(|(id, file)| Self::deserialize(id, file).map(|r#struct| (id, r#struct)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "structs" })?;
         Ok(locations)
     }
 
-    fn deserialize(id: u32, mut buffer: Bytes) -> Self {
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
         let mut r#struct = Self { id, ..Default::default() };
 
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
         loop {
-            match buffer.get_u8() {
-                0 => {
-                    debug_assert!(!buffer.has_remaining());
-                    break r#struct;
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(r#struct);
+                        }
+                    }
+                    249 => r#struct.params = Some(ParamTable::deserialize(&mut buffer)?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: r#struct.to_string(),
+                    })
                 }
-                249 => r#struct.params = Some(ParamTable::deserialize(&mut buffer)),
-                missing => unimplemented!("Struct::deserialize cannot deserialize opcode {} in id {}", missing, id),
             }
         }
     }
 }
 
-use std::fmt::{self, Display, Formatter};
-
 impl Display for Struct {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
@@ -92,6 +121,8 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
     let mut structs = Struct::dump_all(config)?.into_values().collect::<Vec<_>>();
     structs.sort_unstable_by_key(|loc| loc.id);
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    ParamConfig::resolve_tables(config, structs.iter_mut().filter_map(|r#struct| r#struct.params.as_mut()))?;
 
     let path = path!(&config.output / "structs.json");
 
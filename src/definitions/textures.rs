@@ -1,3 +1,12 @@
+#[cfg(feature = "rs3")]
+mod bcn;
+#[cfg(feature = "rs3")]
+mod etc;
+
+use std::collections::BTreeMap;
+#[cfg(feature = "rs3")]
+use std::panic::Location;
+#[cfg(feature = "osrs")]
 use std::{
     collections::HashMap,
     fs::{self, File},
@@ -5,61 +14,161 @@ use std::{
 };
 
 use ::error::Context;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
+#[cfg(feature = "rs3")]
+use console::style;
+#[cfg(feature = "rs3")]
+use image::{ImageFormat, RgbaImage};
+#[cfg(feature = "rs3")]
+use indicatif::{ProgressBar, ProgressStyle};
 use path_macro::path;
-#[cfg(feature = "pyo3")]
+#[cfg(all(feature = "osrs", feature = "pyo3"))]
 use pyo3::prelude::*;
+#[cfg(feature = "rs3")]
+use rayon::iter::ParallelIterator;
+use rs3cache_backend::buf::{BufExtra, ReadError};
+#[cfg(feature = "rs3")]
+use rs3cache_backend::index::Initial;
 use rs3cache_backend::{
     error::{self, CacheResult},
     index::CacheIndex,
 };
+#[cfg(feature = "rs3")]
+use rs3cache_utils::bar::Render;
 use serde::Serialize;
 
 use crate::definitions::indextype::IndexType;
+#[cfg(feature = "rs3")]
+use crate::definitions::sprites::Sprite;
 
-/// Describes the properties of a given item.
-
+/// Describes the properties of a given texture.
+#[cfg(feature = "osrs")]
 #[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Clone, Debug, Default)]
 pub struct TextureConfig {
     /// Its id.
     pub id: u32,
-    pub field1777: u16,
+    /// The average colour of the texture, in the client's 16 bit HSL format.
+    pub average_hsl: u16,
     pub field1778: bool,
+    /// The ids of the sprites making up the texture.
+    pub sprite_ids: Vec<u16>,
+    pub field1780: Option<Vec<u8>>,
+    pub field1781: Option<Vec<u8>>,
+    pub field1786: Vec<i32>,
+    /// The direction the texture scrolls in, if animated.
+    pub animation_direction: u8,
+    /// How fast the texture scrolls, if animated.
+    pub animation_speed: u8,
 }
 
+#[cfg(feature = "osrs")]
 impl TextureConfig {
     /// Returns a mapping of all [`TextureConfig`]s.
     pub fn dump_all(config: &crate::cli::Config) -> CacheResult<HashMap<u32, Self>> {
-        let archives = CacheIndex::new(IndexType::TEXTURES, config.input.clone())?.into_iter();
-        let locations = archives
-            .flat_map(|archive| archive.unwrap().take_files().into_iter())
-            .map(|(id, file)| (id, Self::deserialize(id, file)))
-            .collect::<HashMap<u32, Self>>();
-        Ok(locations)
+        let mut textures = HashMap::new();
+        for archive in CacheIndex::new(IndexType::TEXTURES, config.input.clone())? {
+            for (id, file) in archive?.take_files() {
+                let texture = Self::deserialize(id, file).context(error::Read { what: "textures" })?;
+                textures.insert(id, texture);
+            }
+        }
+        Ok(textures)
     }
 
-    fn deserialize(id: u32, mut buffer: Bytes) -> Self {
-        let field1777 = buffer.get_u16();
-        let field1778 = buffer.get_i8() != 0;
-        let _count = buffer.get_u8();
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let average_hsl = buffer.try_get_u16()?;
+        let field1778 = buffer.try_get_i8()? != 0;
+        let count = buffer.try_get_u8()? as usize;
+        let sprite_ids = (0..count).map(|_| buffer.try_get_u16()).collect::<Result<_, _>>()?;
+        let field1780 = if count > 1 {
+            Some((1..count).map(|_| buffer.try_get_u8()).collect::<Result<_, _>>()?)
+        } else {
+            None
+        };
+        let field1781 = if count > 1 {
+            Some((1..count).map(|_| buffer.try_get_u8()).collect::<Result<_, _>>()?)
+        } else {
+            None
+        };
+        let field1786 = (0..count).map(|_| buffer.try_get_i32()).collect::<Result<_, _>>()?;
+        let animation_direction = buffer.try_get_u8()?;
+        let animation_speed = buffer.try_get_u8()?;
 
-        // todo: a lot of fields I dont care about
+        Ok(Self {
+            id,
+            average_hsl,
+            field1778,
+            sprite_ids,
+            field1780,
+            field1781,
+            field1786,
+            animation_direction,
+            animation_speed,
+        })
+    }
 
-        Self { id, field1777, field1778 }
+    /// The average colour of the texture, as the client draws it when textures are disabled.
+    pub fn average_colour(&self) -> [u8; 3] {
+        hsl_to_rgb(self.average_hsl)
     }
 }
 
+/// The brightness the client builds its colour palette with by default.
+#[cfg(feature = "osrs")]
+const BRIGHTNESS: f64 = 0.8;
+
+/// Converts a colour in the client's 16 bit HSL format to RGB, the same way the client builds its colour palette.
+///
+/// The six highest bits are the hue, followed by three bits of saturation and seven bits of lightness.
+#[cfg(feature = "osrs")]
+fn hsl_to_rgb(hsl: u16) -> [u8; 3] {
+    let hue = (hsl >> 10) as f64 / 64.0 + 0.0078125;
+    let saturation = ((hsl >> 7) & 7) as f64 / 8.0 + 0.0625;
+    let lightness = (hsl & 127) as f64 / 128.0;
+
+    let q = if lightness < 0.5 {
+        lightness * (1.0 + saturation)
+    } else {
+        lightness + saturation - lightness * saturation
+    };
+    let p = 2.0 * lightness - q;
+
+    let channel = |mut t: f64| {
+        if t > 1.0 {
+            t -= 1.0;
+        } else if t < 0.0 {
+            t += 1.0;
+        }
+        if 6.0 * t < 1.0 {
+            p + (q - p) * 6.0 * t
+        } else if 2.0 * t < 1.0 {
+            q
+        } else if 3.0 * t < 2.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        }
+    };
+
+    [hue + 1.0 / 3.0, hue, hue - 1.0 / 3.0].map(|t| {
+        let value = (channel(t) * 256.0) as i32;
+        ((value as f64 / 256.0).powf(BRIGHTNESS) * 256.0).min(255.0) as u8
+    })
+}
+
 use std::fmt::{self, Display, Formatter};
 
+#[cfg(feature = "osrs")]
 impl Display for TextureConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
     }
 }
 
-/// Save the textures as `textures.json`. Exposed as `--dump item_configs`.
+/// Save the textures as `textures.json`. Exposed as `--dump textures`.
+#[cfg(feature = "osrs")]
 pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
     fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
 
@@ -72,3 +181,480 @@ pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
 
     Ok(())
 }
+
+/// The pixels of a 4x4 block of a block compressed texture, row by row.
+#[cfg(feature = "rs3")]
+type Block = [[u8; 4]; 16];
+
+/// The image container a [`Texture`] is stored in.
+#[cfg(feature = "rs3")]
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextureFormat {
+    /// A PNG image.
+    Png,
+    /// A DirectDraw Surface, containing BCn (DXTn) compressed data.
+    Dds,
+    /// A Khronos texture, containing ETC1 or ETC2 compressed data.
+    Ktx,
+}
+
+#[cfg(feature = "rs3")]
+impl TextureFormat {
+    const PNG_MAGIC: &'static [u8] = b"\x89PNG\r\n\x1a\n";
+    const DDS_MAGIC: &'static [u8] = b"DDS ";
+    const KTX_MAGIC: &'static [u8] = b"\xabKTX 11\xbb\r\n\x1a\n";
+
+    fn sniff(data: &[u8]) -> Option<Self> {
+        if data.starts_with(Self::PNG_MAGIC) {
+            Some(Self::Png)
+        } else if data.starts_with(Self::DDS_MAGIC) {
+            Some(Self::Dds)
+        } else if data.starts_with(Self::KTX_MAGIC) {
+            Some(Self::Ktx)
+        } else {
+            None
+        }
+    }
+}
+
+/// A texture, as used by materials.
+///
+/// The same texture is usually present in several indices, each with a different encoding:
+/// [`TEXTURES`](IndexType::TEXTURES) holds DXT compressed textures,
+/// [`TEXTURES_PNG`](IndexType::TEXTURES_PNG) and [`TEXTURES_PNG_MIPPED`](IndexType::TEXTURES_PNG_MIPPED) hold PNG images
+/// and [`TEXTURES_ETC`](IndexType::TEXTURES_ETC) holds ETC compressed textures.
+#[cfg(feature = "rs3")]
+#[derive(Serialize, Clone, Debug)]
+pub struct Texture {
+    /// Its id.
+    pub id: u32,
+    /// The index it was read from.
+    pub index: u32,
+    /// The container of the image data.
+    pub format: TextureFormat,
+    /// The number of mipmap levels stored.
+    pub mipmaps: u8,
+    pub width: u32,
+    pub height: u32,
+    /// The image data of the largest mipmap level.
+    #[serde(skip)]
+    data: Bytes,
+}
+
+#[cfg(feature = "rs3")]
+impl Texture {
+    /// The indices holding textures, in order of preference.
+    pub const INDICES: [u32; 4] = [
+        IndexType::TEXTURES_PNG,
+        IndexType::TEXTURES,
+        IndexType::TEXTURES_ETC,
+        IndexType::TEXTURES_PNG_MIPPED,
+    ];
+
+    /// Returns a mapping of all [`Texture`]s.
+    ///
+    /// Textures present in multiple indices are read from the first of [`Texture::INDICES`] that has them.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let mut textures = BTreeMap::new();
+        for index_id in Self::INDICES {
+            let archives = CacheIndex::new(index_id, config.input.clone())?.into_iter();
+            for archive in archives {
+                let archive = archive?;
+                let id = archive.archive_id();
+                if textures.contains_key(&id) {
+                    continue;
+                }
+                if let Some(file) = archive.file(&0) {
+                    let texture = Self::deserialize(id, index_id, file).context(error::Read { what: "texture" })?;
+                    textures.insert(id, texture);
+                }
+            }
+        }
+        Ok(textures)
+    }
+
+    /// Opens each of [`Texture::INDICES`], for use with [`Texture::load_from`].
+    pub fn open_indices(config: &crate::cli::Config) -> CacheResult<Vec<(u32, CacheIndex<Initial>)>> {
+        Self::INDICES
+            .into_iter()
+            .map(|index_id| Ok((index_id, CacheIndex::new(index_id, config.input.clone())?)))
+            .collect()
+    }
+
+    /// Loads a single texture.
+    pub fn load(id: u32, config: &crate::cli::Config) -> CacheResult<Option<Self>> {
+        Self::load_from(id, &Self::open_indices(config)?)
+    }
+
+    /// Loads a single texture from indices opened by [`Texture::open_indices`].
+    pub fn load_from(id: u32, indices: &[(u32, CacheIndex<Initial>)]) -> CacheResult<Option<Self>> {
+        for (index_id, index) in indices {
+            if index.metadatas().get(&id).is_none() {
+                continue;
+            }
+            if let Some(file) = index.archive(id)?.file(&0) {
+                let texture = Self::deserialize(id, *index_id, file).context(error::Read { what: "texture" })?;
+                return Ok(Some(texture));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads the container of a texture.
+    ///
+    /// Textures are either a bare image, or are prefixed by a format byte and a mipmap count,
+    /// followed by the length prefixed image data of each mipmap level.
+    pub fn deserialize(id: u32, index: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let (mipmaps, data) = if TextureFormat::sniff(&buffer).is_some() {
+            (1, buffer)
+        } else {
+            let _format = buffer.try_get_u8()?;
+            let mipmaps = buffer.try_get_u8()?;
+            let len = buffer.try_get_u32()? as usize;
+            if buffer.len() < len {
+                return Err(ReadError::Eof {
+                    location: Location::caller(),
+                    expected: len,
+                    found: buffer.len(),
+                });
+            }
+            (mipmaps, buffer.split_to(len))
+        };
+
+        let format = TextureFormat::sniff(&data).ok_or_else(|| ReadError::Unsupported {
+            location: Location::caller(),
+            what: "texture container",
+            reason: format!("unrecognized magic bytes {:?}", &data[..data.len().min(12)]),
+        })?;
+
+        let (width, height) = match format {
+            TextureFormat::Png => (read_u32_be(&data, 16)?, read_u32_be(&data, 20)?),
+            TextureFormat::Dds => (read_u32_le(&data, 16)?, read_u32_le(&data, 12)?),
+            TextureFormat::Ktx => (read_u32_le(&data, 36)?, read_u32_le(&data, 40)?),
+        };
+
+        Ok(Self {
+            id,
+            index,
+            format,
+            mipmaps,
+            width,
+            height,
+            data,
+        })
+    }
+
+    /// Decodes the largest mipmap level of the texture.
+    pub fn decode(&self) -> Result<Sprite, ReadError> {
+        match self.format {
+            TextureFormat::Png => image::load_from_memory_with_format(&self.data, ImageFormat::Png)
+                .map(|img| img.to_rgba8())
+                .map_err(|e| ReadError::Unsupported {
+                    location: Location::caller(),
+                    what: "png texture",
+                    reason: e.to_string(),
+                }),
+            TextureFormat::Dds => self.decode_dds(),
+            TextureFormat::Ktx => self.decode_ktx(),
+        }
+    }
+
+    fn decode_dds(&self) -> Result<Sprite, ReadError> {
+        const HEADER_LEN: usize = 4 + 124;
+        const DX10_HEADER_LEN: usize = 20;
+        const DDPF_FOURCC: u32 = 0x4;
+        const DDPF_RGB: u32 = 0x40;
+
+        let data = &self.data;
+        let flags = read_u32_le(data, 80)?;
+        let fourcc = data.get(84..88).unwrap_or_default();
+
+        if flags & DDPF_FOURCC != 0 {
+            let (block_size, decoder, offset): (usize, fn(&[u8]) -> Block, usize) = match fourcc {
+                b"DXT1" => (8, bcn::bc1, HEADER_LEN),
+                b"DXT2" | b"DXT3" => (16, bcn::bc2, HEADER_LEN),
+                b"DXT4" | b"DXT5" => (16, bcn::bc3, HEADER_LEN),
+                b"ATI1" | b"BC4U" => (8, bcn::bc4, HEADER_LEN),
+                b"ATI2" | b"BC5U" => (16, bcn::bc5, HEADER_LEN),
+                b"DX10" => match read_u32_le(data, HEADER_LEN)? {
+                    70..=72 => (8, bcn::bc1, HEADER_LEN + DX10_HEADER_LEN),
+                    73..=75 => (16, bcn::bc2, HEADER_LEN + DX10_HEADER_LEN),
+                    76..=78 => (16, bcn::bc3, HEADER_LEN + DX10_HEADER_LEN),
+                    79..=81 => (8, bcn::bc4, HEADER_LEN + DX10_HEADER_LEN),
+                    82..=84 => (16, bcn::bc5, HEADER_LEN + DX10_HEADER_LEN),
+                    other => {
+                        return Err(ReadError::Unsupported {
+                            location: Location::caller(),
+                            what: "dds texture",
+                            reason: format!("dxgi format {other}"),
+                        })
+                    }
+                },
+                other => {
+                    return Err(ReadError::Unsupported {
+                        location: Location::caller(),
+                        what: "dds texture",
+                        reason: format!("fourcc {:?}", String::from_utf8_lossy(other)),
+                    })
+                }
+            };
+            decode_blocks(self.width, self.height, &data[offset.min(data.len())..], block_size, decoder)
+        } else if flags & DDPF_RGB != 0 && read_u32_le(data, 88)? == 32 {
+            let masks = [read_u32_le(data, 92)?, read_u32_le(data, 96)?, read_u32_le(data, 100)?, read_u32_le(data, 104)?];
+            let pixels = data.get(HEADER_LEN..).unwrap_or_default();
+            let expected = self.width as usize * self.height as usize * 4;
+            if pixels.len() < expected {
+                return Err(ReadError::Eof {
+                    location: Location::caller(),
+                    expected,
+                    found: pixels.len(),
+                });
+            }
+
+            let extract = |value: u32, mask: u32| if mask == 0 { 255 } else { ((value & mask) >> mask.trailing_zeros()) as u8 };
+            let mut img = RgbaImage::new(self.width, self.height);
+            for (pixel, chunk) in img.pixels_mut().zip(pixels.chunks_exact(4)) {
+                let value = u32::from_le_bytes(chunk.try_into().unwrap());
+                pixel.0 = masks.map(|mask| extract(value, mask));
+            }
+            Ok(img)
+        } else {
+            Err(ReadError::Unsupported {
+                location: Location::caller(),
+                what: "dds texture",
+                reason: format!("pixel format flags {flags:#x}"),
+            })
+        }
+    }
+
+    fn decode_ktx(&self) -> Result<Sprite, ReadError> {
+        const HEADER_LEN: usize = 64;
+        const GL_RGBA8: u32 = 0x8058;
+        const GL_COMPRESSED_RGBA_S3TC_DXT1: u32 = 0x83F1;
+        const GL_COMPRESSED_RGBA_S3TC_DXT3: u32 = 0x83F2;
+        const GL_COMPRESSED_RGBA_S3TC_DXT5: u32 = 0x83F3;
+        const GL_ETC1_RGB8: u32 = 0x8D64;
+        const GL_COMPRESSED_RGB8_ETC2: u32 = 0x9274;
+        const GL_COMPRESSED_RGBA8_ETC2_EAC: u32 = 0x9278;
+
+        let data = &self.data;
+        let internal_format = read_u32_le(data, 28)?;
+        let key_value_len = read_u32_le(data, 60)? as usize;
+        let offset = HEADER_LEN + key_value_len;
+        let image_len = read_u32_le(data, offset)? as usize;
+        let pixels = data.get(offset + 4..).unwrap_or_default();
+        let pixels = &pixels[..image_len.min(pixels.len())];
+
+        let (block_size, decoder): (usize, fn(&[u8]) -> Block) = match internal_format {
+            GL_ETC1_RGB8 => (8, etc::etc1),
+            GL_COMPRESSED_RGB8_ETC2 => (8, etc::etc2_rgb),
+            GL_COMPRESSED_RGBA8_ETC2_EAC => (16, etc::etc2_rgba),
+            0x83F0 | GL_COMPRESSED_RGBA_S3TC_DXT1 => (8, bcn::bc1),
+            GL_COMPRESSED_RGBA_S3TC_DXT3 => (16, bcn::bc2),
+            GL_COMPRESSED_RGBA_S3TC_DXT5 => (16, bcn::bc3),
+            GL_RGBA8 => {
+                return RgbaImage::from_raw(self.width, self.height, pixels.to_vec()).ok_or_else(|| ReadError::Eof {
+                    location: Location::caller(),
+                    expected: self.width as usize * self.height as usize * 4,
+                    found: pixels.len(),
+                })
+            }
+            other => {
+                return Err(ReadError::Unsupported {
+                    location: Location::caller(),
+                    what: "ktx texture",
+                    reason: format!("internal format {other:#x}"),
+                })
+            }
+        };
+        decode_blocks(self.width, self.height, pixels, block_size, decoder)
+    }
+
+    /// The average colour of the texture, ignoring transparent pixels.
+    pub fn average_colour(&self) -> Result<[u8; 3], ReadError> {
+        self.decode().map(|img| average(&img))
+    }
+}
+
+#[cfg(feature = "rs3")]
+impl Display for Texture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "rs3")]
+fn read_u32_le(data: &[u8], offset: usize) -> Result<u32, ReadError> {
+    let bytes = data.get(offset..offset + 4).ok_or_else(|| ReadError::Eof {
+        location: Location::caller(),
+        expected: offset + 4,
+        found: data.len(),
+    })?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(feature = "rs3")]
+fn read_u32_be(data: &[u8], offset: usize) -> Result<u32, ReadError> {
+    read_u32_le(data, offset).map(u32::swap_bytes)
+}
+
+/// Decodes block compressed image data, with 4x4 pixels per block.
+#[cfg(feature = "rs3")]
+fn decode_blocks(width: u32, height: u32, data: &[u8], block_size: usize, decoder: fn(&[u8]) -> Block) -> Result<Sprite, ReadError> {
+    let blocks_wide = width.div_ceil(4) as usize;
+    let blocks_high = height.div_ceil(4) as usize;
+    let expected = blocks_wide * blocks_high * block_size;
+    if data.len() < expected {
+        return Err(ReadError::Eof {
+            location: Location::caller(),
+            expected,
+            found: data.len(),
+        });
+    }
+
+    let mut img = RgbaImage::new(width, height);
+    for (n, block) in data.chunks_exact(block_size).take(blocks_wide * blocks_high).enumerate() {
+        let (block_x, block_y) = ((n % blocks_wide) as u32 * 4, (n / blocks_wide) as u32 * 4);
+        for (i, pixel) in decoder(block).into_iter().enumerate() {
+            let (x, y) = (block_x + i as u32 % 4, block_y + i as u32 / 4);
+            if x < width && y < height {
+                img.put_pixel(x, y, image::Rgba(pixel));
+            }
+        }
+    }
+    Ok(img)
+}
+
+/// The average colour of an image, ignoring transparent pixels.
+#[cfg(feature = "rs3")]
+fn average(img: &Sprite) -> [u8; 3] {
+    let (sum, count) = img
+        .pixels()
+        .filter(|pixel| pixel[3] != 0)
+        .fold(([0u64; 3], 0u64), |([r, g, b], count), pixel| {
+            ([r + pixel[0] as u64, g + pixel[1] as u64, b + pixel[2] as u64], count + 1)
+        });
+    sum.map(|channel| (channel / count.max(1)) as u8)
+}
+
+/// Computes the average colours of the given textures. Textures that cannot be found are skipped.
+#[cfg(feature = "osrs")]
+pub fn average_colours(ids: impl IntoIterator<Item = u32>, config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, [u8; 3]>> {
+    let textures = TextureConfig::dump_all(config)?;
+    let colours = ids
+        .into_iter()
+        .filter_map(|id| textures.get(&id).map(|texture| (id, texture.average_colour())))
+        .collect();
+    Ok(colours)
+}
+
+/// Computes the average colours of the given textures.
+///
+/// Textures that cannot be found are skipped. Textures that cannot be decoded are skipped,
+/// with a warning printed through the progress bar.
+#[cfg(feature = "rs3")]
+pub fn average_colours(ids: impl IntoIterator<Item = u32>, config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, [u8; 3]>> {
+    let ids = ids.into_iter().collect::<Vec<_>>();
+    let indices = Texture::open_indices(config)?;
+
+    let progress = ProgressBar::new(ids.len() as u64).with_style(
+        ProgressStyle::with_template(&format!("   {} [{{bar:30}}] {{pos}}/{{len}}: textures", style("Loading").cyan().bright()))
+            .unwrap()
+            .progress_chars("=> "),
+    );
+
+    let mut colours = BTreeMap::new();
+    for id in ids {
+        if let Some(texture) = Texture::load_from(id, &indices)? {
+            match texture.average_colour() {
+                Ok(colour) => {
+                    colours.insert(id, colour);
+                }
+                Err(e) => progress.println(format!("    {} texture {id} could not be decoded: {e}", style("Warning").yellow())),
+            }
+        }
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+
+    Ok(colours)
+}
+
+/// Saves every texture as `textures/{id}.png` and their descriptions as `textures.json`. Exposed as `--dump textures`.
+#[cfg(feature = "rs3")]
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    use std::{
+        fs::{self, File},
+        io::Write,
+    };
+
+    let folder = path!(config.output / "textures");
+    fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
+
+    let textures = Texture::dump_all(config)?;
+
+    textures.values().collect::<Vec<_>>().into_iter().render("textures").for_each(|(texture, bar)| {
+        match texture.decode() {
+            Ok(img) => {
+                let filename = path!(&folder / format!("{}.png", texture.id));
+                img.save(&filename)
+                    .unwrap_or_else(|_| panic!("Unable to save texture {} to {}", texture.id, filename.to_string_lossy()));
+            }
+            Err(e) => bar.println(format!("Unable to decode texture {}: {e}", texture.id)),
+        }
+    });
+
+    let path = path!(config.output / "textures.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&textures.values().collect::<Vec<_>>()).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "osrs"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn water_colour() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let colours = average_colours([1], &config)?;
+        let [red, green, blue] = colours[&1];
+        assert!(blue > red && blue > green, "water should be blue, but is {:?}", colours[&1]);
+        Ok(())
+    }
+
+    #[test]
+    fn hsl_to_rgb_builds_the_palette() {
+        assert_eq!(hsl_to_rgb(0), [0, 0, 0]);
+        assert_eq!(hsl_to_rgb(127), [254, 253, 253]);
+        assert_eq!(hsl_to_rgb(7 << 7 | 64), [249, 31, 15]);
+    }
+
+    #[test]
+    fn average_colour_of_known_texture() {
+        // Hue 43, saturation 4 and lightness 64: a blue.
+        let texture = TextureConfig {
+            average_hsl: 43 << 10 | 4 << 7 | 64,
+            ..Default::default()
+        };
+        assert_eq!(texture.average_colour(), [87, 75, 210]);
+    }
+}
+
+#[cfg(all(test, feature = "rs3"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_textures() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let texture = Texture::load(1, &config)?.expect("texture 1 is missing");
+        let img = texture.decode().unwrap();
+        assert_eq!(img.dimensions(), (texture.width, texture.height));
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+//! Decoders for the block compression formats of DirectX, also known as S3TC or DXTn.
+//!
+//! See <https://learn.microsoft.com/en-us/windows/win32/direct3d10/d3d10-graphics-programming-guide-resources-block-compression>.
+
+use super::Block;
+
+fn rgb565(colour: u16) -> [i32; 3] {
+    let r = (colour >> 11) & 0x1F;
+    let g = (colour >> 5) & 0x3F;
+    let b = colour & 0x1F;
+    [(r << 3 | r >> 2) as i32, (g << 2 | g >> 4) as i32, (b << 3 | b >> 2) as i32]
+}
+
+fn mix([a, b]: [[i32; 3]; 2], weight_a: i32, weight_b: i32) -> [u8; 4] {
+    let channel = |c| ((a[c] * weight_a + b[c] * weight_b) / (weight_a + weight_b)) as u8;
+    [channel(0), channel(1), channel(2), 255]
+}
+
+/// Decodes the colour part of a block. BC2 and BC3 always use the four colour mode.
+fn colours(block: &[u8], always_opaque: bool) -> Block {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let endpoints = [rgb565(c0), rgb565(c1)];
+
+    let palette = if c0 > c1 || always_opaque {
+        [mix(endpoints, 1, 0), mix(endpoints, 0, 1), mix(endpoints, 2, 1), mix(endpoints, 1, 2)]
+    } else {
+        [mix(endpoints, 1, 0), mix(endpoints, 0, 1), mix(endpoints, 1, 1), [0, 0, 0, 0]]
+    };
+
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    let mut out = [[0; 4]; 16];
+    for (i, pixel) in out.iter_mut().enumerate() {
+        *pixel = palette[(indices >> (2 * i) & 0b11) as usize];
+    }
+    out
+}
+
+/// Decodes an interpolated single channel block, as used by BC3, BC4 and BC5.
+fn channel(block: &[u8]) -> [u8; 16] {
+    let a0 = block[0] as u32;
+    let a1 = block[1] as u32;
+
+    let mut palette = [0; 8];
+    palette[0] = a0;
+    palette[1] = a1;
+    if a0 > a1 {
+        for k in 1..7 {
+            palette[k as usize + 1] = ((7 - k) * a0 + k * a1) / 7;
+        }
+    } else {
+        for k in 1..5 {
+            palette[k as usize + 1] = ((5 - k) * a0 + k * a1) / 5;
+        }
+        palette[6] = 0;
+        palette[7] = 255;
+    }
+
+    let indices = block[2..8].iter().rev().fold(0u64, |acc, &byte| acc << 8 | byte as u64);
+    let mut out = [0; 16];
+    for (i, value) in out.iter_mut().enumerate() {
+        *value = palette[(indices >> (3 * i) & 0b111) as usize] as u8;
+    }
+    out
+}
+
+/// Decodes a BC1 (DXT1) block of 8 bytes.
+pub(super) fn bc1(block: &[u8]) -> Block {
+    colours(block, false)
+}
+
+/// Decodes a BC2 (DXT3) block of 16 bytes.
+pub(super) fn bc2(block: &[u8]) -> Block {
+    let mut out = colours(&block[8..], true);
+    let alphas = u64::from_le_bytes(block[..8].try_into().unwrap());
+    for (i, pixel) in out.iter_mut().enumerate() {
+        pixel[3] = (alphas >> (4 * i) & 0xF) as u8 * 17;
+    }
+    out
+}
+
+/// Decodes a BC3 (DXT5) block of 16 bytes.
+pub(super) fn bc3(block: &[u8]) -> Block {
+    let mut out = colours(&block[8..], true);
+    for (pixel, alpha) in out.iter_mut().zip(channel(&block[..8])) {
+        pixel[3] = alpha;
+    }
+    out
+}
+
+/// Decodes a BC4 block of 8 bytes, as greyscale.
+pub(super) fn bc4(block: &[u8]) -> Block {
+    channel(block).map(|value| [value, value, value, 255])
+}
+
+/// Decodes a BC5 block of 16 bytes.
+///
+/// These are normal maps, so the blue channel is reconstructed from the other two.
+pub(super) fn bc5(block: &[u8]) -> Block {
+    let reds = channel(&block[..8]);
+    let greens = channel(&block[8..]);
+
+    let mut out = [[0; 4]; 16];
+    for ((pixel, r), g) in out.iter_mut().zip(reds).zip(greens) {
+        let x = r as f32 / 127.5 - 1.0;
+        let y = g as f32 / 127.5 - 1.0;
+        let z = (1.0 - x * x - y * y).max(0.0).sqrt();
+        *pixel = [r, g, ((z + 1.0) * 127.5) as u8, 255];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bc1_endpoints() {
+        // Pure red and pure blue, with every pixel using the first endpoint but the last one.
+        let block = [0x00, 0xF8, 0x1F, 0x00, 0x00, 0x00, 0x00, 0x40];
+        let pixels = bc1(&block);
+        assert_eq!(pixels[0], [255, 0, 0, 255]);
+        assert_eq!(pixels[15], [0, 0, 255, 255]);
+    }
+}
@@ -0,0 +1,236 @@
+//! Decoders for the Ericsson texture compression formats ETC1 and ETC2, including EAC alpha.
+//!
+//! See the Khronos Data Format Specification, sections "ETC1 Compressed Texture Image Formats"
+//! and "ETC2 Compressed Texture Image Formats".
+
+use super::Block;
+
+const MODIFIERS: [[i32; 4]; 8] = [
+    [2, 8, -2, -8],
+    [5, 17, -5, -17],
+    [9, 29, -9, -29],
+    [13, 42, -13, -42],
+    [18, 60, -18, -60],
+    [24, 80, -24, -80],
+    [33, 106, -33, -106],
+    [47, 183, -47, -183],
+];
+
+const DISTANCES: [i32; 8] = [3, 6, 11, 16, 23, 32, 41, 64];
+
+const ALPHA_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+/// Extracts the bits `high..=low` of `bits`.
+fn field(bits: u64, high: u32, low: u32) -> i32 {
+    ((bits >> low) & ((1 << (high - low + 1)) - 1)) as i32
+}
+
+fn extend4(value: i32) -> i32 {
+    value << 4 | value
+}
+
+fn extend5(value: i32) -> i32 {
+    value << 3 | value >> 2
+}
+
+fn extend6(value: i32) -> i32 {
+    value << 2 | value >> 4
+}
+
+fn extend7(value: i32) -> i32 {
+    value << 1 | value >> 6
+}
+
+fn pixel([r, g, b]: [i32; 3]) -> [u8; 4] {
+    [r.clamp(0, 255) as u8, g.clamp(0, 255) as u8, b.clamp(0, 255) as u8, 255]
+}
+
+fn offset([r, g, b]: [i32; 3], by: i32) -> [i32; 3] {
+    [r + by, g + by, b + by]
+}
+
+/// The index of the pixel at (x, y) in the selector bits. Pixels are stored column by column.
+fn selector(bits: u64, x: usize, y: usize) -> usize {
+    let i = x * 4 + y;
+    ((bits >> (i + 16) & 1) << 1 | (bits >> i & 1)) as usize
+}
+
+/// Places the pixel at (x, y) in the row-major output block.
+fn for_each_pixel(mut f: impl FnMut(usize, usize) -> [u8; 4]) -> Block {
+    let mut out = [[0; 4]; 16];
+    for y in 0..4 {
+        for x in 0..4 {
+            out[y * 4 + x] = f(x, y);
+        }
+    }
+    out
+}
+
+/// Decodes an ETC1 block of 8 bytes.
+pub(super) fn etc1(block: &[u8]) -> Block {
+    let bits = u64::from_be_bytes(block[..8].try_into().unwrap());
+    let differential = field(bits, 33, 33) == 1;
+    let (base_1, base_2) = if differential {
+        let base = [field(bits, 63, 59), field(bits, 55, 51), field(bits, 47, 43)];
+        let delta = [field(bits, 58, 56), field(bits, 50, 48), field(bits, 42, 40)].map(|d| (d << 29) >> 29);
+        (
+            base.map(extend5),
+            [base[0] + delta[0], base[1] + delta[1], base[2] + delta[2]].map(extend5),
+        )
+    } else {
+        (
+            [field(bits, 63, 60), field(bits, 55, 52), field(bits, 47, 44)].map(extend4),
+            [field(bits, 59, 56), field(bits, 51, 48), field(bits, 43, 40)].map(extend4),
+        )
+    };
+    individual(bits, base_1, base_2)
+}
+
+/// Decodes a block in the "individual" or "differential" mode, given its two base colours.
+fn individual(bits: u64, base_1: [i32; 3], base_2: [i32; 3]) -> Block {
+    let tables = [field(bits, 39, 37) as usize, field(bits, 36, 34) as usize];
+    let flipped = field(bits, 32, 32) == 1;
+
+    for_each_pixel(|x, y| {
+        let second = if flipped { y >= 2 } else { x >= 2 };
+        let (base, table) = if second { (base_2, tables[1]) } else { (base_1, tables[0]) };
+        pixel(offset(base, MODIFIERS[table][selector(bits, x, y)]))
+    })
+}
+
+/// Decodes an ETC2 RGB block of 8 bytes. This is a superset of ETC1.
+pub(super) fn etc2_rgb(block: &[u8]) -> Block {
+    let bits = u64::from_be_bytes(block[..8].try_into().unwrap());
+    if field(bits, 33, 33) == 0 {
+        return etc1(block);
+    }
+
+    // In differential mode, an overflowing base colour selects one of the ETC2 modes.
+    let overflows = |high, low| {
+        let value = field(bits, high, high - 4) + ((field(bits, low, low - 2) << 29) >> 29);
+        !(0..32).contains(&value)
+    };
+
+    if overflows(63, 58) {
+        t_mode(bits)
+    } else if overflows(55, 50) {
+        h_mode(bits)
+    } else if overflows(47, 42) {
+        planar(bits)
+    } else {
+        etc1(block)
+    }
+}
+
+fn t_mode(bits: u64) -> Block {
+    let colour_1 = [
+        field(bits, 60, 59) << 2 | field(bits, 57, 56),
+        field(bits, 55, 52),
+        field(bits, 51, 48),
+    ]
+    .map(extend4);
+    let colour_2 = [field(bits, 47, 44), field(bits, 43, 40), field(bits, 39, 36)].map(extend4);
+    let distance = DISTANCES[(field(bits, 35, 34) << 1 | field(bits, 32, 32)) as usize];
+
+    let paint = [colour_1, offset(colour_2, distance), colour_2, offset(colour_2, -distance)].map(pixel);
+    for_each_pixel(|x, y| paint[selector(bits, x, y)])
+}
+
+fn h_mode(bits: u64) -> Block {
+    let colour_1 = [
+        field(bits, 62, 59),
+        field(bits, 58, 56) << 1 | field(bits, 52, 52),
+        field(bits, 51, 51) << 3 | field(bits, 49, 47),
+    ];
+    let colour_2 = [field(bits, 46, 43), field(bits, 42, 39), field(bits, 38, 35)];
+
+    let order = |[r, g, b]: [i32; 3]| r << 8 | g << 4 | b;
+    let distance_index = field(bits, 34, 34) << 2 | field(bits, 32, 32) << 1 | (order(colour_1) >= order(colour_2)) as i32;
+    let distance = DISTANCES[distance_index as usize];
+
+    let (colour_1, colour_2) = (colour_1.map(extend4), colour_2.map(extend4));
+    let paint = [
+        offset(colour_1, distance),
+        offset(colour_1, -distance),
+        offset(colour_2, distance),
+        offset(colour_2, -distance),
+    ]
+    .map(pixel);
+    for_each_pixel(|x, y| paint[selector(bits, x, y)])
+}
+
+fn planar(bits: u64) -> Block {
+    let origin = [
+        extend6(field(bits, 62, 57)),
+        extend7(field(bits, 56, 56) << 6 | field(bits, 54, 49)),
+        extend6(field(bits, 48, 48) << 5 | field(bits, 44, 43) << 3 | field(bits, 41, 39)),
+    ];
+    let horizontal = [
+        extend6(field(bits, 38, 34) << 1 | field(bits, 32, 32)),
+        extend7(field(bits, 31, 25)),
+        extend6(field(bits, 24, 19)),
+    ];
+    let vertical = [extend6(field(bits, 18, 13)), extend7(field(bits, 12, 6)), extend6(field(bits, 5, 0))];
+
+    for_each_pixel(|x, y| {
+        let (x, y) = (x as i32, y as i32);
+        let channel = |c: usize| (x * (horizontal[c] - origin[c]) + y * (vertical[c] - origin[c]) + 4 * origin[c] + 2) >> 2;
+        pixel([channel(0), channel(1), channel(2)])
+    })
+}
+
+/// Decodes an EAC alpha block of 8 bytes.
+fn eac(block: &[u8]) -> [u8; 16] {
+    let bits = u64::from_be_bytes(block[..8].try_into().unwrap());
+    let base = field(bits, 63, 56);
+    let multiplier = field(bits, 55, 52);
+    let table = ALPHA_MODIFIERS[field(bits, 51, 48) as usize];
+
+    let mut out = [0; 16];
+    for y in 0..4 {
+        for x in 0..4 {
+            let i = (x * 4 + y) as u32;
+            let modifier = table[field(bits, 47 - 3 * i, 45 - 3 * i) as usize];
+            out[y * 4 + x] = (base + modifier * multiplier).clamp(0, 255) as u8;
+        }
+    }
+    out
+}
+
+/// Decodes an ETC2 RGBA block of 16 bytes, which is an EAC alpha block followed by an ETC2 RGB block.
+pub(super) fn etc2_rgba(block: &[u8]) -> Block {
+    let mut out = etc2_rgb(&block[8..]);
+    for (pixel, alpha) in out.iter_mut().zip(eac(&block[..8])) {
+        pixel[3] = alpha;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etc1_individual_mode() {
+        // Both halves are the colour 0x88 grey with the smallest modifiers, and every selector 0.
+        let block = [0x88, 0x88, 0x88, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert!(etc1(&block).iter().all(|&p| p == [0x88 + 2, 0x88 + 2, 0x88 + 2, 255]));
+    }
+}
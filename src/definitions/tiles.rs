@@ -3,9 +3,7 @@ use bytes::{Buf, Bytes};
 use ndarray::{Array, ArrayBase, Dim, OwnedRepr};
 #[cfg(feature = "pyo3")]
 use pyo3::prelude::*;
-use rs3cache_backend::buf::BufExtra;
-#[cfg(any(feature = "osrs", feature = "legacy"))]
-use rs3cache_backend::buf::ReadError;
+use rs3cache_backend::buf::{BufExtra, ReadError};
 use serde::Serialize;
 
 /// Type alias for the 4x64x64 array of [`Tile`]s in a [`MapSquare`](crate::definitions::mapsquares::MapSquare).
@@ -35,59 +33,66 @@ pub struct Tile {
 
 impl Tile {
     /// Constructor for a sequence of [`Tile`]s.
+    ///
+    /// This also reads the underwater tiles of a [`MapSquare`](crate::definitions::mapsquares::MapSquare), which share this layout.
     #[cfg(any(feature = "rs3", feature = "2013_shim"))]
-    pub fn dump(buffer: &mut Bytes) -> TileArray {
-        let is_936 = &buffer[0..5] == b"jagx\x01";
+    pub fn dump(buffer: &mut Bytes) -> Result<TileArray, ReadError> {
+        let is_936 = buffer.starts_with(b"jagx\x01");
         if is_936 {
             buffer.advance(5)
         }
 
-        Array::from_shape_simple_fn((4, 64, 64), || {
+        let producer = || try {
             let mut tile = Tile::default();
 
-            let [flag_1, flag_2, flag_3, flag_4, ..] = buffer.get_bitflags();
+            let flags = buffer.try_get_u8()?;
 
-            if flag_1 {
-                tile.shape = Some(buffer.get_u8());
-                tile.overlay_id = Some(buffer.get_unsigned_smart());
+            if flags & 0x1 != 0 {
+                tile.shape = Some(buffer.try_get_u8()?);
+                tile.overlay_id = Some(buffer.try_get_unsigned_smart()?);
             }
 
-            if flag_2 {
-                tile.settings = Some(buffer.get_u8());
+            if flags & 0x2 != 0 {
+                tile.settings = Some(buffer.try_get_u8()?);
             }
 
-            if flag_3 {
-                tile.underlay_id = Some(buffer.get_unsigned_smart());
+            if flags & 0x4 != 0 {
+                tile.underlay_id = Some(buffer.try_get_unsigned_smart()?);
             }
 
-            if flag_4 {
+            if flags & 0x8 != 0 {
                 // Before 936 this used to be a u8
-                tile.height = Some(if is_936 { buffer.get_u16() } else { buffer.get_u8() as _ });
+                tile.height = Some(if is_936 { buffer.try_get_u16()? } else { buffer.try_get_u8()? as _ });
             }
 
             tile
-        })
+        };
+
+        let tiles = std::iter::repeat_with(producer)
+            .take(4 * 64 * 64)
+            .collect::<Result<Vec<Tile>, ReadError>>()?;
+        Ok(Array::from_shape_vec((4, 64, 64), tiles).unwrap())
     }
 
     #[cfg(feature = "legacy")]
-    pub fn dump(buffer: &mut Bytes) -> TileArray {
-        let shape = Self::try_dump(buffer.clone(), false).unwrap();
+    pub fn dump(buffer: &mut Bytes) -> Result<TileArray, ReadError> {
+        let shape = Self::try_dump(buffer.clone(), false)?;
 
-        Array::from_shape_vec((4, 64, 64), shape).unwrap()
+        Ok(Array::from_shape_vec((4, 64, 64), shape).unwrap())
     }
 
     #[cfg(all(feature = "osrs", not(feature = "2013_shim")))]
-    pub fn dump(buffer: &mut Bytes) -> TileArray {
+    pub fn dump(buffer: &mut Bytes) -> Result<TileArray, ReadError> {
         // This is a hack to deal with the changing of the tile format
         //
         // Rather than introducing a new feature for it,
         // try to figure out the correct format at runtime
         let shape = match Self::try_dump(buffer.clone(), true) {
             Ok(shape) => shape,
-            Err(_) => Self::try_dump(buffer.clone(), false).unwrap(),
+            Err(_) => Self::try_dump(buffer.clone(), false)?,
         };
 
-        Array::from_shape_vec((4, 64, 64), shape).unwrap()
+        Ok(Array::from_shape_vec((4, 64, 64), shape).unwrap())
     }
 
     #[cfg(any(feature = "osrs", feature = "legacy"))]
@@ -0,0 +1,273 @@
+//! Variables of the various var domains.
+//!
+//! See also [`VarbitConfig`](crate::definitions::varbit_configs::VarbitConfig), which maps onto these.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::Write,
+};
+
+use ::error::Context;
+use bytes::{Buf, Bytes};
+use path_macro::path;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::{
+    buf::{BufExtra, JString, NotExhausted, OpcodeNotImplemented, ReadError, WithInfo},
+    error::{self, CacheResult},
+    index::CacheIndex,
+};
+use serde::Serialize;
+
+use crate::definitions::indextype::{ConfigType, IndexType};
+
+/// The domain a variable belongs to, such as the player or the world.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum VarDomain {
+    Player,
+    Npc,
+    Client,
+    World,
+    Region,
+    Object,
+    Clan,
+    ClanSetting,
+    Campaign,
+}
+
+impl VarDomain {
+    /// All domains, ordered by their id.
+    pub const ALL: [Self; 9] = [
+        Self::Player,
+        Self::Npc,
+        Self::Client,
+        Self::World,
+        Self::Region,
+        Self::Object,
+        Self::Clan,
+        Self::ClanSetting,
+        Self::Campaign,
+    ];
+
+    /// Looks up a domain by the id a [`VarbitConfig`](crate::definitions::varbit_configs::VarbitConfig) refers to it with.
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    /// The [`ConfigType`] archive containing the variables of this domain.
+    pub fn archive(self) -> u32 {
+        match self {
+            Self::Player => ConfigType::PLAYER,
+            Self::Npc => ConfigType::NPC,
+            Self::Client => ConfigType::CLIENT,
+            Self::World => ConfigType::WORLD,
+            Self::Region => ConfigType::REGION,
+            Self::Object => ConfigType::OBJECT,
+            Self::Clan => ConfigType::CLAN,
+            Self::ClanSetting => ConfigType::CLAN_SETTING,
+            Self::Campaign => ConfigType::CAMPAIGN,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Player => "player",
+            Self::Npc => "npc",
+            Self::Client => "client",
+            Self::World => "world",
+            Self::Region => "region",
+            Self::Object => "object",
+            Self::Clan => "clan",
+            Self::ClanSetting => "clan_setting",
+            Self::Campaign => "campaign",
+        }
+    }
+}
+
+/// How long a variable keeps its value.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VarLifetime {
+    /// Reset when the player logs out.
+    Temporary,
+    /// Saved when the player logs out.
+    Permanent,
+    /// Saved by the server, and not sent to the client.
+    ServerPermanent,
+    /// A lifetime that is not known yet.
+    Other(u8),
+}
+
+impl VarLifetime {
+    /// Looks up a lifetime by the id the client uses for it.
+    pub fn from_id(id: u8) -> Self {
+        match id {
+            0 => Self::Temporary,
+            1 => Self::Permanent,
+            2 => Self::ServerPermanent,
+            other => Self::Other(other),
+        }
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl<'py> IntoPyObject<'py> for VarLifetime {
+    type Target = PyAny;
+    type Output = Bound<'py, Self::Target>;
+    type Error = PyErr;
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        match self {
+            Self::Temporary => Ok("temporary".into_pyobject(py)?.into_any()),
+            Self::Permanent => Ok("permanent".into_pyobject(py)?.into_any()),
+            Self::ServerPermanent => Ok("server_permanent".into_pyobject(py)?.into_any()),
+            Self::Other(id) => Ok(id.into_pyobject(py)?.into_any()),
+        }
+    }
+}
+
+/// A variable, such as a varp.
+#[cfg_attr(feature = "pyo3", pyclass(frozen, get_all, from_py_object))]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct VarConfig {
+    /// Its id.
+    pub id: u32,
+    /// The type of the variable, as the character the client uses for it.
+    pub type_char: Option<u8>,
+    /// The type of the variable.
+    pub type_id: Option<u16>,
+    /// How long the variable keeps its value.
+    pub lifetime: Option<VarLifetime>,
+    /// Which clients the variable is sent to.
+    pub transmit_level: Option<u8>,
+    /// Makes the client act on the variable when it changes.
+    pub client_code: Option<u16>,
+    /// Set by opcode 6, which has no payload. What the client does with it is not known.
+    pub unknown_6: Option<bool>,
+    /// The variable's name, if the cache has it.
+    pub debug_name: Option<JString<Bytes>>,
+}
+
+impl VarConfig {
+    /// Returns a mapping of all [`VarConfig`]s in a given domain.
+    pub fn dump_domain(domain: VarDomain, config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
+        let index = CacheIndex::new(IndexType::CONFIG, config.input.clone())?;
+        if !index.metadatas().keys().any(|&archive_id| archive_id == domain.archive()) {
+            return Ok(BTreeMap::new());
+        }
+        let vars = index
+            .archive(domain.archive())?
+            .take_files()
+            .into_iter()
+            .map(|(id, file)| Self::deserialize(id, file).map(|item| (id, item)))
+            .collect::<Result<BTreeMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "var configs" })?;
+        Ok(vars)
+    }
+
+    /// Returns a mapping of all [`VarConfig`]s, for every domain.
+    pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<VarDomain, BTreeMap<u32, Self>>> {
+        VarDomain::ALL
+            .into_iter()
+            .map(|domain| Ok((domain, Self::dump_domain(domain, config)?)))
+            .collect()
+    }
+
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut var = Self { id, ..Default::default() };
+
+        #[cfg(debug_assertions)]
+        let mut opcodes = Vec::new();
+
+        loop {
+            let opcode = buffer.try_get_u8()?;
+            let read: Result<(), ReadError> = try {
+                match opcode {
+                    0 => {
+                        if buffer.has_remaining() {
+                            return Err(NotExhausted::new(buffer));
+                        } else {
+                            break Ok(var);
+                        }
+                    }
+                    1 => var.type_char = Some(buffer.try_get_u8()?),
+                    3 => var.type_id = Some(buffer.try_get_unsigned_smart()?),
+                    4 => var.lifetime = Some(VarLifetime::from_id(buffer.try_get_u8()?)),
+                    5 => var.transmit_level = Some(buffer.try_get_u8()?),
+                    6 => var.unknown_6 = Some(true),
+                    7 => var.client_code = Some(buffer.try_get_u16()?),
+                    110 => var.debug_name = Some(buffer.try_get_string()?),
+                    opcode => {
+                        do yeet OpcodeNotImplemented::new(opcode)
+                    }
+                }
+            };
+            match read {
+                Ok(()) => {
+                    #[cfg(debug_assertions)]
+                    opcodes.push(opcode);
+                }
+                Err(e) => {
+                    return Err(Box::new(e)).context(WithInfo {
+                        #[cfg(debug_assertions)]
+                        opcodes,
+                        buffer,
+                        #[cfg(debug_assertions)]
+                        thing: var.to_string(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Display for VarConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(&self).unwrap())
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[pymethods]
+impl VarConfig {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("VarConfig({})", serde_json::to_string(self).unwrap()))
+    }
+
+    fn __str__(&self) -> PyResult<String> {
+        Ok(format!("VarConfig({})", serde_json::to_string(self).unwrap()))
+    }
+}
+
+/// Save the variables of each domain as `var_configs.json`. Exposed as `--dump var_configs`.
+pub fn export(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+    let vars = VarConfig::dump_all(config)?
+        .into_iter()
+        .map(|(domain, vars)| (domain.as_str(), vars.into_values().collect::<Vec<_>>()))
+        .collect::<BTreeMap<_, _>>();
+
+    let path = path!(config.output / "var_configs.json");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&vars).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_player_vars() -> CacheResult<()> {
+        let config = crate::cli::Config::env();
+
+        let vars = VarConfig::dump_domain(VarDomain::Player, &config)?;
+        assert!(!vars.is_empty());
+        Ok(())
+    }
+}
@@ -14,6 +14,8 @@ use bytes::{Buf, Bytes};
 use path_macro::path;
 #[cfg(feature = "pyo3")]
 use pyo3::prelude::*;
+#[cfg(feature = "rs3")]
+use rs3cache_backend::buf::JString;
 use rs3cache_backend::{
     error::{self, CacheResult},
     index::CacheIndex,
@@ -21,6 +23,8 @@ use rs3cache_backend::{
 use serde::Serialize;
 
 use crate::definitions::indextype::{ConfigType, IndexType};
+#[cfg(feature = "rs3")]
+use crate::definitions::var_configs::{VarConfig, VarDomain};
 /// A varbit configuration.
 ///
 /// The varbit is the bits of Varp `index` from `least_significant_bit` to `most_significant_bit` inclusive.
@@ -31,26 +35,48 @@ use crate::definitions::indextype::{ConfigType, IndexType};
 pub struct VarbitConfig {
     /// Id of the [`Varbit`](crate::types::variables::Varbit).
     pub id: u32,
-    pub unknown_1: u8,
+    /// The [domain](crate::definitions::var_configs::VarDomain) of the variable this varbit maps to.
+    pub domain: u8,
     /// The Varp that this varbit maps to.
     pub index: u16,
     pub least_significant_bit: u8,
     pub most_significant_bit: u8,
+    /// The name of the domain of the variable this varbit maps to.
+    #[cfg(feature = "rs3")]
+    pub domain_name: Option<&'static str>,
+    /// The name of the variable this varbit maps to, if the cache has it.
+    #[cfg(feature = "rs3")]
+    pub base_name: Option<JString<Bytes>>,
 }
 
 impl VarbitConfig {
     /// Returns a mapping of all [`VarbitConfig`]s.
     pub fn dump_all(config: &crate::cli::Config) -> CacheResult<BTreeMap<u32, Self>> {
-        Ok(CacheIndex::new(IndexType::CONFIG, config.input.clone())?
+        #[allow(unused_mut)]
+        let mut varbits = CacheIndex::new(IndexType::CONFIG, config.input.clone())?
             .archive(ConfigType::VARBITS)?
             .take_files()
             .into_iter()
             .map(|(file_id, file)| (file_id, VarbitConfig::deserialize(file_id, file)))
-            .collect())
+            .collect::<BTreeMap<u32, Self>>();
+
+        #[cfg(feature = "rs3")]
+        {
+            let mut domains = BTreeMap::<VarDomain, BTreeMap<u32, VarConfig>>::new();
+            for varbit in varbits.values_mut() {
+                let Some(domain) = VarDomain::from_id(varbit.domain) else { continue };
+                if !domains.contains_key(&domain) {
+                    domains.insert(domain, VarConfig::dump_domain(domain, config)?);
+                }
+                varbit.domain_name = Some(domain.as_str());
+                varbit.base_name = domains[&domain].get(&(varbit.index as u32)).and_then(|var| var.debug_name.clone());
+            }
+        }
+        Ok(varbits)
     }
 
     fn deserialize(id: u32, mut buffer: Bytes) -> Self {
-        let mut unknown_1 = None;
+        let mut domain = None;
         let mut index = None;
         let mut least_significant_bit = None;
         let mut most_significant_bit = None;
@@ -61,14 +87,18 @@ impl VarbitConfig {
                     assert!(!buffer.has_remaining());
                     break Self {
                         id,
-                        unknown_1: unknown_1.expect("opcode 1 was not read"),
+                        domain: domain.expect("opcode 1 was not read"),
                         index: index.expect("opcode 1 was not read"),
                         least_significant_bit: least_significant_bit.expect("opcode 2 was not read"),
                         most_significant_bit: most_significant_bit.expect("opcode 2 was not read"),
+                        #[cfg(feature = "rs3")]
+                        domain_name: None,
+                        #[cfg(feature = "rs3")]
+                        base_name: None,
                     };
                 }
                 1 => {
-                    unknown_1 = Some(buffer.get_u8());
+                    domain = Some(buffer.get_u8());
                     index = Some(buffer.get_u16());
                 }
                 2 => {
@@ -2,24 +2,36 @@ use std::{
     collections::{BTreeMap, HashMap},
     fs::{self, File},
     io::Write,
-    iter,
+    panic::Location,
 };
 
 use ::error::Context;
 use bytes::{Buf, Bytes};
 use path_macro::path;
+#[cfg(feature = "osrs")]
 use rs3cache_backend::{
-    buf::{BufExtra, JString},
+    arc::Archive,
+    index::{self, Initial},
+};
+use rs3cache_backend::{
+    buf::{BufExtra, JString, NotExhausted, ReadError},
     error::{self, CacheResult},
     index::CacheIndex,
 };
 use serde::Serialize;
 
-use crate::{definitions::indextype::IndexType, types::coordinate::Coordinate};
+use crate::{
+    definitions::indextype::IndexType,
+    types::{
+        coordinate::Coordinate,
+        geojson::{Feature, FeatureCollection, Geometry},
+    },
+};
 
 /// Enumeration of the archives in the [WORLDMAP](IndexType::WORLDMAP) index.
 pub struct WorldMapType;
 
+#[cfg(feature = "rs3")]
 impl WorldMapType {
     pub const ZONES: u32 = 0;
     pub const PASTES: u32 = 1;
@@ -30,49 +42,127 @@ impl WorldMapType {
     pub const UNKNOWN_3: u32 = 3;
     pub const BIG: u32 = 4;
 }
+
+/// On osrs, the archives are referred to by name.
+///
+/// Only [`DETAILS`](WorldMapType::DETAILS) and [`COMPOSITE_MAP`](WorldMapType::COMPOSITE_MAP) are decoded.
+/// The layout of the other archives, and of the [WORLDMAP_GEOGRAPHY](IndexType::WORLDMAP_GEOGRAPHY)
+/// and [WORLDMAP_GROUND](IndexType::WORLDMAP_GROUND) indices, is not known to this crate;
+/// [`dump_raw`] exports their files as they are.
+#[cfg(feature = "osrs")]
+impl WorldMapType {
+    /// Contains the [`MapZone`] definitions.
+    pub const DETAILS: &'static str = "details";
+    /// Contains the [`MapPastes`] of each world map, and the icons drawn on it.
+    pub const COMPOSITE_MAP: &'static str = "compositemap";
+    /// The pre-rendered images of each world map.
+    pub const COMPOSITE_TEXTURE: &'static str = "compositetexture";
+    /// The world map areas.
+    pub const AREA: &'static str = "area";
+    /// The world map labels.
+    pub const LABELS: &'static str = "labels";
+
+    /// Looks up an archive of the [WORLDMAP](IndexType::WORLDMAP) index by its name.
+    pub fn archive(index: &CacheIndex<Initial>, name: &str) -> CacheResult<Archive> {
+        let hash = rs3cache_backend::hash::hash_djb2(name);
+        let archive_id = index
+            .metadatas()
+            .iter()
+            .find(|(_, metadata)| metadata.name() == Some(hash))
+            .map(|(&archive_id, _)| archive_id)
+            .with_context(|| index::ArchiveMissingNamed {
+                index_id: IndexType::WORLDMAP,
+                name: name.to_string(),
+            })
+            .context(error::Integrity)?;
+        index.archive(archive_id)
+    }
+}
+/// Reads a packed [`Coordinate`].
+#[track_caller]
+fn coordinate(packed: u32) -> Result<Coordinate, ReadError> {
+    Coordinate::try_from(packed).map_err(|reason| ReadError::Unsupported {
+        location: Location::caller(),
+        what: "coordinate",
+        reason: format!("{reason}: {packed:#x}"),
+    })
+}
+
+/// The error for a byte that should be a boolean, but is neither 0 nor 1.
+#[track_caller]
+fn invalid_bool(what: &'static str, value: u8) -> ReadError {
+    ReadError::Unsupported {
+        location: Location::caller(),
+        what,
+        reason: format!("{value} is not a boolean"),
+    }
+}
+
 /// Describes the general properties of a map zone.
 #[derive(Debug, Serialize)]
 pub struct MapZone {
     id: u32,
     internal_name: JString<Bytes>,
     name: JString<Bytes>,
+    #[cfg(feature = "rs3")]
     center: Coordinate,
+    /// This is absent for some world maps.
+    #[cfg(feature = "osrs")]
+    center: Option<Coordinate>,
     unknown_1: u32,
+    #[cfg(feature = "rs3")]
     show: bool,
+    /// Whether this is the main world map, rather than that of a dungeon or minigame.
+    #[cfg(feature = "osrs")]
+    main: bool,
     default_zoom: u8,
     unknown_2: u8,
+    #[cfg(feature = "rs3")]
     bounds: Vec<BoundDef>,
+    #[cfg(feature = "osrs")]
+    bounds: Vec<MapSection>,
 }
 
 impl MapZone {
     /// Returns a mapping of all [`MapZone`] configurations.
     pub fn dump_all(config: &crate::cli::Config) -> CacheResult<HashMap<u32, Self>> {
-        Ok(CacheIndex::new(IndexType::WORLDMAP, config.input.clone())?
-            .archive(WorldMapType::ZONES)?
+        let index = CacheIndex::new(IndexType::WORLDMAP, config.input.clone())?;
+
+        #[cfg(feature = "rs3")]
+        let archive = index.archive(WorldMapType::ZONES)?;
+        #[cfg(feature = "osrs")]
+        let archive = WorldMapType::archive(&index, WorldMapType::DETAILS)?;
+
+        let zones = archive
             .take_files()
             .into_iter()
-            .map(|(file_id, file)| (file_id, Self::deserialize(file_id, file)))
-            .collect())
+            .map(|(file_id, file)| Self::deserialize(file_id, file).map(|zone| (file_id, zone)))
+            .collect::<Result<HashMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "map zones" })?;
+        Ok(zones)
     }
 
-    fn deserialize(id: u32, mut buffer: Bytes) -> Self {
-        let internal_name = buffer.get_string();
-        let name = buffer.get_string();
-        let center = buffer.get_u32().try_into().unwrap();
-        let unknown_1 = buffer.get_u32();
-        let show = match buffer.get_u8() {
+    #[cfg(feature = "rs3")]
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let internal_name = buffer.try_get_string()?;
+        let name = buffer.try_get_string()?;
+        let center = coordinate(buffer.try_get_u32()?)?;
+        let unknown_1 = buffer.try_get_u32()?;
+        let show = match buffer.try_get_u8()? {
             0 => false,
             1 => true,
-            other => unimplemented!("Cannot convert value {} for 'show' to boolean", other),
+            other => return Err(invalid_bool("show", other)),
         };
-        let default_zoom = buffer.get_u8();
-        let unknown_2 = buffer.get_u8();
-        let count = buffer.get_u8() as usize;
-        let bounds = iter::repeat_with(|| BoundDef::deserialize(&mut buffer)).take(count).collect();
+        let default_zoom = buffer.try_get_u8()?;
+        let unknown_2 = buffer.try_get_u8()?;
+        let count = buffer.try_get_u8()? as usize;
+        let bounds = (0..count).map(|_| BoundDef::deserialize(&mut buffer)).collect::<Result<_, _>>()?;
 
-        debug_assert_eq!(buffer.remaining(), 0);
+        if buffer.has_remaining() {
+            return Err(NotExhausted::new(buffer));
+        }
 
-        Self {
+        Ok(Self {
             id,
             internal_name,
             name,
@@ -82,7 +172,40 @@ impl MapZone {
             default_zoom,
             unknown_2,
             bounds,
+        })
+    }
+
+    #[cfg(feature = "osrs")]
+    fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let internal_name = buffer.try_get_string()?;
+        let name = buffer.try_get_string()?;
+        let center = buffer.try_get_u32()?.try_into().ok();
+        let unknown_1 = buffer.try_get_u32()?;
+        let unknown_2 = buffer.try_get_u8()?;
+        let main = match buffer.try_get_u8()? {
+            0 => false,
+            1 => true,
+            other => return Err(invalid_bool("main", other)),
+        };
+        let default_zoom = buffer.try_get_u8()?;
+        let count = buffer.try_get_u8()? as usize;
+        let bounds = (0..count).map(|_| MapSection::deserialize(&mut buffer)).collect::<Result<_, _>>()?;
+
+        if buffer.has_remaining() {
+            return Err(NotExhausted::new(buffer));
         }
+
+        Ok(Self {
+            id,
+            internal_name,
+            name,
+            center,
+            unknown_1,
+            main,
+            default_zoom,
+            unknown_2,
+            bounds,
+        })
     }
 
     /// Get a reference to the map zone's internal name.
@@ -96,15 +219,29 @@ impl MapZone {
     }
 
     /// Get the map zone's center coordinate.
+    #[cfg(feature = "rs3")]
     pub const fn center(&self) -> Coordinate {
         self.center
     }
 
+    /// Get the map zone's center coordinate, if it has one.
+    #[cfg(feature = "osrs")]
+    pub const fn center(&self) -> Option<Coordinate> {
+        self.center
+    }
+
     /// Whether the mapzone is shown.
+    #[cfg(feature = "rs3")]
     pub const fn show(&self) -> bool {
         self.show
     }
 
+    /// Whether this is the main world map.
+    #[cfg(feature = "osrs")]
+    pub const fn main(&self) -> bool {
+        self.main
+    }
+
     /// Get a reference to the map zone's unknown 1.
     pub const fn unknown_1(&self) -> u32 {
         self.unknown_1
@@ -121,29 +258,68 @@ impl MapZone {
     }
 
     /// Get a reference to the map zone's bounds.
+    #[cfg(feature = "rs3")]
     pub fn bounds(&self) -> &[BoundDef] {
         self.bounds.as_slice()
     }
+
+    /// Get a reference to the map zone's bounds.
+    #[cfg(feature = "osrs")]
+    pub fn bounds(&self) -> &[MapSection] {
+        self.bounds.as_slice()
+    }
+
+    /// Creates a GeoJSON feature for each of the map zone's bounds,
+    /// covering the part of the game map it shows.
+    pub fn to_features(&self) -> Vec<Feature> {
+        self.bounds
+            .iter()
+            .map(|bound| {
+                #[cfg(feature = "rs3")]
+                let (plane, [west, south, east, north]) = {
+                    let Bound { west, south, east, north } = bound.src;
+                    (bound.plane, [west, south, east, north].map(u32::from))
+                };
+                #[cfg(feature = "osrs")]
+                let (plane, [west, south, east, north]) = (bound.plane(), bound.src_tiles());
+
+                let mut properties = serde_json::Map::new();
+                properties.insert("id".into(), self.id.into());
+                properties.insert("name".into(), self.name().into());
+                properties.insert("internal_name".into(), self.internal_name().into());
+                properties.insert("plane".into(), plane.into());
+                Feature {
+                    geometry: Geometry::rectangle(west, south, east, north),
+                    properties,
+                }
+            })
+            .collect()
+    }
 }
 
 mod mapzone_fields_impl {
+    use std::panic::Location;
 
-    use bytes::{Buf, Bytes};
+    use bytes::Bytes;
+    use rs3cache_backend::buf::{BufExtra, ReadError};
     use serde::Serialize;
 
+    #[cfg(feature = "osrs")]
+    use super::Chunk;
+
     #[derive(Debug, Serialize)]
     pub struct BoundDef {
-        plane: u8,
-        src: Bound,
-        dst: Bound,
+        pub plane: u8,
+        pub src: Bound,
+        pub dst: Bound,
     }
 
     impl BoundDef {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let plane = buffer.get_u8();
-            let src = Bound::deserialize(buffer);
-            let dst = Bound::deserialize(buffer);
-            Self { plane, src, dst }
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let plane = buffer.try_get_u8()?;
+            let src = Bound::deserialize(buffer)?;
+            let dst = Bound::deserialize(buffer)?;
+            Ok(Self { plane, src, dst })
         }
     }
 
@@ -157,13 +333,184 @@ mod mapzone_fields_impl {
     }
 
     impl Bound {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let west = buffer.get_u16();
-            let south = buffer.get_u16();
-            let east = buffer.get_u16();
-            let north = buffer.get_u16();
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let west = buffer.try_get_u16()?;
+            let south = buffer.try_get_u16()?;
+            let east = buffer.try_get_u16()?;
+            let north = buffer.try_get_u16()?;
+
+            Ok(Self { west, south, east, north })
+        }
+    }
 
-            Self { west, south, east, north }
+    /// Describes which part of the game map is shown where on an osrs world map.
+    #[cfg(feature = "osrs")]
+    #[derive(Debug, Serialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    pub enum MapSection {
+        /// A rectangle of mapsquares.
+        Squares { plane: u8, n_planes: u8, src: Bound, dst: Bound },
+        /// A single chunk.
+        Chunk {
+            plane: u8,
+            n_planes: u8,
+            src_i: u16,
+            src_j: u16,
+            src_chunk: Chunk,
+            dst_i: u16,
+            dst_j: u16,
+            dst_chunk: Chunk,
+        },
+        /// A rectangle of chunks within a mapsquare.
+        Chunks {
+            plane: u8,
+            n_planes: u8,
+            src_i: u16,
+            src_j: u16,
+            src_chunks: [Chunk; 2],
+            dst_i: u16,
+            dst_j: u16,
+            dst_chunks: [Chunk; 2],
+        },
+        /// A single mapsquare.
+        Square {
+            plane: u8,
+            n_planes: u8,
+            src_i: u16,
+            src_j: u16,
+            dst_i: u16,
+            dst_j: u16,
+        },
+    }
+
+    #[cfg(feature = "osrs")]
+    impl MapSection {
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let section = match buffer.try_get_u8()? {
+                0 => {
+                    let plane = buffer.try_get_u8()?;
+                    let n_planes = buffer.try_get_u8()?;
+                    let src = Bound::deserialize(buffer)?;
+                    let dst = Bound::deserialize(buffer)?;
+                    Self::Squares { plane, n_planes, src, dst }
+                }
+                1 => {
+                    let plane = buffer.try_get_u8()?;
+                    let n_planes = buffer.try_get_u8()?;
+                    let src_i = buffer.try_get_u16()?;
+                    let src_x = buffer.try_get_u8()?;
+                    let src_j = buffer.try_get_u16()?;
+                    let src_y = buffer.try_get_u8()?;
+                    let dst_i = buffer.try_get_u16()?;
+                    let dst_x = buffer.try_get_u8()?;
+                    let dst_j = buffer.try_get_u16()?;
+                    let dst_y = buffer.try_get_u8()?;
+                    Self::Chunk {
+                        plane,
+                        n_planes,
+                        src_i,
+                        src_j,
+                        src_chunk: Chunk { x: src_x, y: src_y },
+                        dst_i,
+                        dst_j,
+                        dst_chunk: Chunk { x: dst_x, y: dst_y },
+                    }
+                }
+                2 => {
+                    let plane = buffer.try_get_u8()?;
+                    let n_planes = buffer.try_get_u8()?;
+                    let src_i = buffer.try_get_u16()?;
+                    let [src_x_low, src_x_high] = [buffer.try_get_u8()?, buffer.try_get_u8()?];
+                    let src_j = buffer.try_get_u16()?;
+                    let [src_y_low, src_y_high] = [buffer.try_get_u8()?, buffer.try_get_u8()?];
+                    let dst_i = buffer.try_get_u16()?;
+                    let [dst_x_low, dst_x_high] = [buffer.try_get_u8()?, buffer.try_get_u8()?];
+                    let dst_j = buffer.try_get_u16()?;
+                    let [dst_y_low, dst_y_high] = [buffer.try_get_u8()?, buffer.try_get_u8()?];
+                    Self::Chunks {
+                        plane,
+                        n_planes,
+                        src_i,
+                        src_j,
+                        src_chunks: [
+                            Chunk { x: src_x_low, y: src_y_low },
+                            Chunk {
+                                x: src_x_high,
+                                y: src_y_high,
+                            },
+                        ],
+                        dst_i,
+                        dst_j,
+                        dst_chunks: [
+                            Chunk { x: dst_x_low, y: dst_y_low },
+                            Chunk {
+                                x: dst_x_high,
+                                y: dst_y_high,
+                            },
+                        ],
+                    }
+                }
+                3 => {
+                    let plane = buffer.try_get_u8()?;
+                    let n_planes = buffer.try_get_u8()?;
+                    let src_i = buffer.try_get_u16()?;
+                    let src_j = buffer.try_get_u16()?;
+                    let dst_i = buffer.try_get_u16()?;
+                    let dst_j = buffer.try_get_u16()?;
+                    Self::Square {
+                        plane,
+                        n_planes,
+                        src_i,
+                        src_j,
+                        dst_i,
+                        dst_j,
+                    }
+                }
+                other => {
+                    return Err(ReadError::Unsupported {
+                        location: Location::caller(),
+                        what: "map section",
+                        reason: format!("type {other}"),
+                    })
+                }
+            };
+            Ok(section)
+        }
+
+        /// The lowest plane of the game map this section shows.
+        pub fn plane(&self) -> u8 {
+            match self {
+                Self::Squares { plane, .. } | Self::Chunk { plane, .. } | Self::Chunks { plane, .. } | Self::Square { plane, .. } => *plane,
+            }
+        }
+
+        /// The tiles of the game map this section shows, as `[west, south, east, north]` (inclusive).
+        pub fn src_tiles(&self) -> [u32; 4] {
+            match self {
+                Self::Squares { src, .. } => {
+                    let [west, south, east, north] = [src.west, src.south, src.east, src.north].map(|n| n as u32 * 64);
+                    [west, south, east + 63, north + 63]
+                }
+                Self::Chunk { src_i, src_j, src_chunk, .. } => {
+                    let (west, south) = (*src_i as u32 * 64 + src_chunk.x as u32 * 8, *src_j as u32 * 64 + src_chunk.y as u32 * 8);
+                    [west, south, west + 7, south + 7]
+                }
+                Self::Chunks {
+                    src_i,
+                    src_j,
+                    src_chunks: [low, high],
+                    ..
+                } => [
+                    *src_i as u32 * 64 + low.x as u32 * 8,
+                    *src_j as u32 * 64 + low.y as u32 * 8,
+                    *src_i as u32 * 64 + high.x as u32 * 8 + 7,
+                    *src_j as u32 * 64 + high.y as u32 * 8 + 7,
+                ],
+                Self::Square { src_i, src_j, .. } => {
+                    let (west, south) = (*src_i as u32 * 64, *src_j as u32 * 64);
+                    [west, south, west + 63, south + 63]
+                }
+            }
         }
     }
 }
@@ -176,49 +523,98 @@ pub struct MapPastes {
     ///The map id.
     pub id: u32,
     /// The horizontal dimension of the world map.
+    #[cfg(feature = "rs3")]
     pub dim_i: u8,
     /// The vertical dimension of the world map.
+    #[cfg(feature = "rs3")]
     pub dim_j: u8,
 
     /// The [`Paste`]s making up the world map.
     pub pastes: Vec<Paste>,
+
+    /// The icons drawn on the world map.
+    #[cfg(feature = "osrs")]
+    pub icons: Vec<MapIcon>,
 }
 
 impl MapPastes {
     /// Returns a mapping of all [`MapPastes`].
+    ///
+    /// On osrs, these are keyed by their file id in the [`COMPOSITE_MAP`](WorldMapType::COMPOSITE_MAP) archive.
     pub fn dump_all(config: &crate::cli::Config) -> CacheResult<HashMap<u32, Self>> {
-        Ok(CacheIndex::new(IndexType::WORLDMAP, config.input.clone())?
-            .archive(WorldMapType::PASTES)?
+        let index = CacheIndex::new(IndexType::WORLDMAP, config.input.clone())?;
+
+        #[cfg(feature = "rs3")]
+        let archive = index.archive(WorldMapType::PASTES)?;
+        #[cfg(feature = "osrs")]
+        let archive = WorldMapType::archive(&index, WorldMapType::COMPOSITE_MAP)?;
+
+        let pastes = archive
             .take_files()
             .into_iter()
-            .map(|(file_id, file)| (file_id, Self::deserialize(file_id, file)))
-            .collect())
+            .map(|(file_id, file)| Self::deserialize(file_id, file).map(|pastes| (file_id, pastes)))
+            .collect::<Result<HashMap<u32, Self>, ReadError>>()
+            .context(error::Read { what: "map pastes" })?;
+        Ok(pastes)
     }
 
     /// Constructor for [`MapPastes`].
-    pub fn deserialize(id: u32, mut buffer: Bytes) -> Self {
+    #[cfg(feature = "rs3")]
+    pub fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
         let mut pastes = Vec::new();
 
-        let square_count = buffer.get_u16() as usize;
-        let square_pastes = iter::repeat_with(|| Paste::deserialize_square(&mut buffer)).take(square_count);
-        pastes.extend(square_pastes);
+        let square_count = buffer.try_get_u16()? as usize;
+        for _ in 0..square_count {
+            pastes.push(Paste::deserialize_square(&mut buffer)?);
+        }
+
+        let chunk_count = buffer.try_get_u16()? as usize;
+        for _ in 0..chunk_count {
+            pastes.push(Paste::deserialize_chunk(&mut buffer)?);
+        }
+        let dim_i = buffer.try_get_u8()?;
+        let dim_j = buffer.try_get_u8()?;
+        if buffer.has_remaining() {
+            return Err(NotExhausted::new(buffer));
+        }
+
+        Ok(Self { id, dim_i, dim_j, pastes })
+    }
 
-        let chunk_count = buffer.get_u16() as usize;
-        let chunk_pastes = iter::repeat_with(|| Paste::deserialize_chunk(&mut buffer)).take(chunk_count);
-        pastes.extend(chunk_pastes);
-        let dim_i = buffer.get_u8();
-        let dim_j = buffer.get_u8();
-        debug_assert_eq!(buffer.remaining(), 0);
+    /// Constructor for [`MapPastes`].
+    #[cfg(feature = "osrs")]
+    pub fn deserialize(id: u32, mut buffer: Bytes) -> Result<Self, ReadError> {
+        let mut pastes = Vec::new();
+
+        let square_count = buffer.try_get_u16()? as usize;
+        for _ in 0..square_count {
+            pastes.push(Paste::deserialize_square(&mut buffer)?);
+        }
+
+        let chunk_count = buffer.try_get_u16()? as usize;
+        for _ in 0..chunk_count {
+            pastes.push(Paste::deserialize_chunk(&mut buffer)?);
+        }
+
+        let icon_count = buffer.try_get_u16()? as usize;
+        let icons = (0..icon_count).map(|_| MapIcon::deserialize(&mut buffer)).collect::<Result<_, _>>()?;
+        if buffer.has_remaining() {
+            return Err(NotExhausted::new(buffer));
+        }
 
-        Self { id, dim_i, dim_j, pastes }
+        Ok(Self { id, pastes, icons })
     }
 }
 
 mod mappaste_fields_impl {
 
-    use bytes::{Buf, Bytes};
+    use bytes::Bytes;
+    use rs3cache_backend::buf::{BufExtra, ReadError};
     use serde::Serialize;
 
+    #[cfg(feature = "osrs")]
+    use crate::types::coordinate::Coordinate;
+
     #[derive(Debug, Serialize)]
     pub struct Paste {
         pub src_plane: u8,
@@ -232,20 +628,28 @@ mod mappaste_fields_impl {
         pub dst_j: u16,
 
         pub dst_chunk: Option<Chunk>,
+
+        /// The archive of the [WORLDMAP_GROUND](crate::definitions::indextype::IndexType::WORLDMAP_GROUND) index
+        /// that contains the terrain of this paste.
+        #[cfg(feature = "osrs")]
+        pub group_id: Option<u32>,
+        #[cfg(feature = "osrs")]
+        pub file_id: Option<u32>,
     }
 
     impl Paste {
-        pub fn deserialize_square(buffer: &mut Bytes) -> Self {
-            let src_plane = buffer.get_u8();
-            let n_planes = buffer.get_u8();
-            let src_i = buffer.get_u16();
-            let src_j = buffer.get_u16();
-
-            let dst_plane = buffer.get_u8();
-            let dst_i = buffer.get_u16();
-            let dst_j = buffer.get_u16();
-
-            Self {
+        #[cfg(feature = "rs3")]
+        pub fn deserialize_square(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let src_plane = buffer.try_get_u8()?;
+            let n_planes = buffer.try_get_u8()?;
+            let src_i = buffer.try_get_u16()?;
+            let src_j = buffer.try_get_u16()?;
+
+            let dst_plane = buffer.try_get_u8()?;
+            let dst_i = buffer.try_get_u16()?;
+            let dst_j = buffer.try_get_u16()?;
+
+            Ok(Self {
                 src_plane,
                 n_planes,
                 src_i,
@@ -257,22 +661,23 @@ mod mappaste_fields_impl {
                 dst_j,
 
                 dst_chunk: None,
-            }
+            })
         }
 
-        pub fn deserialize_chunk(buffer: &mut Bytes) -> Self {
-            let src_plane = buffer.get_u8();
-            let n_planes = buffer.get_u8();
-            let src_i = buffer.get_u16();
-            let src_j = buffer.get_u16();
-            let src_chunk = Chunk::deserialize(buffer);
+        #[cfg(feature = "rs3")]
+        pub fn deserialize_chunk(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let src_plane = buffer.try_get_u8()?;
+            let n_planes = buffer.try_get_u8()?;
+            let src_i = buffer.try_get_u16()?;
+            let src_j = buffer.try_get_u16()?;
+            let src_chunk = Chunk::deserialize(buffer)?;
 
-            let dst_plane = buffer.get_u8();
-            let dst_i = buffer.get_u16();
-            let dst_j = buffer.get_u16();
-            let dst_chunk = Chunk::deserialize(buffer);
+            let dst_plane = buffer.try_get_u8()?;
+            let dst_i = buffer.try_get_u16()?;
+            let dst_j = buffer.try_get_u16()?;
+            let dst_chunk = Chunk::deserialize(buffer)?;
 
-            Self {
+            Ok(Self {
                 src_plane,
                 n_planes,
                 src_i,
@@ -284,7 +689,92 @@ mod mappaste_fields_impl {
                 dst_j,
 
                 dst_chunk: Some(dst_chunk),
-            }
+            })
+        }
+
+        /// On osrs, the destination comes first and pastes do not change planes.
+        #[cfg(feature = "osrs")]
+        pub fn deserialize_square(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let src_plane = buffer.try_get_u8()?;
+            let n_planes = buffer.try_get_u8()?;
+            let dst_i = buffer.try_get_u16()?;
+            let dst_j = buffer.try_get_u16()?;
+            let src_i = buffer.try_get_u16()?;
+            let src_j = buffer.try_get_u16()?;
+            let group_id = buffer.try_get_smart32()?;
+            let file_id = buffer.try_get_smart32()?;
+
+            Ok(Self {
+                src_plane,
+                n_planes,
+                src_i,
+                src_j,
+                src_chunk: None,
+
+                dst_plane: src_plane,
+                dst_i,
+                dst_j,
+
+                dst_chunk: None,
+
+                group_id,
+                file_id,
+            })
+        }
+
+        #[cfg(feature = "osrs")]
+        pub fn deserialize_chunk(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let src_plane = buffer.try_get_u8()?;
+            let n_planes = buffer.try_get_u8()?;
+            let dst_i = buffer.try_get_u16()?;
+            let dst_j = buffer.try_get_u16()?;
+            let dst_chunk = Chunk::deserialize(buffer)?;
+            let src_i = buffer.try_get_u16()?;
+            let src_j = buffer.try_get_u16()?;
+            let src_chunk = Chunk::deserialize(buffer)?;
+            let group_id = buffer.try_get_smart32()?;
+            let file_id = buffer.try_get_smart32()?;
+
+            Ok(Self {
+                src_plane,
+                n_planes,
+                src_i,
+                src_j,
+                src_chunk: Some(src_chunk),
+
+                dst_plane: src_plane,
+                dst_i,
+                dst_j,
+
+                dst_chunk: Some(dst_chunk),
+
+                group_id,
+                file_id,
+            })
+        }
+    }
+
+    /// An icon drawn on an osrs world map.
+    #[cfg(feature = "osrs")]
+    #[derive(Debug, Serialize)]
+    pub struct MapIcon {
+        /// The [`MapLabelConfig`](crate::definitions::maplabel_configs::MapLabelConfig) of this icon.
+        pub element: Option<u32>,
+        pub coordinate: Coordinate,
+        pub members: bool,
+    }
+
+    #[cfg(feature = "osrs")]
+    impl MapIcon {
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let element = buffer.try_get_smart32()?;
+            let coordinate = super::coordinate(buffer.try_get_u32()?)?;
+            let members = buffer.try_get_u8()? == 1;
+            Ok(Self {
+                element,
+                coordinate,
+                members,
+            })
         }
     }
 
@@ -295,10 +785,10 @@ mod mappaste_fields_impl {
     }
 
     impl Chunk {
-        pub fn deserialize(buffer: &mut Bytes) -> Self {
-            let x = buffer.get_u8();
-            let y = buffer.get_u8();
-            Self { x, y }
+        pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+            let x = buffer.try_get_u8()?;
+            let y = buffer.try_get_u8()?;
+            Ok(Self { x, y })
         }
     }
 }
@@ -330,7 +820,43 @@ pub fn export_zones(config: &crate::cli::Config) -> CacheResult<()> {
     Ok(())
 }
 
+/// Exports all world map zones to `out/map_zones.geojson`.
+pub fn export_zones_geojson(config: &crate::cli::Config) -> CacheResult<()> {
+    fs::create_dir_all(&config.output).with_context(|| error::Io { path: config.output.clone() })?;
+
+    let mut map_zones = MapZone::dump_all(config)?.into_values().collect::<Vec<_>>();
+    map_zones.sort_unstable_by_key(|loc| loc.id);
+    let features = map_zones.iter().flat_map(MapZone::to_features).collect();
+
+    let path = path!(config.output / "map_zones.geojson");
+    let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+    let data = serde_json::to_string_pretty(&FeatureCollection { features }).unwrap();
+    file.write_all(data.as_bytes()).context(error::Io { path })?;
+    Ok(())
+}
+
+/// Exports the files of the world map archives that are not decoded to `out/world_map_{name}`,
+/// such as `out/world_map_area/{id}.dat`.
+#[cfg(feature = "osrs")]
+pub fn dump_raw(config: &crate::cli::Config) -> CacheResult<()> {
+    let index = CacheIndex::new(IndexType::WORLDMAP, config.input.clone())?;
+
+    for name in [WorldMapType::COMPOSITE_TEXTURE, WorldMapType::AREA, WorldMapType::LABELS] {
+        let folder = path!(config.output / format!("world_map_{name}"));
+        fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
+
+        for (id, data) in WorldMapType::archive(&index, name)?.take_files() {
+            let path = path!(folder / format!("{id}.dat"));
+            let mut file = File::create(&path).with_context(|| error::Io { path: path.clone() })?;
+            file.write_all(&data).context(error::Io { path })?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Exports small images of world maps to `out/world_map_small`.
+#[cfg(feature = "rs3")]
 pub fn dump_small(config: &crate::cli::Config) -> CacheResult<()> {
     let folder = path!(config.output / "world_map_small");
     fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
@@ -348,6 +874,7 @@ pub fn dump_small(config: &crate::cli::Config) -> CacheResult<()> {
 }
 
 /// Exports big images of world maps to `out/world_map_big`.
+#[cfg(feature = "rs3")]
 pub fn dump_big(config: &crate::cli::Config) -> CacheResult<()> {
     let folder = path!(config.output / "world_map_big");
     fs::create_dir_all(&folder).with_context(|| error::Io { path: folder.clone() })?;
@@ -366,3 +893,77 @@ pub fn dump_big(config: &crate::cli::Config) -> CacheResult<()> {
 
     Ok(())
 }
+
+#[cfg(all(test, any(feature = "rs3", feature = "osrs")))]
+mod tests {
+    use super::*;
+    use crate::cli::Config;
+
+    #[test]
+    fn dump_zones() -> CacheResult<()> {
+        let config = Config::env();
+
+        let zones = MapZone::dump_all(&config)?;
+        assert!(!zones.is_empty());
+        assert!(zones.values().all(|zone| !zone.bounds().is_empty()));
+        Ok(())
+    }
+
+    #[test]
+    fn dump_pastes() -> CacheResult<()> {
+        let config = Config::env();
+
+        let pastes = MapPastes::dump_all(&config)?;
+        assert!(!pastes.is_empty());
+        assert!(pastes.values().any(|map| !map.pastes.is_empty()));
+        Ok(())
+    }
+
+    #[cfg(feature = "osrs")]
+    #[test]
+    fn section_src_tiles() {
+        let bound = |west, south, east, north| Bound { west, south, east, north };
+
+        let squares = MapSection::Squares {
+            plane: 0,
+            n_planes: 4,
+            src: bound(50, 50, 51, 52),
+            dst: bound(0, 0, 1, 2),
+        };
+        assert_eq!(squares.src_tiles(), [3200, 3200, 3327, 3391]);
+
+        let chunk = MapSection::Chunk {
+            plane: 0,
+            n_planes: 1,
+            src_i: 50,
+            src_j: 50,
+            src_chunk: Chunk { x: 2, y: 3 },
+            dst_i: 0,
+            dst_j: 0,
+            dst_chunk: Chunk { x: 0, y: 0 },
+        };
+        assert_eq!(chunk.src_tiles(), [3216, 3224, 3223, 3231]);
+
+        let chunks = MapSection::Chunks {
+            plane: 0,
+            n_planes: 1,
+            src_i: 50,
+            src_j: 50,
+            src_chunks: [Chunk { x: 1, y: 2 }, Chunk { x: 3, y: 5 }],
+            dst_i: 0,
+            dst_j: 0,
+            dst_chunks: [Chunk { x: 0, y: 0 }, Chunk { x: 2, y: 3 }],
+        };
+        assert_eq!(chunks.src_tiles(), [3208, 3216, 3231, 3247]);
+
+        let square = MapSection::Square {
+            plane: 0,
+            n_planes: 1,
+            src_i: 50,
+            src_j: 50,
+            dst_i: 0,
+            dst_j: 0,
+        };
+        assert_eq!(square.src_tiles(), [3200, 3200, 3263, 3263]);
+    }
+}
@@ -17,6 +17,14 @@
 //! get_location_configs()
 //! get_npc_configs()
 //! get_item_configs()
+//! get_spotanim_configs()
+//! get_identity_kits()
+//! get_inventories()
+//! get_cursors()
+//! get_area_configs()
+//! get_skyboxes()
+//! get_light_configs()
+//! get_params()
 //! get_varbit_configs()
 //! get_struct_configs()
 //! get_enum_configs()
@@ -44,7 +52,11 @@ use rs3cache_backend::{error::py_error_impl::*, path::CachePath};
 pub use sprites::*;
 
 #[cfg(feature = "rs3")]
-use crate::definitions::achievements::Achievement;
+use crate::definitions::{
+    achievements::Achievement, area_configs::AreaConfig, cursors::Cursor, light_configs::LightConfig, skyboxes::Skybox,
+};
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::definitions::{identity_kits::IdentityKit, inventories::Inventory, params::ParamConfig, spotanim_configs::SpotAnimConfig};
 use crate::{
     cli::Config,
     definitions::{
@@ -59,6 +71,22 @@ pub fn initializer<'py>(py: Python<'py>, m: &Bound<'py, PyModule>) -> PyResult<(
     m.add_function(wrap_pyfunction!(get_location_configs, m)?)?;
     m.add_function(wrap_pyfunction!(get_npc_configs, m)?)?;
     m.add_function(wrap_pyfunction!(get_item_configs, m)?)?;
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    m.add_function(wrap_pyfunction!(get_spotanim_configs, m)?)?;
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    m.add_function(wrap_pyfunction!(get_identity_kits, m)?)?;
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    m.add_function(wrap_pyfunction!(get_inventories, m)?)?;
+    #[cfg(feature = "rs3")]
+    m.add_function(wrap_pyfunction!(get_cursors, m)?)?;
+    #[cfg(feature = "rs3")]
+    m.add_function(wrap_pyfunction!(get_area_configs, m)?)?;
+    #[cfg(feature = "rs3")]
+    m.add_function(wrap_pyfunction!(get_skyboxes, m)?)?;
+    #[cfg(feature = "rs3")]
+    m.add_function(wrap_pyfunction!(get_light_configs, m)?)?;
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    m.add_function(wrap_pyfunction!(get_params, m)?)?;
     m.add_function(wrap_pyfunction!(get_varbit_configs, m)?)?;
     m.add_function(wrap_pyfunction!(get_struct_configs, m)?)?;
     m.add_function(wrap_pyfunction!(get_enum_configs, m)?)?;
@@ -119,6 +147,94 @@ pub fn get_item_configs(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, ItemCon
     Ok(ItemConfig::dump_all(&config)?)
 }
 
+/// Wrapper for [`SpotAnimConfig::dump_all`]
+#[pyfunction]
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+pub fn get_spotanim_configs(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, SpotAnimConfig>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(SpotAnimConfig::dump_all(&config)?)
+}
+
+/// Wrapper for [`IdentityKit::dump_all`]
+#[pyfunction]
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+pub fn get_identity_kits(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, IdentityKit>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(IdentityKit::dump_all(&config)?)
+}
+
+/// Wrapper for [`Inventory::dump_all`]
+#[pyfunction]
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+pub fn get_inventories(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, Inventory>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(Inventory::dump_all(&config)?)
+}
+
+/// Wrapper for [`Cursor::dump_all`]
+#[pyfunction]
+#[cfg(feature = "rs3")]
+pub fn get_cursors(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, Cursor>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(Cursor::dump_all(&config)?)
+}
+
+/// Wrapper for [`AreaConfig::dump_all`]
+#[pyfunction]
+#[cfg(feature = "rs3")]
+pub fn get_area_configs(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, AreaConfig>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(AreaConfig::dump_all(&config)?)
+}
+
+/// Wrapper for [`Skybox::dump_all`]
+#[pyfunction]
+#[cfg(feature = "rs3")]
+pub fn get_skyboxes(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, Skybox>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(Skybox::dump_all(&config)?)
+}
+
+/// Wrapper for [`LightConfig::dump_all`]
+#[pyfunction]
+#[cfg(feature = "rs3")]
+pub fn get_light_configs(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, LightConfig>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(LightConfig::dump_all(&config)?)
+}
+
+/// Wrapper for [`ParamConfig::dump_all`]
+#[pyfunction]
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+pub fn get_params(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, ParamConfig>> {
+    let mut config = Config::env();
+    if let Some(path) = path {
+        config.input = CachePath::Argument(path.into())
+    }
+    Ok(ParamConfig::dump_all(&config)?)
+}
+
 /// Wrapper for [`Struct::dump_all`]
 #[pyfunction]
 pub fn get_struct_configs(path: Option<PathBuf>) -> PyResult<BTreeMap<u32, Struct>> {
@@ -6,6 +6,8 @@ use pyo3::{
     prelude::*,
     types::{PyInt, PyList},
 };
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use pyo3::types::PyBytes;
 use rs3cache_backend::{error, index, path::CachePath};
 
 use crate::{
@@ -191,10 +193,50 @@ impl PyMapSquare {
                 file: crate::definitions::indextype::MapFileType::WATER_LOCATIONS,
             })
             .context(error::Integrity)?;
-        let water_locations = water_locations.as_ref()?;
+        let water_locations = water_locations?;
         PyList::new(py, water_locations.iter().copied())
     }
 
+    /// The [`Npc`](crate::definitions::npcs::Npc)s in a mapsquare.
+    #[cfg(feature = "rs3")]
+    pub fn npcs<'gil>(&self, py: Python<'gil>) -> PyResult<Bound<'gil, PyList>> {
+        let npcs = self
+            .inner
+            .npcs()
+            .context(index::FileMissing {
+                index_id: 5,
+                archive_id: (self.i() as u32) | (self.j() as u32) << 7,
+                file: crate::definitions::indextype::MapFileType::NPCS,
+            })
+            .context(error::Integrity)?;
+        let npcs = npcs.as_ref()?;
+        PyList::new(py, npcs.iter().copied())
+    }
+
+    /// The underwater [`Tile`]s in a mapsquare.
+    #[cfg(feature = "rs3")]
+    pub fn water_tiles(&self) -> PyResult<BTreeMap<(u8, u8, u8), Tile>> {
+        let water_tiles = self.inner.water_tiles().ok_or_else(|| PyKeyError::new_err("not present"))?;
+        let water_tiles = water_tiles.as_ref()?;
+        let map: BTreeMap<(u8, u8, u8), Tile> = water_tiles
+            .indexed_iter()
+            .map(|((p, x, y), &t)| ((p as u8, x as u8, y as u8), t))
+            .collect();
+        Ok(map)
+    }
+
+    /// The raw contents of a file in a mapsquare that is not decoded, if present.
+    #[cfg(feature = "rs3")]
+    pub fn unknown_file<'gil>(&self, py: Python<'gil>, file: u32) -> Option<Bound<'gil, PyBytes>> {
+        self.inner.unknown_file(file).map(|bytes| PyBytes::new(py, bytes))
+    }
+
+    /// The raw environment file of a mapsquare, if present.
+    #[cfg(all(feature = "osrs", not(feature = "2013_4_shim")))]
+    pub fn environment<'gil>(&self, py: Python<'gil>) -> Option<Bound<'gil, PyBytes>> {
+        self.inner.environment().map(|bytes| PyBytes::new(py, bytes))
+    }
+
     /// The [`Tile`]s in a mapsquare.   
     pub fn tiles(&self) -> PyResult<BTreeMap<(u8, u8, u8), Tile>> {
         let tiles = self.inner.tiles().ok_or_else(|| PyKeyError::new_err("not present"))?;
@@ -26,6 +26,9 @@ pub mod cli;
 #[cfg(any(feature = "rs3", feature = "osrs", feature = "legacy"))]
 pub mod types {
     pub mod coordinate;
+    /// GeoJSON features, for map viewers.
+    pub mod geojson;
+    pub mod typed_value;
     /// Player variables
     pub mod variables;
 }
@@ -37,11 +40,51 @@ pub mod definitions {
     #[cfg(feature = "rs3")]
     pub mod achievements;
 
+    /// Areas of the old world map.
+    #[cfg(feature = "rs3")]
+    pub mod area_configs;
+
+    /// Camera facing textured quads.
+    #[cfg(feature = "rs3")]
+    pub mod billboards;
+
+    /// Mouse cursors.
+    #[cfg(feature = "rs3")]
+    pub mod cursors;
+
     pub mod dbrows;
 
+    /// Client-wide default settings.
+    #[cfg(feature = "rs3")]
+    pub mod defaults;
+
     #[cfg(feature = "legacy")]
     pub mod flo;
 
+    /// Fonts, and drawing text with them.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub mod fonts;
+
+    /// Health bars and other bars shown above entities.
+    #[cfg(feature = "rs3")]
+    pub mod headbars;
+
+    /// Markers showing damage and healing.
+    #[cfg(feature = "rs3")]
+    pub mod hitsplats;
+
+    /// Parts of a player's appearance.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub mod identity_kits;
+
+    /// Inventories, such as banks and shops.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub mod inventories;
+
+    /// Flickering of light sources.
+    #[cfg(feature = "rs3")]
+    pub mod light_configs;
+
     /// Configuration of game locations.
     pub mod location_configs;
 
@@ -60,6 +103,10 @@ pub mod definitions {
 
     pub mod maplabel_configs;
 
+    /// Describes how surfaces are shaded.
+    #[cfg(feature = "rs3")]
+    pub mod materials;
+
     /// Configuration of images drawn on the world map.
     /// Describes text, sprites and polygons drawn on the map.
     #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
@@ -73,12 +120,41 @@ pub mod definitions {
     /// Describes the colours of tiles.
     #[cfg(any(feature = "rs3", feature = "osrs"))]
     pub mod overlays;
+
+    /// Describes the keys of param tables.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub mod params;
+
+    /// Particle emitters and effectors.
+    #[cfg(feature = "rs3")]
+    pub mod particles;
+
+    /// Quests and miniquests.
+    #[cfg(feature = "rs3")]
+    pub mod quests;
+
+    /// Quickchat menus and phrases.
+    #[cfg(feature = "rs3")]
+    pub mod quickchat;
+    /// The skies drawn around scenes.
+    #[cfg(feature = "rs3")]
+    pub mod skyboxes;
+
+    /// Sound effects.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub mod sounds;
+
     /// Images displayed by the game client.
     pub mod sprites;
 
+    /// Configuration of spot animations.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub mod spotanim_configs;
+
     pub mod enums;
     pub mod structs;
-    #[cfg(feature = "osrs")]
+    /// Textures of the game's materials.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
     pub mod textures;
 
     /// Describes the properties of game surface tiles.
@@ -87,9 +163,13 @@ pub mod definitions {
     #[cfg(any(feature = "rs3", feature = "osrs"))]
     pub mod underlays;
 
+    /// Variables of the player, npcs, the world and other domains.
+    #[cfg(feature = "rs3")]
+    pub mod var_configs;
+
     pub mod varbit_configs;
 
-    #[cfg(feature = "rs3")]
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
     pub mod worldmaps;
 }
 
@@ -1,5 +1,8 @@
 /// Renders the ground colours.
 pub mod base;
+/// Draws the text of map labels with the game's fonts.
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+pub mod labels;
 /// Responsible for drawing lines - doors, fences, walls and so on.
 pub mod lines;
 /// Describes the shape of lines drawn by the map renderer.
@@ -25,7 +28,13 @@ use crate::definitions::flo::Flo;
 #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
 use crate::definitions::mapscenes::MapScene;
 #[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::definitions::textures;
+#[cfg(any(feature = "rs3", feature = "osrs"))]
 use crate::definitions::{overlays::Overlay, underlays::Underlay};
+#[cfg(feature = "rs3")]
+use crate::definitions::worldmaps::MapPastes;
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::renderers::map::labels::Labels;
 use crate::{
     cli::Config,
     definitions::{
@@ -37,7 +46,9 @@ use crate::{
 };
 
 pub struct RenderConfig {
-    /// -1 is the "real" world map.
+    /// The id of the "real" world map, -1.
+    ///
+    /// Other world maps are rendered under the id of their [`MapZone`](crate::definitions::worldmaps::MapZone).
     pub map_id: i32,
     /// Scale factor, this cannot be zero.
     pub scale: u32,
@@ -81,85 +92,174 @@ pub static CONFIG: RenderConfig = RenderConfig::fast();
 #[cfg(not(feature = "fast"))]
 pub static CONFIG: RenderConfig = RenderConfig::detailed();
 
-/// Entry point for the map renderer.
-pub fn render(config: &Config) -> CacheResult<()> {
-    const NAME: &str = "mapsquares";
+/// The definitions that are needed to render mapsquares.
+///
+/// These are the same for every world map, so they are loaded once and shared between them.
+struct Definitions {
+    locations: BTreeMap<u32, LocationConfig>,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    overlays: BTreeMap<u32, Overlay>,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    underlays: BTreeMap<u32, Underlay>,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    texture_colours: BTreeMap<u32, [u8; 3]>,
+    #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
+    mapscenes: BTreeMap<u32, MapScene>,
+    sprites: BTreeMap<(u32, u32), Sprite>,
+    #[cfg(feature = "legacy")]
+    flos: BTreeMap<u32, Flo>,
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    labels: Option<Labels>,
+}
 
-    let map_id = CONFIG.map_id;
-    for zoom in 2..=4 {
-        let path = path!(config.output / NAME / format!("{map_id}/{zoom}"));
+impl Definitions {
+    fn load(config: &Config) -> CacheResult<Self> {
+        let locations = LocationConfig::dump_all(config)?;
+
+        #[cfg(any(feature = "rs3", feature = "osrs"))]
+        let overlays = Overlay::dump_all(config)?;
+        #[cfg(any(feature = "rs3", feature = "osrs"))]
+        let underlays = Underlay::dump_all(config)?;
+
+        #[cfg(feature = "rs3")]
+        let texture_ids = overlays.values().filter_map(|overlay| overlay.texture_id.map(u32::from));
+        #[cfg(feature = "osrs")]
+        let texture_ids = overlays.values().filter_map(|overlay| overlay.texture.map(u32::from));
+        #[cfg(any(feature = "rs3", feature = "osrs"))]
+        let texture_colours = textures::average_colours(texture_ids.collect::<std::collections::BTreeSet<_>>(), config)?;
+
+        #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
+        let mapscenes = MapScene::dump_all(config)?;
+
+        #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
+        let sprites = sprites::dumps(
+            CONFIG.scale,
+            mapscenes.values().filter_map(|mapscene| mapscene.sprite_id).collect::<Vec<_>>(),
+            config,
+        )?;
 
-        fs::create_dir_all(&path).context(error::Io { path })?;
+        #[cfg(all(feature = "osrs", not(feature = "2009_1_shim")))]
+        let sprites = sprites::dumps(CONFIG.scale, vec![317], config)?; // 317 is the sprite named "mapscene"
+
+        #[cfg(feature = "legacy")]
+        let sprites: BTreeMap<(u32, u32), Sprite> = sprites::get_mapscenes(CONFIG.scale, config)?;
+
+        #[cfg(feature = "legacy")]
+        let flos = Flo::dump_all(config)?;
+
+        #[cfg(any(feature = "rs3", feature = "osrs"))]
+        let labels = if config.render_labels { Some(Labels::load(config)?) } else { None };
+
+        Ok(Self {
+            locations,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            overlays,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            underlays,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            texture_colours,
+            #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
+            mapscenes,
+            sprites,
+            #[cfg(feature = "legacy")]
+            flos,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            labels,
+        })
     }
+}
 
+/// Entry point for the map renderer.
+pub fn render(config: &Config) -> CacheResult<()> {
+    let definitions = Definitions::load(config)?;
     let iter = GroupMapSquareIterator::new(-1_i32..=1_i32, -1_i32..=1_i32, config)?;
+    render_map(config, CONFIG.map_id, iter, &definitions)
+}
 
-    inner_render(config, NAME, iter)?;
-
-    zoom::render_zoom_levels(config, NAME, map_id, -4..2, Color::ALPHA)?;
+/// Renders every world map other than the "real" one, such as dungeons and instances,
+/// by applying their [`MapPastes`] to the game map.
+#[cfg(feature = "rs3")]
+pub fn render_zones(config: &Config) -> CacheResult<()> {
+    let mut maps = MapPastes::dump_all(config)?.into_values().collect::<Vec<_>>();
+    maps.sort_unstable_by_key(|map| map.id);
+
+    let definitions = Definitions::load(config)?;
+    for map in maps {
+        let map_id = map.id as i32;
+        let iter = GroupMapSquareIterator::pasted(-1_i32..=1_i32, -1_i32..=1_i32, map, config)?;
+        render_map(config, map_id, iter, &definitions)?;
+    }
     Ok(())
 }
 
-// Separated for use in tests.
+/// Renders the mapsquares of `iter` into `mapsquares/{map_id}`, followed by all zoom levels.
+fn render_map(config: &Config, map_id: i32, iter: GroupMapSquareIterator, definitions: &Definitions) -> CacheResult<()> {
+    const NAME: &str = "mapsquares";
 
-fn inner_render(config: &Config, name: &str, iter: GroupMapSquareIterator) -> CacheResult<()> {
-    let location_definitions = LocationConfig::dump_all(config)?;
+    for zoom in 2..=4 {
+        let path = path!(config.output / NAME / format!("{map_id}/{zoom}"));
 
-    #[cfg(any(feature = "rs3", feature = "osrs"))]
-    let overlay_definitions = Overlay::dump_all(config)?;
-    #[cfg(any(feature = "rs3", feature = "osrs"))]
-    let underlay_definitions = Underlay::dump_all(config)?;
+        fs::create_dir_all(&path).context(error::Io { path })?;
 
-    #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
-    let mapscenes = MapScene::dump_all(config)?;
+        #[cfg(feature = "rs3")]
+        if config.render_water {
+            let path = path!(config.output / format!("{NAME}_water") / format!("{map_id}/{zoom}"));
 
-    #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
-    let sprites = sprites::dumps(
-        CONFIG.scale,
-        mapscenes.values().filter_map(|mapscene| mapscene.sprite_id).collect::<Vec<_>>(),
-        config,
-    )?;
+            fs::create_dir_all(&path).context(error::Io { path })?;
+        }
+    }
 
-    #[cfg(all(feature = "osrs", not(feature = "2009_1_shim")))]
-    let sprites = sprites::dumps(CONFIG.scale, vec![317], config)?; // 317 is the sprite named "mapscene"
+    inner_render(config, NAME, map_id, iter, definitions);
 
-    #[cfg(feature = "legacy")]
-    let sprites: BTreeMap<(u32, u32), Sprite> = sprites::get_mapscenes(CONFIG.scale, config)?;
+    zoom::render_zoom_levels(config, NAME, map_id, -4..2, Color::ALPHA)?;
+    #[cfg(feature = "rs3")]
+    if config.render_water {
+        zoom::render_zoom_levels(config, &format!("{NAME}_water"), map_id, -4..2, Color::ALPHA)?;
+    }
+    Ok(())
+}
 
-    #[cfg(feature = "legacy")]
-    let flos = Flo::dump_all(config)?;
+// Separated for use in tests.
 
+fn inner_render(config: &Config, name: &str, map_id: i32, iter: GroupMapSquareIterator, definitions: &Definitions) {
     iter.progress().par_bridge().for_each(|gsq| {
         render_tile(
             config,
             name,
+            map_id,
             gsq,
-            &location_definitions,
+            &definitions.locations,
             #[cfg(any(feature = "rs3", feature = "osrs"))]
-            &overlay_definitions,
+            &definitions.overlays,
             #[cfg(any(feature = "rs3", feature = "osrs"))]
-            &underlay_definitions,
+            &definitions.underlays,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            &definitions.texture_colours,
             #[cfg(any(feature = "rs3", feature = "2009_1_shim"))]
-            &mapscenes,
+            &definitions.mapscenes,
             #[cfg(feature = "legacy")]
-            &flos,
-            &sprites,
+            &definitions.flos,
+            &definitions.sprites,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            definitions.labels.as_ref(),
         );
     });
-    Ok(())
 }
 
 /// Responsible for rendering a single [`MapSquare`](crate::definitions::mapsquares::MapSquare).
 pub fn render_tile(
     config: &Config,
     name: &str,
+    map_id: i32,
     squares: GroupMapSquare,
     location_config: &BTreeMap<u32, LocationConfig>,
     #[cfg(any(feature = "rs3", feature = "osrs"))] overlay_definitions: &BTreeMap<u32, Overlay>,
     #[cfg(any(feature = "rs3", feature = "osrs"))] underlay_definitions: &BTreeMap<u32, Underlay>,
+    #[cfg(any(feature = "rs3", feature = "osrs"))] texture_colours: &BTreeMap<u32, [u8; 3]>,
     #[cfg(any(feature = "rs3", feature = "2009_1_shim"))] mapscenes: &BTreeMap<u32, MapScene>,
     #[cfg(feature = "legacy")] flos: &BTreeMap<u32, Flo>,
     sprites: &BTreeMap<(u32, u32), Sprite>,
+    #[cfg(any(feature = "rs3", feature = "osrs"))] labels: Option<&Labels>,
 ) {
     let func = |plane| {
         let backfill = Rgba(Color::ALPHA);
@@ -174,6 +274,8 @@ pub fn render_tile(
             underlay_definitions,
             #[cfg(any(feature = "rs3", feature = "osrs"))]
             overlay_definitions,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            texture_colours,
             #[cfg(feature = "legacy")]
             flos,
         );
@@ -187,9 +289,24 @@ pub fn render_tile(
             mapscenes,
             sprites,
         );
+        #[cfg(any(feature = "rs3", feature = "osrs"))]
+        if let Some(labels) = labels {
+            labels::put(plane, &mut img, &squares, location_config, labels);
+        }
         img
     };
 
+    #[cfg(feature = "rs3")]
+    if config.render_water {
+        let water_func = |plane| {
+            let mut img = RgbaImage::from_pixel(CONFIG.dim, CONFIG.dim, Rgba(Color::ALPHA));
+            base::put_water(plane, &mut img, &squares, underlay_definitions, overlay_definitions);
+            img
+        };
+        let imgs = [water_func(0), water_func(1), water_func(2), water_func(3)];
+        save_smallest(config, &format!("{name}_water"), map_id, squares.core_i(), squares.core_j(), imgs);
+    }
+
     let imgs = [func(0), func(1), func(2), func(3)];
 
     #[cfg(test)]
@@ -198,14 +315,12 @@ pub fn render_tile(
         imgs[0].save(filename).unwrap();
     }
 
-    save_smallest(config, name, squares.core_i(), squares.core_j(), imgs);
+    save_smallest(config, name, map_id, squares.core_i(), squares.core_j(), imgs);
 }
 
 type Img = ImageBuffer<Rgba<u8>, Vec<u8>>;
 
-pub fn save_smallest(config: &Config, name: &str, i: u8, j: u8, imgs: [Img; 4]) {
-    let map_id = CONFIG.map_id;
-
+pub fn save_smallest(config: &Config, name: &str, map_id: i32, i: u8, j: u8, imgs: [Img; 4]) {
     // SAFETY (2) these checks assure that...
     assert_eq!(CONFIG.dim % 4, 0);
     for img in &imgs {
@@ -311,8 +426,9 @@ pub fn render_bench() -> CacheResult<()> {
     fs::create_dir_all(path).unwrap();
     let coordinates: Vec<(u8, u8)> = iproduct!(45..55, 45..55).collect();
 
+    let definitions = Definitions::load(&config)?;
     let iter = GroupMapSquareIterator::new_only(-1_i32..=1_i32, -1_i32..=1_i32, coordinates, &config)?;
-    inner_render(&config, path, iter)?;
+    inner_render(&config, path, CONFIG.map_id, iter, &definitions);
 
     Ok(())
 }
@@ -330,7 +446,9 @@ mod map_tests {
         fs::create_dir_all(path).unwrap();
         let coordinates: Vec<(u8, u8)> = vec![(50, 50), (41, 63), (47, 50), (56, 49), (34, 66), (33, 72), (49, 108), (43, 46)];
 
+        let definitions = Definitions::load(&config)?;
         let iter = GroupMapSquareIterator::new_only(-1_i32..=1_i32, -1_i32..=1_i32, coordinates, &config)?;
-        inner_render(&config, path, iter)
+        inner_render(&config, path, CONFIG.map_id, iter, &definitions);
+        Ok(())
     }
 }
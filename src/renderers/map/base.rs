@@ -17,6 +17,7 @@ pub fn put(
     squares: &GroupMapSquare,
     #[cfg(any(feature = "rs3", feature = "osrs"))] underlay_definitions: &BTreeMap<u32, Underlay>,
     #[cfg(any(feature = "rs3", feature = "osrs"))] overlay_definitions: &BTreeMap<u32, Overlay>,
+    #[cfg(any(feature = "rs3", feature = "osrs"))] texture_colours: &BTreeMap<u32, [u8; 3]>,
     #[cfg(feature = "legacy")] flos: &BTreeMap<u32, Flo>,
 ) {
     if let Some(core) = squares.core() {
@@ -72,98 +73,37 @@ pub fn put(
                             }
                         }
 
+                        // Overlays without a usable colour are drawn with the average colour of their texture.
+                        #[cfg(feature = "rs3")]
+                        if let Some(id) = column[p].overlay_id {
+                            let ov = &overlay_definitions[&(id.checked_sub(1).expect("Not 100% sure about this invariant.") as u32)];
+                            if matches!(ov.primary_colour, None | Some([255, 0, 255])) {
+                                if let Some([red, green, blue]) = ov.texture_id.and_then(|texture_id| texture_colours.get(&(texture_id as u32))) {
+                                    let fill = Rgba([*red, *green, *blue, 255]);
+
+                                    tileshape::draw_overlay(column[p].shape.unwrap_or(0), CONFIG.tile_size, |(a, b)| unsafe {
+                                        debug_assert!(
+                                            (CONFIG.tile_size * x + a) < img.width() && (CONFIG.tile_size * (63u32 - y) + b) < img.height(),
+                                            "Index out of range."
+                                        );
+                                        img.unsafe_put_pixel(CONFIG.tile_size * x + a, CONFIG.tile_size * (63u32 - y) + b, fill)
+                                    })
+                                }
+                            }
+                        }
+
                         // The osrs client gets the average colour of textures here.
                         //
-                        // The map has tiles, whose overlay_id points to an overlay config whose texture property points to a texture whose
-                        // id points to a texture that has a field that refers to an index in a palette.
-                        //
-                        // To simplify the implementation, we simply hardcode these values.
-                        // They don't change much, so this should be OK.
+                        // The map has tiles, whose overlay_id points to an overlay config whose texture property points to a texture,
+                        // whose config stores the average colour of its sprite as HSL. See `TextureConfig::average_colour`.
                         #[cfg(feature = "osrs")]
                         if let Some(id) = column[p].overlay_id {
-                            if let Some(texture_id) =
-                                &overlay_definitions[&(id.checked_sub(1).expect("Not 100% sure about this invariant.") as u32)].texture
+                            if let Some([red, green, blue]) = overlay_definitions
+                                [&(id.checked_sub(1).expect("Not 100% sure about this invariant.") as u32)]
+                                .texture
+                                .and_then(|texture_id| texture_colours.get(&(texture_id as u32)))
                             {
-                                let (red, green, blue) = match texture_id {
-                                    1 => (104, 125, 169),   // 6847913
-                                    2 => (70, 70, 70),      // 4605510
-                                    3 => (75, 43, 25),      // 4926233
-                                    11 => (62, 62, 62),     // 4079166
-                                    15 => (102, 102, 102),  // 6710886
-                                    23 => (48, 42, 29),     // 3156509
-                                    25 => (50, 110, 87),    // 3305047
-                                    31 => (240, 124, 21),   // 15760405
-                                    35 => (125, 115, 68),   // 8221508
-                                    43 => (89, 89, 89),     // 5855577
-                                    46 => (84, 84, 84),     // 5526612
-                                    51 => (126, 81, 42),    // 8278314
-                                    91 => (204, 204, 204),  // 13421772
-                                    119 => (48, 48, 48),    // 3158064
-                                    120 => (48, 42, 29),    // 3156509
-                                    121 => (105, 84, 74),   // 6902858
-                                    122 => (105, 84, 74),   // 6902858
-                                    125 => (54, 49, 41),    // 3551529
-                                    126 => (54, 49, 41),    // 3551529
-                                    130 => (91, 116, 170),  // 5993642
-                                    131 => (87, 111, 162),  // 5730210
-                                    132 => (83, 105, 152),  // 5466520
-                                    133 => (74, 93, 137),   // 4873609
-                                    134 => (71, 89, 132),   // 4675972
-                                    135 => (102, 139, 175), // 6720431
-                                    136 => (98, 136, 173),  // 6457517
-                                    137 => (91, 131, 170),  // 5997482
-                                    138 => (87, 125, 162),  // 5733794
-                                    140 => (91, 139, 170),  // 5999530
-                                    141 => (87, 132, 162),  // 5735586
-                                    142 => (85, 128, 156),  // 5603484
-                                    143 => (83, 125, 152),  // 5471640
-                                    144 => (74, 110, 137),  // 4877961
-                                    145 => (118, 119, 183), // 7763895
-                                    146 => (102, 103, 175), // 6711215
-                                    147 => (91, 94, 170),   // 5988010
-                                    148 => (83, 84, 152),   // 5461144
-                                    149 => (80, 81, 147),   // 5263763
-                                    150 => (124, 118, 161), // 8156833
-                                    151 => (115, 108, 155), // 7564443
-                                    152 => (107, 100, 143), // 7038095
-                                    153 => (103, 97, 138),  // 6775178
-                                    154 => (97, 90, 129),   // 6380161
-                                    155 => (113, 114, 158), // 7434910
-                                    156 => (108, 110, 155), // 7106203
-                                    157 => (103, 105, 148), // 6777236
-                                    158 => (97, 98, 138),   // 6382218
-                                    159 => (87, 88, 124),   // 5724284
-                                    160 => (93, 143, 192),  // 6131648
-                                    161 => (81, 135, 188),  // 5343164
-                                    162 => (75, 131, 186),  // 4948922
-                                    163 => (71, 122, 172),  // 4684460
-                                    164 => (64, 110, 155),  // 4222619
-                                    165 => (136, 151, 173), // 8951725
-                                    166 => (126, 142, 167), // 8294055
-                                    167 => (118, 135, 161), // 7767969
-                                    168 => (108, 127, 155), // 7110555
-                                    169 => (100, 117, 143), // 6583695
-                                    170 => (76, 148, 177),  // 5018801
-                                    171 => (68, 139, 177),  // 4492209
-                                    172 => (65, 133, 170),  // 4294058
-                                    173 => (61, 123, 160),  // 4029344
-                                    174 => (54, 110, 147),  // 3567251
-                                    175 => (116, 129, 129), // 7635329
-                                    176 => (105, 119, 119), // 6911863
-                                    180 => (102, 151, 175), // 6723503
-                                    181 => (91, 146, 170),  // 6001322
-                                    182 => (85, 135, 156),  // 5605276
-                                    183 => (83, 129, 152),  // 5472664
-                                    184 => (74, 116, 137),  // 4879497
-                                    185 => (118, 135, 161), // 7767969
-                                    186 => (108, 127, 155), // 7110555
-                                    187 => (100, 117, 143), // 6583695
-                                    188 => (93, 109, 134),  // 6122886
-                                    189 => (87, 102, 124),  // 5727868
-                                    208 => (32, 47, 61),    // 2109245
-                                    unknown => unimplemented!("unimplemented texture id {}", unknown),
-                                };
-                                let fill = Rgba([red, green, blue, 255]);
+                                let fill = Rgba([*red, *green, *blue, 255]);
 
                                 tileshape::draw_overlay(column[p].shape.unwrap_or(0), CONFIG.tile_size, |(a, b)| unsafe {
                                     debug_assert!(
@@ -257,6 +197,49 @@ pub fn put(
     }
 }
 
+/// Applies ground colouring of the underwater tiles to the base image.
+///
+/// Unlike [`put`], underlay colours are not blended with their surroundings.
+#[cfg(feature = "rs3")]
+pub fn put_water(
+    plane: usize,
+    img: &mut RgbaImage,
+    squares: &GroupMapSquare,
+    underlay_definitions: &BTreeMap<u32, Underlay>,
+    overlay_definitions: &BTreeMap<u32, Overlay>,
+) {
+    let Some(Ok(water_tiles)) = squares.core().and_then(|core| core.water_tiles()) else {
+        return;
+    };
+
+    for (x, y) in itertools::iproduct!(0..64u32, 0..64u32) {
+        let tile = &water_tiles[[plane, x as usize, y as usize]];
+
+        let underlay_colour = tile
+            .underlay_id
+            .and_then(|id| underlay_definitions.get(&(id.checked_sub(1)? as u32)))
+            .and_then(|underlay| underlay.colour);
+        if let Some([red, green, blue]) = underlay_colour {
+            let fill = Rgba([red, green, blue, 255u8]);
+            tileshape::draw_underlay(tile.shape, CONFIG.tile_size, |(a, b)| {
+                img.put_pixel(CONFIG.tile_size * x + a, CONFIG.tile_size * (63u32 - y) + b, fill)
+            })
+        }
+
+        let overlay_colour = tile
+            .overlay_id
+            .and_then(|id| overlay_definitions.get(&(id.checked_sub(1)? as u32)))
+            .and_then(|overlay| overlay.primary_colour)
+            .filter(|colour| colour != &[255, 0, 255]);
+        if let Some([red, green, blue]) = overlay_colour {
+            let fill = Rgba([red, green, blue, 255u8]);
+            tileshape::draw_overlay(tile.shape.unwrap_or(0), CONFIG.tile_size, |(a, b)| {
+                img.put_pixel(CONFIG.tile_size * x + a, CONFIG.tile_size * (63u32 - y) + b, fill)
+            })
+        }
+    }
+}
+
 /// Averages out the [`Underlay`] colours, with a range specified by [`INTERP`].
 #[cfg(any(feature = "rs3", feature = "osrs"))]
 fn get_underlay_colour(
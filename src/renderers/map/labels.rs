@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use image::{Rgba, RgbaImage};
+use rs3cache_backend::error::CacheResult;
+
+use crate::{
+    cli::Config,
+    definitions::{fonts::Font, location_configs::LocationConfig, maplabel_configs::MapLabelConfig, mapsquares::GroupMapSquare},
+    renderers::map::CONFIG,
+};
+
+/// The fonts used for the [`font_size`](MapLabelConfig::font_size)s of map labels.
+#[cfg(feature = "rs3")]
+const FONT_IDS: [u32; 3] = [494, 495, 496];
+#[cfg(feature = "osrs")]
+const FONT_IDS: [u32; 3] = [1442, 1445, 1447];
+
+/// Everything needed to draw the text of [`MapLabelConfig`]s.
+pub struct Labels {
+    pub maplabels: BTreeMap<u32, MapLabelConfig>,
+    pub fonts: Vec<Font>,
+}
+
+impl Labels {
+    pub fn load(config: &Config) -> CacheResult<Self> {
+        let maplabels = MapLabelConfig::dump_all(config)?;
+        let fonts = FONT_IDS
+            .into_iter()
+            .map(|font_id| Font::load(font_id, config))
+            .collect::<CacheResult<Vec<_>>>()?;
+        Ok(Self { maplabels, fonts })
+    }
+
+    fn font(&self, font_size: Option<u8>) -> &Font {
+        let index = font_size.unwrap_or(0) as usize;
+        &self.fonts[index.min(self.fonts.len() - 1)]
+    }
+}
+
+/// Draws the text of map labels, centered on the location referring to them.
+pub fn put(plane: usize, img: &mut RgbaImage, squares: &GroupMapSquare, location_config: &BTreeMap<u32, LocationConfig>, labels: &Labels) {
+    for loc in squares.all_locations_iter() {
+        if !loc.plane.matches(&(plane as u8)) {
+            continue;
+        }
+
+        let Some(label) = location_config
+            .get(&loc.id)
+            .and_then(LocationConfig::maplabel_id)
+            .and_then(|label_id| labels.maplabels.get(&(label_id as u32)))
+        else {
+            continue;
+        };
+        let Some(text) = &label.text else { continue };
+
+        let [r, g, b] = label.label_colour_1.unwrap_or([255, 255, 255]);
+        let sprite = labels.font(label.font_size).render(text, Rgba([r, g, b, 255]));
+
+        let center_a = CONFIG.tile_size as i32 * ((loc.i as i32 - squares.core_i() as i32) * 64 + loc.x as i32) + CONFIG.tile_size as i32 / 2;
+        let center_b = CONFIG.tile_size as i32 * (63 - (loc.j as i32 - squares.core_j() as i32) * 64 - loc.y as i32) + CONFIG.tile_size as i32 / 2;
+
+        let offset_a = center_a - sprite.width() as i32 / 2;
+        let offset_b = center_b - sprite.height() as i32 / 2;
+
+        for (x, y, pixel) in sprite.enumerate_pixels() {
+            let (a, b) = (offset_a + x as i32, offset_b + y as i32);
+            if pixel[3] != 0 && (0..img.width() as i32).contains(&a) && (0..img.height() as i32).contains(&b) {
+                img.put_pixel(a as u32, b as u32, *pixel);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rs3"))]
+mod tests {
+    use itertools::iproduct;
+
+    use super::*;
+    use crate::definitions::mapsquares::GroupMapSquareIterator;
+
+    #[test]
+    fn draw_some_labels() -> CacheResult<()> {
+        let config = Config::env();
+        let labels = Labels::load(&config)?;
+        let location_config = LocationConfig::dump_all(&config)?;
+
+        let coordinates = iproduct!(48..=52, 48..=52).collect::<Vec<(u8, u8)>>();
+        let drawn = GroupMapSquareIterator::new_only(-1_i32..=1_i32, -1_i32..=1_i32, coordinates, &config)?.any(|squares| {
+            let mut img = RgbaImage::new(CONFIG.dim, CONFIG.dim);
+            put(0, &mut img, &squares, &location_config, &labels);
+            img.pixels().any(|pixel| pixel[3] != 0)
+        });
+        assert!(drawn, "no labels were drawn around Lumbridge and Varrock");
+        Ok(())
+    }
+}
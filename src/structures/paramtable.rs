@@ -1,11 +1,14 @@
-use std::{collections::BTreeMap, iter};
+use std::{collections::BTreeMap, panic::Location};
 
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 #[cfg(feature = "pyo3")]
 use pyo3::{exceptions::PyKeyError, prelude::*};
-use rs3cache_backend::buf::{BufExtra, JString};
+use rs3cache_backend::buf::{BufExtra, JString, ReadError};
 use serde::Serialize;
 
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+use crate::{definitions::params::ParamConfig, types::typed_value::TypedValue};
+
 /// [`LocationConfig`](crate::definitions::location_configs::LocationConfig)s,
 /// items and
 /// [`NpcConfig`](crate::definitions::npc_configs::NpcConfig)s can have additional mapping of keys to properties.
@@ -15,27 +18,67 @@ use serde::Serialize;
 pub struct ParamTable {
     /// Key:Value pairs of additional properties.
     pub params: BTreeMap<u32, Param>,
+    /// The values of `params`, interpreted according to their [`ParamConfig`]. Filled in by [`ParamTable::resolve`].
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typed: Option<BTreeMap<u32, TypedValue>>,
 }
 
 impl ParamTable {
     /// Constructor for [`ParamTable`]
-    pub fn deserialize(buffer: &mut Bytes) -> Self {
-        let count = buffer.get_u8().into();
-        let params = iter::repeat_with(|| Self::sub_deserialize(buffer)).take(count).collect();
-        Self { params }
+    pub fn deserialize(buffer: &mut Bytes) -> Result<Self, ReadError> {
+        let count = buffer.try_get_u8()?;
+        let params = (0..count).map(|_| Self::sub_deserialize(buffer)).collect::<Result<_, _>>()?;
+        Ok(Self {
+            params,
+            #[cfg(any(feature = "rs3", feature = "osrs"))]
+            typed: None,
+        })
+    }
+
+    /// Interprets all values according to their [`ParamConfig`], storing them in `typed`.
+    ///
+    /// Only the keys this table has are resolved; use [`ParamTable::get_or_default`] for the defaults of other keys.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub fn resolve(&mut self, configs: &BTreeMap<u32, ParamConfig>) {
+        let typed = self
+            .params
+            .iter()
+            .map(|(&key, value)| (key, value.typed(configs.get(&key))))
+            .collect();
+        self.typed = Some(typed);
+    }
+
+    /// Returns the value of `key`, or the default of its [`ParamConfig`] if this table does not have it.
+    #[cfg(any(feature = "rs3", feature = "osrs"))]
+    pub fn get_or_default(&self, key: u32, configs: &BTreeMap<u32, ParamConfig>) -> Option<Param> {
+        self.params.get(&key).cloned().or_else(|| {
+            let config = configs.get(&key)?;
+            config
+                .default_string
+                .clone()
+                .map(Param::String)
+                .or(config.default_int.map(Param::Integer))
+        })
     }
 
-    fn sub_deserialize(buffer: &mut Bytes) -> (u32, Param) {
-        let r#type = buffer.get_u8();
+    fn sub_deserialize(buffer: &mut Bytes) -> Result<(u32, Param), ReadError> {
+        let r#type = buffer.try_get_u8()?;
 
-        let key = buffer.get_uint(3) as u32;
+        let key = buffer.try_get_uint(3)? as u32;
 
         let value = match r#type {
-            0 => Param::Integer(buffer.get_i32()),
-            1 => Param::String(buffer.get_string()),
-            other => unimplemented!("Cannot decode unknown type {}", other),
+            0 => Param::Integer(buffer.try_get_i32()?),
+            1 => Param::String(buffer.try_get_string()?),
+            other => {
+                return Err(ReadError::Unsupported {
+                    location: Location::caller(),
+                    what: "param",
+                    reason: format!("unknown value type {other} for key {key}"),
+                })
+            }
         };
-        (key, value)
+        Ok((key, value))
     }
 }
 
@@ -61,6 +104,17 @@ pub enum Param {
     String(JString<Bytes>),
 }
 
+#[cfg(any(feature = "rs3", feature = "osrs"))]
+impl Param {
+    /// Interprets this value according to the type of `config`, if it is known.
+    pub fn typed(&self, config: Option<&ParamConfig>) -> TypedValue {
+        match self {
+            Param::String(value) => TypedValue::String(value.clone()),
+            &Param::Integer(value) => TypedValue::new(value, config.map(ParamConfig::script_type).unwrap_or_default()),
+        }
+    }
+}
+
 #[cfg(feature = "pyo3")]
 impl<'py> IntoPyObject<'py> for Param {
     type Target = PyAny;
@@ -0,0 +1,75 @@
+//! Minimal [GeoJSON](https://geojson.org/) types, for use with map viewers.
+//!
+//! Positions are in game coordinates, like the rendered map tiles: the tile at `(x, y)` spans `x..x + 1` and `y..y + 1`.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// A position, as `[x, y]`.
+pub type Position = [f64; 2];
+
+/// A collection of [`Feature`]s.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(tag = "type")]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+}
+
+/// A [`Geometry`] and its properties.
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub struct Feature {
+    pub geometry: Geometry,
+    pub properties: Map<String, Value>,
+}
+
+/// The shape of a [`Feature`].
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum Geometry {
+    Point(Position),
+    /// A list of closed rings, the first of which is the exterior.
+    Polygon(Vec<Vec<Position>>),
+}
+
+impl Geometry {
+    /// A polygon with `points` as its exterior, closing the ring if needed.
+    pub fn polygon(mut points: Vec<Position>) -> Self {
+        if let (Some(&first), Some(&last)) = (points.first(), points.last()) {
+            if first != last {
+                points.push(first);
+            }
+        }
+        Self::Polygon(vec![points])
+    }
+
+    /// A rectangle spanning the tiles from `(west, south)` to `(east, north)`, inclusive.
+    pub fn rectangle(west: u32, south: u32, east: u32, north: u32) -> Self {
+        let (west, south, east, north) = (west as f64, south as f64, east as f64 + 1.0, north as f64 + 1.0);
+        Self::polygon(vec![[west, south], [east, south], [east, north], [west, north]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygon_closes_its_ring() {
+        let polygon = Geometry::polygon(vec![[0.0, 0.0], [2.0, 0.0], [0.0, 2.0]]);
+        assert_eq!(polygon, Geometry::Polygon(vec![vec![[0.0, 0.0], [2.0, 0.0], [0.0, 2.0], [0.0, 0.0]]]));
+    }
+
+    #[test]
+    fn polygon_keeps_a_closed_ring() {
+        let ring = vec![[0.0, 0.0], [2.0, 0.0], [0.0, 2.0], [0.0, 0.0]];
+        assert_eq!(Geometry::polygon(ring.clone()), Geometry::Polygon(vec![ring]));
+    }
+
+    #[test]
+    fn rectangle_covers_whole_tiles() {
+        let rectangle = Geometry::rectangle(10, 20, 12, 20);
+        let ring = vec![[10.0, 20.0], [13.0, 20.0], [13.0, 21.0], [10.0, 21.0], [10.0, 20.0]];
+        assert_eq!(rectangle, Geometry::Polygon(vec![ring]));
+    }
+}
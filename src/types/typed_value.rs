@@ -0,0 +1,206 @@
+//! Values interpreted according to the type the client gives them.
+
+use bytes::Bytes;
+#[cfg(feature = "pyo3")]
+use pyo3::prelude::*;
+use rs3cache_backend::buf::JString;
+use serde::Serialize;
+
+use crate::types::coordinate::Coordinate;
+
+/// The types of values that are interpreted by [`TypedValue`].
+///
+/// The client refers to these by either a character or an id.
+#[derive(Debug, PartialEq, Eq, Hash, Serialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptType {
+    #[default]
+    Integer,
+    String,
+    Boolean,
+    Coordinate,
+    Item,
+    Npc,
+    Location,
+    Stat,
+    Enum,
+    Struct,
+    Component,
+}
+
+impl ScriptType {
+    /// Looks up a type by the character the client uses for it.
+    /// Types that are not interpreted are treated as integers.
+    pub fn from_char(c: u8) -> Self {
+        match c {
+            b's' => Self::String,
+            b'1' => Self::Boolean,
+            b'c' => Self::Coordinate,
+            b'o' | b'O' => Self::Item,
+            b'n' => Self::Npc,
+            b'l' => Self::Location,
+            b'S' => Self::Stat,
+            b'g' => Self::Enum,
+            b'J' => Self::Struct,
+            b'I' => Self::Component,
+            _ => Self::Integer,
+        }
+    }
+
+    /// The name of this type, as it is serialized.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::String => "string",
+            Self::Boolean => "boolean",
+            Self::Coordinate => "coordinate",
+            Self::Item => "item",
+            Self::Npc => "npc",
+            Self::Location => "location",
+            Self::Stat => "stat",
+            Self::Enum => "enum",
+            Self::Struct => "struct",
+            Self::Component => "component",
+        }
+    }
+
+    /// Looks up a type by its id.
+    /// Types that are not interpreted are treated as integers.
+    pub fn from_id(id: u16) -> Self {
+        match id {
+            1 => Self::Boolean,
+            9 => Self::Component,
+            13 | 33 => Self::Item,
+            17 => Self::Stat,
+            22 => Self::Coordinate,
+            26 => Self::Enum,
+            30 => Self::Location,
+            32 => Self::Npc,
+            36 => Self::String,
+            73 => Self::Struct,
+            _ => Self::Integer,
+        }
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl<'py> IntoPyObject<'py> for ScriptType {
+    type Target = pyo3::types::PyString;
+    type Output = Bound<'py, Self::Target>;
+    type Error = std::convert::Infallible;
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.as_str().into_pyobject(py)
+    }
+}
+
+/// A value, interpreted according to its [`ScriptType`].
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum TypedValue {
+    Integer(i32),
+    String(JString<Bytes>),
+    Boolean(bool),
+    Coordinate(Coordinate),
+    Item(u32),
+    Npc(u32),
+    Location(u32),
+    Stat(u32),
+    Enum(u32),
+    Struct(u32),
+    Component(u32),
+    /// An id or coordinate of `-1`, meaning there is none.
+    Null,
+}
+
+impl TypedValue {
+    /// Interprets an integer as a `ty`.
+    pub fn new(value: i32, ty: ScriptType) -> Self {
+        let id = |variant: fn(u32) -> Self| u32::try_from(value).map_or(Self::Null, variant);
+
+        match ty {
+            ScriptType::Integer | ScriptType::String => Self::Integer(value),
+            ScriptType::Boolean => Self::Boolean(value == 1),
+            ScriptType::Coordinate if value == -1 => Self::Null,
+            ScriptType::Coordinate => Coordinate::try_from(value as u32).map_or(Self::Integer(value), Self::Coordinate),
+            ScriptType::Item => id(Self::Item),
+            ScriptType::Npc => id(Self::Npc),
+            ScriptType::Location => id(Self::Location),
+            ScriptType::Stat => id(Self::Stat),
+            ScriptType::Enum => id(Self::Enum),
+            ScriptType::Struct => id(Self::Struct),
+            ScriptType::Component => id(Self::Component),
+        }
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl<'py> IntoPyObject<'py> for &TypedValue {
+    type Target = PyAny;
+    type Output = Bound<'py, Self::Target>;
+    type Error = PyErr;
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        match self {
+            TypedValue::Integer(val) => Ok(val.into_pyobject(py)?.into_any()),
+            TypedValue::String(val) => Ok(val.as_ref().into_pyobject(py)?.into_any()),
+            TypedValue::Boolean(val) => Ok(val.into_pyobject(py)?.to_owned().into_any()),
+            TypedValue::Coordinate(Coordinate { plane, x, y }) => Ok((plane, x, y).into_pyobject(py)?.into_any()),
+            TypedValue::Item(val)
+            | TypedValue::Npc(val)
+            | TypedValue::Location(val)
+            | TypedValue::Stat(val)
+            | TypedValue::Enum(val)
+            | TypedValue::Struct(val)
+            | TypedValue::Component(val) => Ok(val.into_pyobject(py)?.into_any()),
+            TypedValue::Null => Ok(py.None().into_bound(py)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn char_and_id_agree() {
+        let pairs = [
+            (b'1', 1),
+            (b'I', 9),
+            (b'O', 13),
+            (b'S', 17),
+            (b'c', 22),
+            (b'g', 26),
+            (b'l', 30),
+            (b'n', 32),
+            (b'o', 33),
+            (b's', 36),
+            (b'J', 73),
+        ];
+        for (c, id) in pairs {
+            assert_ne!(ScriptType::from_char(c), ScriptType::Integer, "{}", c as char);
+            assert_eq!(ScriptType::from_char(c), ScriptType::from_id(id), "{} and {id}", c as char);
+        }
+    }
+
+    #[test]
+    fn uninterpreted_types_are_integers() {
+        assert_eq!(ScriptType::from_char(b'i'), ScriptType::Integer);
+        assert_eq!(ScriptType::from_id(0), ScriptType::Integer);
+    }
+
+    #[test]
+    fn new_interprets_by_type() {
+        assert_eq!(TypedValue::new(5, ScriptType::Integer), TypedValue::Integer(5));
+        assert_eq!(TypedValue::new(1, ScriptType::Boolean), TypedValue::Boolean(true));
+        assert_eq!(TypedValue::new(0, ScriptType::Boolean), TypedValue::Boolean(false));
+        assert_eq!(TypedValue::new(4151, ScriptType::Item), TypedValue::Item(4151));
+        assert_eq!(TypedValue::new(7, ScriptType::Struct), TypedValue::Struct(7));
+        assert_eq!(TypedValue::new(-1, ScriptType::Npc), TypedValue::Null);
+
+        let packed = 1 << 28 | 3200 << 14 | 3200;
+        assert_eq!(
+            TypedValue::new(packed, ScriptType::Coordinate),
+            TypedValue::Coordinate(Coordinate { plane: 1, x: 3200, y: 3200 })
+        );
+        assert_eq!(TypedValue::new(-1, ScriptType::Coordinate), TypedValue::Null);
+    }
+}
@@ -1,7 +1,11 @@
+use std::collections::BTreeMap;
+
 #[cfg(feature = "pyo3")]
 use pyo3::prelude::*;
 use serde::Serialize;
 
+use crate::definitions::varbit_configs::VarbitConfig;
+
 /// A bitmapping of a [`Varp`]
 #[cfg_attr(feature = "pyo3", pyclass(frozen, from_py_object))]
 #[derive(Serialize, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -105,3 +109,99 @@ impl<'py> IntoPyObject<'py> for VarpOrVarbit {
         }
     }
 }
+
+/// The values of a player's variables.
+///
+/// Variables that are not present are zero, like they are in the client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VarState {
+    /// The values of [`Varp`]s, by id.
+    pub varps: BTreeMap<u16, i32>,
+    /// The values of [`Varbit`]s, by id. These take precedence over the bits of the varp they map onto.
+    pub varbits: BTreeMap<u16, i32>,
+}
+
+impl VarState {
+    /// Constructor for [`VarState`].
+    pub fn new(varps: BTreeMap<u16, i32>, varbits: BTreeMap<u16, i32>) -> Self {
+        Self { varps, varbits }
+    }
+
+    /// Returns the value of `var`, using `varbit_configs` to find the bits a varbit maps onto.
+    pub fn get(&self, var: VarpOrVarbit, varbit_configs: &BTreeMap<u32, VarbitConfig>) -> i32 {
+        match var {
+            VarpOrVarbit::Varp(id) => self.varps.get(&id).copied().unwrap_or(0),
+            VarpOrVarbit::Varbit(id) => {
+                if let Some(&value) = self.varbits.get(&id) {
+                    return value;
+                }
+                let Some(config) = varbit_configs.get(&(id as u32)) else { return 0 };
+                let varp = self.varps.get(&config.index).copied().unwrap_or(0) as u32;
+                // A varp has only 32 bits, however many the varbit claims.
+                let width = (config.most_significant_bit.saturating_sub(config.least_significant_bit) as u32 + 1).min(32);
+                let mask = u32::MAX.checked_shr(32 - width).unwrap_or(0);
+                (varp.checked_shr(config.least_significant_bit as u32).unwrap_or(0) & mask) as i32
+            }
+        }
+    }
+
+    /// Picks the id that `var` selects from `ids`, like the client does for morphing locations and npcs.
+    ///
+    /// Values outside of `ids` select `default` instead.
+    pub fn morph<T: Copy>(
+        &self,
+        var: VarpOrVarbit,
+        ids: &[Option<T>],
+        default: Option<T>,
+        varbit_configs: &BTreeMap<u32, VarbitConfig>,
+    ) -> Option<T> {
+        let value = self.get(var, varbit_configs);
+        match usize::try_from(value).ok().and_then(|index| ids.get(index)) {
+            Some(&id) => id,
+            None => default,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn morph_falls_back_to_default() {
+        let varbit_configs = BTreeMap::from([(
+            7,
+            VarbitConfig {
+                id: 7,
+                index: 3,
+                least_significant_bit: 4,
+                most_significant_bit: 5,
+                ..Default::default()
+            },
+        )]);
+        let state = VarState::new(BTreeMap::from([(3, 0b10_0000)]), BTreeMap::new());
+        let ids = [Some(10), None, Some(12)];
+
+        assert_eq!(state.get(VarpOrVarbit::Varbit(7), &varbit_configs), 2);
+        assert_eq!(state.morph(VarpOrVarbit::Varbit(7), &ids, Some(99), &varbit_configs), Some(12));
+        assert_eq!(state.morph(VarpOrVarbit::Varp(3), &ids, Some(99), &varbit_configs), Some(99));
+        assert_eq!(state.morph(VarpOrVarbit::Varp(4), &ids, Some(99), &varbit_configs), Some(10));
+    }
+
+    #[test]
+    fn wide_varbit_reads_the_whole_varp() {
+        let varbit_configs = BTreeMap::from([(
+            8,
+            VarbitConfig {
+                id: 8,
+                index: 3,
+                least_significant_bit: 0,
+                most_significant_bit: 40,
+                ..Default::default()
+            },
+        )]);
+        let state = VarState::new(BTreeMap::from([(3, -2)]), BTreeMap::new());
+
+        assert_eq!(state.get(VarpOrVarbit::Varbit(8), &varbit_configs), -2);
+    }
+}